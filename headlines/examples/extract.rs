@@ -0,0 +1,43 @@
+//! Prints the readable content of a saved article page.
+//!
+//! cargo run -p headlines --example extract -- page.html [https://original/url]
+
+use headlines::{extract, plain_text, Block};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let path = match args.next() {
+        Some(path) => path,
+        None => {
+            eprintln!("usage : extract <page.html> [base url]");
+            std::process::exit(2);
+        }
+    };
+    let html = match std::fs::read_to_string(&path) {
+        Ok(html) => html,
+        Err(e) => {
+            eprintln!("failed reading {} : {}",path,e);
+            std::process::exit(1);
+        }
+    };
+    let document = extract(&html, args.next().as_deref());
+
+    println!("# {}",document.title);
+    if let Some(byline) = &document.byline {
+        println!("by {}",byline);
+    }
+    if let Some(image) = &document.lead_image {
+        println!("[image] {}",image);
+    }
+    for block in &document.blocks {
+        println!();
+        match block {
+            Block::Heading { level, spans } => println!("{} {}","#".repeat(*level as usize + 1),plain_text(spans)),
+            Block::Paragraph(spans) => println!("{}",plain_text(spans)),
+            Block::Quote(spans) => println!("> {}",plain_text(spans)),
+            Block::ListItem(spans) => println!("- {}",plain_text(spans)),
+            Block::Code(code) => println!("{}",code),
+            Block::Image { src, alt } => println!("[image] {} {}",alt,src)
+        }
+    }
+}
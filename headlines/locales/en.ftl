@@ -0,0 +1,376 @@
+# Messages of the interface, see src/l10n.rs.
+# Countries, languages, categories and sort orders fall back to the newsapi names.
+
+## Common
+
+loading = Loading ⏳
+loading-more = Loading more articles ⏳
+feed-end = The end of the feed, there are no more articles
+feed-full = The end of the feed, it keeps its { $count } latest articles
+cancel = Cancel
+retry = Retry
+refresh = Refresh
+apply = Apply
+reset = Reset
+clear = Clear
+defaults = Defaults
+dismiss = Dismiss
+quit = Quit
+settings = Settings
+read-more = read more 🔜
+open-reader = Open in the reader
+open-browser = Open in the browser
+save = Save for later
+unsave = Remove from saved
+mark-read = Mark as read
+mark-unread = Mark as unread
+mark-all-read = Mark all read
+show-images = Show images
+hide-images = Hide images
+articles = { $count ->
+    [one] { $count } article
+   *[other] { $count } articles
+}
+
+## Errors
+
+error-no-api-key = No API key set
+error-network = Network error: { $message }
+error-response = Unexpected response: { $message }
+
+## Invalid settings
+
+invalid-locale = unknown language { $locale }, use one of { $locales }
+invalid-no-theme = no theme
+invalid-font = unknown font { $font }
+invalid-text-size = text sizes must be between 6 and 48
+invalid-zoom = zoom must be between { $min }% and { $max }%
+invalid-font-file = { $file } is not a ttf, otf or ttc font file
+invalid-thumbnail-cache = the thumbnail cache must be between 1 and 1024 MB
+invalid-history-days = history must be kept between 1 and 365 days
+invalid-timeout = timeout must be between 1 and 300 seconds
+invalid-base-url-scheme = the base url must be http or https
+invalid-base-url = invalid base url : { $error }
+invalid-proxy = invalid proxy : { $error }
+invalid-date = { $date } is not a date like 2022-09-30
+invalid-date-order = the "from" date is after the "to" date
+invalid-profile-name-length = profile names are 1 to 32 characters long
+invalid-profile-name = profile names only use letters, digits, - and _
+invalid-theme-name-length = theme names are 1 to 32 characters long
+invalid-theme-name = theme names only use letters, digits, - and _
+invalid-binding = { $binding } : { $error }
+invalid-bound-twice = { $shortcut } is bound twice
+invalid-modifier = unknown modifier { $modifier }, use Ctrl, Alt or Shift
+invalid-shift = Shift only goes with named keys, type the shifted character instead
+invalid-modified-key = Ctrl and Alt only go with letters, digits and named keys
+invalid-key = expected a character or one of { $keys }, not { $key }
+invalid-muted-keyword = "{ $keyword }" : { $error }
+invalid-rule-name = the rule needs a name
+invalid-rule-pattern = the rule matches nothing
+invalid-rule-empty-phrase = empty quoted phrase
+invalid-rule-missing-close = missing ")"
+invalid-rule-unexpected-close = unexpected ")"
+invalid-rule-early-end = the expression ends too early
+invalid-rule-operator = { $operator } needs words on both sides
+invalid-rule-too-deep = parentheses and NOT nest { $max } deep at most
+invalid-rule-too-long = the expression has more than { $max } words and operators
+
+## Top panel
+
+theme-to-light = Theme : { $theme }, click for the light one
+theme-to-dark = Theme : { $theme }, click for the dark one
+auto-refresh = Refresh automatically
+auto-refresh-never = Never
+auto-refresh-every = Every { $minutes } minutes
+auto-refresh-off = Automatic refresh is off
+auto-refresh-on = Refreshing every { $minutes } minutes
+offline-on = Offline, reading the archive. Click to go back online
+offline-off = Online. Click to read the archive offline
+hide-images-hint = Text only, no images
+mute-filters = Mute filters
+advanced-search = Advanced search
+search-hint = 🔍 Search
+layout = Layout
+layout-menu = Layout : { $layout }, grouped by { $group }
+group-by = Group by
+
+## Feed
+
+tab-top = Top
+tab-archive = ✈ Archive ({ $count })
+tab-saved = ⭐ Saved ({ $count })
+tab-alerts = 🔔 Alerts
+tab-alerts-unread = 🔔 Alerts ({ $count })
+tab-alerts-hint = Articles matched by the watch rules
+any-language = Any language
+feed-language-hint = Language of the search results
+feed-country-hint = Country of the top headlines
+feed-failed = Failed loading headlines
+feed-empty = No articles found
+archive-empty = Nothing archived yet
+archive-empty-hint = Use 💾 on an article while online to read it offline.
+new-articles = { $count ->
+    [one] ⬆ 1 new article
+   *[other] ⬆ { $count } new articles
+}
+unread = { $count } unread
+show-hidden = Show hidden ({ $count })
+show-hidden-hint = Articles muted by the filters
+card-new = NEW
+card-new-hint = First seen since your last visit
+card-hidden = Hidden : { $reason }
+hidden-keyword = muted keyword "{ $keyword }"
+hidden-source = blocked source { $source }
+hidden-domain = blocked domain { $domain }
+hidden-short-description = description shorter than { $count } characters
+card-collapse = Collapse
+card-expand = Expand
+archive = Archive for offline reading
+archived = Available offline, click to remove it from the archive
+archiving = Archiving…
+archive-failed = Archiving failed : { $error }, click to retry
+image-loading = Loading image
+image-unavailable = Image unavailable
+layout-compact = Compact list
+layout-cards = Cards
+layout-magazine = Magazine
+group-none = Nothing
+group-source = Source
+group-category = Category
+group-date = Date
+group-no-source = Unknown source
+group-no-category = Searches
+group-no-date = Unknown date
+group-today = Today
+group-yesterday = Yesterday
+filter-hint = Filter the articles
+filter-regex = Regular expression
+filter-clear = Clear the filter
+filter-invalid = Invalid expression
+filter-empty = No article matches the filter
+order = Sort
+order-default = As fetched
+order-newest = Newest first
+order-oldest = Oldest first
+order-source = Source
+order-title = Title
+order-relevance = Relevance
+footer-source = API source : newsapi.org
+footer-egui = Made with egui
+
+## Saved articles
+
+saved-auto-archive = Archive saved articles for offline reading
+saved-empty = No saved articles
+saved-empty-hint = Use ☆ on an article to read it later.
+saved-tags = Tags :
+saved-all-tags = All
+saved-tags-hint = tags, comma separated
+saved-note-hint = Note
+saved-at = saved { $date }
+
+## Reader
+
+reader-back = ⬅ Back
+reader-browser = Open in browser
+reader-failed = The article could not be shown here : { $error }
+reader-failed-browser = Read it in the browser
+reader-byline = by { $author }
+
+## Alerts
+
+rules = Watch rules
+rule-new = ➕ New rule
+rules-empty = No watch rules
+rules-empty-hint = Rules look for keywords or sources in every article fetched, and alert you here.
+rules-all = All ({ $count })
+rule-notify = Desktop notifications
+rule-no-notify = No desktop notifications
+rule-notify-unavailable = Desktop notifications are not available in this build
+rule-edit = Edit
+rule-delete = Delete the rule and its alerts
+alert-matched = matched { $date }
+alerts-empty = Nothing matched yet, rules apply to the articles fetched from now on.
+rule-new-title = New watch rule
+rule-title = Watch rule
+rule-name = Name
+rule-name-hint = Electric cars
+rule-match = Match
+rule-pattern = Pattern
+rule-enabled = Enabled
+rule-save = Save
+rule-keywords = Keywords
+rule-expression = Expression
+rule-source = Source
+rule-keywords-note = Any of the comma separated words, in the title or the description.
+rule-expression-note = "exact phrase", AND, OR, NOT, +required, -excluded and parentheses.
+rule-source-note = Comma separated publication names or domains, subdomains included.
+notify-new-articles = { $count ->
+    [one] { $rule } : new article
+   *[other] { $rule } : { $count } new articles
+}
+
+## Advanced search
+
+query-keywords = Keywords
+query-language = Language
+query-any = Any
+query-sort-by = Sort by
+query-default-sort = Default
+query-from = From
+query-to = To
+query-sources = Sources
+query-domains = Domains
+query-note = Language, sort order and dates only apply when searching keywords, sources or domains.
+
+## Keyboard
+
+binding-next-article = Next article
+binding-previous-article = Previous article
+binding-open-in-the-reader = Open in the reader
+binding-open-in-the-browser = Open in the browser
+binding-save-for-later = Save for later
+binding-refresh = Refresh
+binding-search = Search
+binding-command-palette = Command palette
+binding-keyboard-shortcuts = Keyboard shortcuts
+help-escape = Close the reader or the palette, clear the selection
+help-zoom = Zoom in and out
+help-note = Change them in ⚙ Settings, Keys tab.
+palette-hint = Type a command
+palette-empty = No matching command
+command-cancel = Cancel loading
+command-search = Search
+command-top = Show the top headlines
+command-category = Show the { $category } headlines
+command-saved = Show the saved articles
+command-alerts = Show the alerts
+command-show-muted = Show the muted articles
+command-hide-muted = Hide the muted articles
+command-online = Go online
+command-offline = Go offline
+command-dark-theme = Toggle the dark theme
+command-theme = Theme : { $theme }
+command-settings = Settings : { $tab }
+command-profile = Switch to the { $profile } profile
+command-layout = Layout : { $layout }
+command-group-by = Group by : { $group }
+command-order = Sort : { $order }
+command-export = Export the saved articles, copied as markdown
+zoom-in = Zoom in
+zoom-out = Zoom out
+zoom-reset = Reset the zoom
+
+## Settings
+
+tab-account = 🔑 Account
+tab-feed = 📰 Feed
+tab-appearance = 🎨 Appearance
+tab-text = 🗛 Text
+tab-accessibility = ♿ Accessibility
+tab-filters = 🔇 Filters
+tab-keys = ⌨ Keys
+tab-storage = 💾 Storage
+tab-network = 🖧 Network
+
+api-key = API_KEY for newsapi.org
+api-key-check = Please check your API_KEY and enter it again.
+api-key-hint = your api key
+api-key-show = Show the key
+api-key-hide = Hide the key
+api-key-register = If you haven't registered for an API_KEY, head over to
+save-key = Save
+profile = Profile
+profile-delete = Delete this profile, switching to another one
+profile-new-hint = new profile
+profile-exists = there is a profile named { $profile } already
+profile-create = Create
+profile-create-hint = A new profile starting with the current settings
+
+feed-country = Country
+feed-category = Category
+feed-search = Search
+feed-offline = Offline, read the archive only
+feed-note = The feed shown on start, it follows the tab and search in use.
+
+language = Language
+language-system = System ({ $language })
+theme = Theme
+theme-follow-system = Follow the system light or dark theme
+theme-follow-system-hint = Where the system reports it, light otherwise
+show-thumbnails = Show thumbnails
+layout-feed = Feed layout
+layout-saved = Saved layout
+layout-alerts = Alerts layout
+group-by-value = Grouped by { $group }
+order-value = Sorted : { $order }
+theme-customize = Customize
+theme-background = Background
+theme-card = Card
+theme-title = Title
+theme-description = Description
+theme-links = Links
+theme-spacing = Spacing
+theme-rounding = Rounding
+theme-outline = Outline
+theme-dark-widgets = Dark widgets
+theme-name = Name
+theme-builtin = { $theme } is a built-in theme
+theme-save = Save theme
+theme-reset-hint = Start again from the theme in use
+theme-reload = Reload user themes
+theme-storage-web = Saved in the browser storage
+theme-storage = Toml files of the themes folder, next to the config
+theme-preview-title = Preview of the theme
+theme-preview-text = Descriptions, and the rest of the text, look like this.
+theme-preview-button = Button
+
+font = Font
+font-file = Font file
+font-file-hint = A ttf or otf file used instead of the font, where it has the glyphs
+font-file-web = The browser gives no access to font files
+size-heading = Headings
+size-body = Body
+size-button = Buttons and descriptions
+size-small = Small
+size-monospace = Monospace
+size-defaults = Default sizes
+zoom = Zoom
+zoom-hint = Ctrl + and Ctrl - zoom in and out, Ctrl 0 resets
+fallback-fonts = Fallback fonts
+fallback-fonts-hint = one ttf, otf or ttc file per line, for the glyphs the font lacks
+fallback-fonts-none = Emoji come with the app, no font with Chinese, Japanese or Korean glyphs was found.
+fallback-fonts-found = Emoji come with the app, then : { $fonts }
+
+high-contrast = High contrast
+high-contrast-hint = White on black with outlined cards, whatever the theme
+reduce-motion = Reduce motion
+reduce-motion-hint = No animation when opening menus, windows and sections
+screen-reader = Describe the widgets to a screen reader
+screen-reader-hint = Where the build supports it, see the readme
+accessibility-keys = Tab and Shift+Tab move between the widgets, Space or Enter activates them, Escape closes the reader.
+accessibility-shortcuts = { $help } lists the keyboard shortcuts, { $palette } opens the command palette.
+accessibility-text = Text sizes and zoom are in the Text tab.
+
+mute-keywords-hint = one per line, regular expressions
+mute-shortest = Shortest description
+mute-shortest-hint = Articles with a shorter description are hidden, 0 keeps them all
+mute-note = Keywords are matched in the title and the description, ignoring case.
+unit-characters = characters
+
+keys-note = A character like j or /, or a key like Enter, PageDown or Ctrl+P.
+
+thumbnail-cache = Thumbnail cache
+thumbnail-cache-web = The browser caches the images
+history = Read history
+history-hint = Articles neither read nor seen for longer are forgotten
+unit-days = days
+archive-size = Archive
+saved = Saved
+
+server = Server
+server-note = A server speaking the newsapi.org api, leave empty for newsapi.org.
+network-web = The browser handles timeouts and proxies.
+timeout = Timeout
+proxy = Proxy
+proxy-note = An http proxy, leave empty to connect directly.
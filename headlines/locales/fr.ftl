@@ -0,0 +1,463 @@
+# Messages de l'interface, voir src/l10n.rs et en.ftl.
+# Ceux qui manquent sont affichés en anglais.
+
+## Commun
+
+loading = Chargement ⏳
+loading-more = Chargement d'autres articles ⏳
+feed-end = La fin du fil, il n'y a pas d'autres articles
+feed-full = La fin du fil, il garde ses { $count } articles les plus récents
+cancel = Annuler
+retry = Réessayer
+refresh = Actualiser
+apply = Appliquer
+reset = Réinitialiser
+clear = Effacer
+defaults = Par défaut
+dismiss = Fermer
+quit = Quitter
+settings = Paramètres
+read-more = lire la suite 🔜
+open-reader = Ouvrir dans le lecteur
+open-browser = Ouvrir dans le navigateur
+save = Lire plus tard
+unsave = Retirer des enregistrés
+mark-read = Marquer comme lu
+mark-unread = Marquer comme non lu
+mark-all-read = Tout marquer comme lu
+show-images = Afficher les images
+hide-images = Masquer les images
+articles = { $count ->
+    [one] { $count } article
+   *[other] { $count } articles
+}
+
+## Erreurs
+
+error-no-api-key = Aucune clé d'API
+error-network = Erreur réseau : { $message }
+error-response = Réponse inattendue : { $message }
+
+## Paramètres invalides
+
+invalid-locale = langue inconnue { $locale }, utiliser l'une de { $locales }
+invalid-no-theme = aucun thème
+invalid-font = police inconnue { $font }
+invalid-text-size = les tailles du texte vont de 6 à 48
+invalid-zoom = le zoom va de { $min } % à { $max } %
+invalid-font-file = { $file } n'est pas un fichier de police ttf, otf ou ttc
+invalid-thumbnail-cache = le cache des vignettes va de 1 à 1024 Mo
+invalid-history-days = l'historique est gardé de 1 à 365 jours
+invalid-timeout = le délai d'attente va de 1 à 300 secondes
+invalid-base-url-scheme = l'url du serveur doit être en http ou https
+invalid-base-url = url du serveur invalide : { $error }
+invalid-proxy = proxy invalide : { $error }
+invalid-date = { $date } n'est pas une date comme 2022-09-30
+invalid-date-order = la date « du » est après la date « au »
+invalid-profile-name-length = les noms de profil font de 1 à 32 caractères
+invalid-profile-name = les noms de profil n'utilisent que des lettres, des chiffres, - et _
+invalid-theme-name-length = les noms de thème font de 1 à 32 caractères
+invalid-theme-name = les noms de thème n'utilisent que des lettres, des chiffres, - et _
+invalid-binding = { $binding } : { $error }
+invalid-bound-twice = { $shortcut } est attribué deux fois
+invalid-modifier = modificateur inconnu { $modifier }, utiliser Ctrl, Alt ou Shift
+invalid-shift = Shift ne va qu'avec les touches nommées, taper plutôt le caractère obtenu avec Shift
+invalid-modified-key = Ctrl et Alt ne vont qu'avec les lettres, les chiffres et les touches nommées
+invalid-key = un caractère ou l'une des touches { $keys } était attendu, pas { $key }
+invalid-muted-keyword = « { $keyword } » : { $error }
+invalid-rule-name = la règle n'a pas de nom
+invalid-rule-pattern = la règle ne correspond à rien
+invalid-rule-empty-phrase = expression entre guillemets vide
+invalid-rule-missing-close = ")" manquante
+invalid-rule-unexpected-close = ")" inattendue
+invalid-rule-early-end = l'expression se termine trop tôt
+invalid-rule-operator = { $operator } a besoin de mots des deux côtés
+invalid-rule-too-deep = les parenthèses et NOT s'imbriquent sur { $max } niveaux au plus
+invalid-rule-too-long = l'expression a plus de { $max } mots et opérateurs
+
+## Barre du haut
+
+theme-to-light = Thème : { $theme }, cliquer pour le thème clair
+theme-to-dark = Thème : { $theme }, cliquer pour le thème sombre
+auto-refresh = Actualiser automatiquement
+auto-refresh-never = Jamais
+auto-refresh-every = Toutes les { $minutes } minutes
+auto-refresh-off = L'actualisation automatique est désactivée
+auto-refresh-on = Actualisation toutes les { $minutes } minutes
+offline-on = Hors ligne, lecture des archives. Cliquer pour repasser en ligne
+offline-off = En ligne. Cliquer pour lire les archives hors ligne
+hide-images-hint = Texte seul, sans images
+mute-filters = Filtres de sourdine
+advanced-search = Recherche avancée
+search-hint = 🔍 Rechercher
+layout = Disposition
+layout-menu = Disposition : { $layout }, groupés par { $group }
+group-by = Grouper par
+
+## Fil
+
+tab-top = À la une
+tab-archive = ✈ Archives ({ $count })
+tab-saved = ⭐ Enregistrés ({ $count })
+tab-alerts = 🔔 Alertes
+tab-alerts-unread = 🔔 Alertes ({ $count })
+tab-alerts-hint = Articles trouvés par les règles de veille
+any-language = Toutes les langues
+feed-language-hint = Langue des résultats de recherche
+feed-country-hint = Pays des gros titres
+feed-failed = Impossible de charger les titres
+feed-empty = Aucun article trouvé
+archive-empty = Rien n'est encore archivé
+archive-empty-hint = Utilisez 💾 sur un article en ligne pour le lire hors ligne.
+new-articles = { $count ->
+    [one] ⬆ 1 nouvel article
+   *[other] ⬆ { $count } nouveaux articles
+}
+unread = { $count ->
+    [one] { $count } non lu
+   *[other] { $count } non lus
+}
+show-hidden = Afficher les masqués ({ $count })
+show-hidden-hint = Articles mis en sourdine par les filtres
+card-new = NOUVEAU
+card-new-hint = Vu pour la première fois depuis votre dernière visite
+card-hidden = Masqué : { $reason }
+hidden-keyword = mot-clé masqué « { $keyword } »
+hidden-source = source bloquée { $source }
+hidden-domain = domaine bloqué { $domain }
+hidden-short-description = description de moins de { $count } caractères
+card-collapse = Réduire
+card-expand = Développer
+layout-compact = Liste compacte
+layout-cards = Cartes
+layout-magazine = Magazine
+group-none = Rien
+group-source = Source
+group-category = Catégorie
+group-date = Date
+group-no-source = Source inconnue
+group-no-category = Recherches
+group-no-date = Date inconnue
+group-today = Aujourd'hui
+group-yesterday = Hier
+filter-hint = Filtrer les articles
+filter-regex = Expression régulière
+filter-clear = Effacer le filtre
+filter-invalid = Expression invalide
+filter-empty = Aucun article ne correspond au filtre
+order = Trier
+order-default = Tel que reçu
+order-newest = Plus récents d'abord
+order-oldest = Plus anciens d'abord
+order-source = Source
+order-title = Titre
+order-relevance = Pertinence
+archive = Archiver pour la lecture hors ligne
+archived = Disponible hors ligne, cliquer pour le retirer des archives
+archiving = Archivage…
+archive-failed = Échec de l'archivage : { $error }, cliquer pour réessayer
+image-loading = Chargement de l'image
+image-unavailable = Image indisponible
+footer-source = Source de l'API : newsapi.org
+footer-egui = Fait avec egui
+
+## Articles enregistrés
+
+saved-auto-archive = Archiver les articles enregistrés pour les lire hors ligne
+saved-empty = Aucun article enregistré
+saved-empty-hint = Utilisez ☆ sur un article pour le lire plus tard.
+saved-tags = Étiquettes :
+saved-all-tags = Toutes
+saved-tags-hint = étiquettes, séparées par des virgules
+saved-note-hint = Note
+saved-at = enregistré le { $date }
+
+## Lecteur
+
+reader-back = ⬅ Retour
+reader-browser = Ouvrir dans le navigateur
+reader-failed = L'article ne peut pas être affiché ici : { $error }
+reader-failed-browser = Le lire dans le navigateur
+reader-byline = par { $author }
+
+## Alertes
+
+rules = Règles de veille
+rule-new = ➕ Nouvelle règle
+rules-empty = Aucune règle de veille
+rules-empty-hint = Les règles cherchent des mots-clés ou des sources dans chaque article récupéré, et vous alertent ici.
+rules-all = Toutes ({ $count })
+rule-notify = Notifications de bureau
+rule-no-notify = Pas de notifications de bureau
+rule-notify-unavailable = Les notifications de bureau ne sont pas disponibles dans cette version
+rule-edit = Modifier
+rule-delete = Supprimer la règle et ses alertes
+alert-matched = trouvé le { $date }
+alerts-empty = Rien pour l'instant, les règles s'appliquent aux articles récupérés à partir de maintenant.
+rule-new-title = Nouvelle règle de veille
+rule-title = Règle de veille
+rule-name = Nom
+rule-name-hint = Voitures électriques
+rule-match = Critère
+rule-pattern = Motif
+rule-enabled = Activée
+rule-save = Enregistrer
+rule-keywords = Mots-clés
+rule-expression = Expression
+rule-source = Source
+rule-keywords-note = L'un des mots séparés par des virgules, dans le titre ou la description.
+rule-expression-note = "phrase exacte", AND, OR, NOT, +obligatoire, -exclu et parenthèses.
+rule-source-note = Noms de publications ou domaines séparés par des virgules, sous-domaines compris.
+notify-new-articles = { $count ->
+    [one] { $rule } : nouvel article
+   *[other] { $rule } : { $count } nouveaux articles
+}
+
+## Recherche avancée
+
+query-keywords = Mots-clés
+query-language = Langue
+query-any = Toutes
+query-sort-by = Trier par
+query-default-sort = Par défaut
+query-from = Du
+query-to = Au
+query-sources = Sources
+query-domains = Domaines
+query-note = La langue, l'ordre et les dates ne s'appliquent qu'aux recherches par mots-clés, sources ou domaines.
+
+## Clavier
+
+binding-next-article = Article suivant
+binding-previous-article = Article précédent
+binding-open-in-the-reader = Ouvrir dans le lecteur
+binding-open-in-the-browser = Ouvrir dans le navigateur
+binding-save-for-later = Lire plus tard
+binding-refresh = Actualiser
+binding-search = Rechercher
+binding-command-palette = Palette de commandes
+binding-keyboard-shortcuts = Raccourcis clavier
+help-escape = Fermer le lecteur ou la palette, désélectionner
+help-zoom = Zoomer et dézoomer
+help-note = Modifiez-les dans ⚙ Paramètres, onglet Touches.
+palette-hint = Tapez une commande
+palette-empty = Aucune commande correspondante
+command-cancel = Annuler le chargement
+command-search = Rechercher
+command-top = Afficher les gros titres
+command-category = Afficher les titres { $category }
+command-saved = Afficher les articles enregistrés
+command-alerts = Afficher les alertes
+command-show-muted = Afficher les articles en sourdine
+command-hide-muted = Masquer les articles en sourdine
+command-online = Passer en ligne
+command-offline = Passer hors ligne
+command-dark-theme = Basculer le thème sombre
+command-theme = Thème : { $theme }
+command-settings = Paramètres : { $tab }
+command-profile = Passer au profil { $profile }
+command-layout = Disposition : { $layout }
+command-group-by = Grouper par : { $group }
+command-order = Trier : { $order }
+command-export = Exporter les articles enregistrés, copiés en markdown
+zoom-in = Zoomer
+zoom-out = Dézoomer
+zoom-reset = Réinitialiser le zoom
+
+## Paramètres
+
+tab-account = 🔑 Compte
+tab-feed = 📰 Fil
+tab-appearance = 🎨 Apparence
+tab-text = 🗛 Texte
+tab-accessibility = ♿ Accessibilité
+tab-filters = 🔇 Filtres
+tab-keys = ⌨ Touches
+tab-storage = 💾 Stockage
+tab-network = 🖧 Réseau
+
+api-key = API_KEY de newsapi.org
+api-key-check = Vérifiez votre API_KEY et saisissez-la de nouveau.
+api-key-hint = votre clé d'API
+api-key-show = Afficher la clé
+api-key-hide = Masquer la clé
+api-key-register = Si vous n'avez pas encore d'API_KEY, rendez-vous sur
+save-key = Enregistrer
+profile = Profil
+profile-delete = Supprimer ce profil et passer à un autre
+profile-new-hint = nouveau profil
+profile-exists = il existe déjà un profil nommé { $profile }
+profile-create = Créer
+profile-create-hint = Un nouveau profil reprenant les paramètres actuels
+
+feed-country = Pays
+feed-category = Catégorie
+feed-search = Recherche
+feed-offline = Hors ligne, lire seulement les archives
+feed-note = Le fil affiché au démarrage, il suit l'onglet et la recherche en cours.
+
+language = Langue
+language-system = Système ({ $language })
+theme = Thème
+theme-follow-system = Suivre le thème clair ou sombre du système
+theme-follow-system-hint = Si le système l'indique, clair sinon
+show-thumbnails = Afficher les vignettes
+layout-feed = Disposition du fil
+layout-saved = Disposition des enregistrés
+layout-alerts = Disposition des alertes
+group-by-value = Groupés par { $group }
+order-value = Triés : { $order }
+theme-customize = Personnaliser
+theme-background = Fond
+theme-card = Carte
+theme-title = Titre
+theme-description = Description
+theme-links = Liens
+theme-spacing = Espacement
+theme-rounding = Arrondi
+theme-outline = Contour
+theme-dark-widgets = Widgets sombres
+theme-name = Nom
+theme-builtin = { $theme } est un thème intégré
+theme-save = Enregistrer le thème
+theme-reset-hint = Repartir du thème utilisé
+theme-reload = Recharger les thèmes utilisateur
+theme-storage-web = Enregistrés dans le stockage du navigateur
+theme-storage = Fichiers toml du dossier themes, à côté de la configuration
+theme-preview-title = Aperçu du thème
+theme-preview-text = Les descriptions, et le reste du texte, ressemblent à ceci.
+theme-preview-button = Bouton
+
+font = Police
+font-file = Fichier de police
+font-file-hint = Un fichier ttf ou otf utilisé à la place de la police, là où il a les glyphes
+font-file-web = Le navigateur ne donne pas accès aux fichiers de police
+size-heading = Titres
+size-body = Corps
+size-button = Boutons et descriptions
+size-small = Petit
+size-monospace = Chasse fixe
+size-defaults = Tailles par défaut
+zoom = Zoom
+zoom-hint = Ctrl + et Ctrl - pour zoomer et dézoomer, Ctrl 0 pour réinitialiser
+fallback-fonts = Polices de secours
+fallback-fonts-hint = un fichier ttf, otf ou ttc par ligne, pour les glyphes absents de la police
+fallback-fonts-none = Les emoji sont fournis avec l'application, aucune police avec des glyphes chinois, japonais ou coréens n'a été trouvée.
+fallback-fonts-found = Les emoji sont fournis avec l'application, puis : { $fonts }
+
+high-contrast = Contraste élevé
+high-contrast-hint = Blanc sur noir avec des cartes encadrées, quel que soit le thème
+reduce-motion = Réduire les animations
+reduce-motion-hint = Pas d'animation à l'ouverture des menus, fenêtres et sections
+screen-reader = Décrire les widgets à un lecteur d'écran
+screen-reader-hint = Si la version le permet, voir le readme
+accessibility-keys = Tab et Maj+Tab passent d'un widget à l'autre, Espace ou Entrée les activent, Échap ferme le lecteur.
+accessibility-shortcuts = { $help } affiche les raccourcis clavier, { $palette } ouvre la palette de commandes.
+accessibility-text = Les tailles de texte et le zoom sont dans l'onglet Texte.
+
+mute-keywords-hint = un par ligne, expressions régulières
+mute-shortest = Description la plus courte
+mute-shortest-hint = Les articles à la description plus courte sont masqués, 0 les garde tous
+mute-note = Les mots-clés sont cherchés dans le titre et la description, sans tenir compte de la casse.
+unit-characters = caractères
+
+keys-note = Un caractère comme j ou /, ou une touche comme Enter, PageDown ou Ctrl+P.
+
+thumbnail-cache = Cache des vignettes
+thumbnail-cache-web = Le navigateur met les images en cache
+history = Historique de lecture
+history-hint = Les articles ni lus ni vus depuis plus longtemps sont oubliés
+unit-days = jours
+archive-size = Archives
+saved = Enregistrés
+
+server = Serveur
+server-note = Un serveur parlant l'api de newsapi.org, vide pour newsapi.org.
+network-web = Le navigateur gère les délais et les proxys.
+timeout = Délai
+proxy = Proxy
+proxy-note = Un proxy http, vide pour se connecter directement.
+
+## Noms de newsapi
+
+category-business = Économie
+category-entertainment = Divertissement
+category-general = Général
+category-health = Santé
+category-science = Sciences
+category-sports = Sports
+category-technology = Technologie
+
+sort-relevancy = Pertinence
+sort-popularity = Popularité
+sort-newest-first = Plus récents d'abord
+
+language-ar = Arabe
+language-de = Allemand
+language-en = Anglais
+language-es = Espagnol
+language-fr = Français
+language-he = Hébreu
+language-it = Italien
+language-nl = Néerlandais
+language-no = Norvégien
+language-pt = Portugais
+language-ru = Russe
+language-sv = Suédois
+language-ud = Ourdou
+language-zh = Chinois
+
+country-ae = Émirats arabes unis
+country-ar = Argentine
+country-at = Autriche
+country-au = Australie
+country-be = Belgique
+country-bg = Bulgarie
+country-br = Brésil
+country-ca = Canada
+country-ch = Suisse
+country-cn = Chine
+country-co = Colombie
+country-cu = Cuba
+country-cz = Tchéquie
+country-de = Allemagne
+country-eg = Égypte
+country-fr = France
+country-gb = Royaume-Uni
+country-gr = Grèce
+country-hk = Hong Kong
+country-hu = Hongrie
+country-id = Indonésie
+country-ie = Irlande
+country-il = Israël
+country-in = Inde
+country-it = Italie
+country-jp = Japon
+country-kr = Corée du Sud
+country-lt = Lituanie
+country-lv = Lettonie
+country-ma = Maroc
+country-mx = Mexique
+country-my = Malaisie
+country-ng = Nigeria
+country-nl = Pays-Bas
+country-no = Norvège
+country-nz = Nouvelle-Zélande
+country-ph = Philippines
+country-pl = Pologne
+country-pt = Portugal
+country-ro = Roumanie
+country-rs = Serbie
+country-ru = Russie
+country-sa = Arabie saoudite
+country-se = Suède
+country-sg = Singapour
+country-si = Slovénie
+country-sk = Slovaquie
+country-th = Thaïlande
+country-tr = Turquie
+country-tw = Taïwan
+country-ua = Ukraine
+country-us = États-Unis
+country-ve = Venezuela
+country-za = Afrique du Sud
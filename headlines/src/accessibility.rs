@@ -0,0 +1,29 @@
+use eframe::egui::{output::OutputEvent, Response};
+
+/// Names for the icon only widgets, the emoji meaning nothing once read aloud.
+///
+/// egui 0.17 predates AccessKit, it describes the widgets clicked or focused in
+/// its output events, which eframe reads aloud when built with its `screen_reader`
+/// feature and [`crate::HeadlinesConfig::screen_reader`] is set.
+pub(crate) trait Describe {
+    /// the tooltip, also the name the widget is read by
+    fn describe(self,text : impl Into<String>) -> Self;
+}
+
+impl Describe for Response {
+    fn describe(self,text : impl Into<String>) -> Self {
+        let text = text.into();
+        // the widget has just pushed its event, under the name of its icon
+        if self.clicked() || self.double_clicked() || self.gained_focus() || self.changed() {
+            if let Some(OutputEvent::Clicked(info) | OutputEvent::DoubleClicked(info) | OutputEvent::FocusGained(info) | OutputEvent::ValueChanged(info))
+                = self.ctx.output().events.last_mut() {
+                info.label = Some(text.clone());
+            }
+        }
+        if self.enabled() {
+            self.on_hover_text(text)
+        } else {
+            self.on_disabled_hover_text(text)
+        }
+    }
+}
@@ -0,0 +1,538 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize,Deserialize};
+
+use crate::{l10n::Message, message, news::NewsCardData, storage};
+
+const STORAGE_NAME : &str = "alerts";
+/// Alerts kept around, the oldest ones are dropped first.
+const MAX_ALERTS : usize = 500;
+
+/// Whether [`notify`] shows anything, desktop notifications need the `notifications` feature on native.
+pub const NOTIFICATIONS : bool = cfg!(all(feature = "notifications", not(target_arch = "wasm32")));
+
+/// What a watch rule looks for in the fetched articles.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Serialize,Deserialize)]
+pub enum RuleKind {
+    /// any of the comma separated keywords, in the title or the description
+    #[default]
+    Keywords,
+    /// keywords combined like newsapi.org searches, `"exact phrase"`, `AND`, `OR`, `NOT`,
+    /// `+required`, `-excluded` and parentheses
+    Expression,
+    /// comma separated publication names or domains
+    Source
+}
+
+impl RuleKind {
+    pub const ALL : [RuleKind; 3] = [RuleKind::Keywords, RuleKind::Expression, RuleKind::Source];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RuleKind::Keywords => "Keywords",
+            RuleKind::Expression => "Expression",
+            RuleKind::Source => "Source"
+        }
+    }
+
+    /// an example pattern
+    pub fn hint(&self) -> &'static str {
+        match self {
+            RuleKind::Keywords => "tesla, spacex",
+            RuleKind::Expression => "apple AND (iphone OR \"vision pro\") -rumor",
+            RuleKind::Source => "Reuters, bloomberg.com"
+        }
+    }
+}
+
+/// Articles to be alerted about, whatever feed they are fetched for.
+#[derive(Clone,Debug,Default,PartialEq,Serialize,Deserialize)]
+#[serde(default)]
+pub struct WatchRule {
+    /// 0 until the rule is saved
+    pub id : u64,
+    pub name : String,
+    pub kind : RuleKind,
+    pub pattern : String,
+    pub enabled : bool,
+    /// show a desktop notification for the matching articles
+    pub notify : bool
+}
+
+impl WatchRule {
+    /// the first problem with the rule, if any
+    pub fn validate(&self) -> Result<(),Message> {
+        if self.name.trim().is_empty() {
+            return Err(message!("invalid-rule-name"));
+        }
+        if split_list(&self.pattern).next().is_none() {
+            return Err(message!("invalid-rule-pattern"));
+        }
+        if self.kind == RuleKind::Expression {
+            Expr::parse(&self.pattern)?;
+        }
+        Ok(())
+    }
+
+    /// parses an expression on every call, [`Alerts`] keeps the ones of its rules parsed
+    pub fn matches(&self,article : &NewsCardData) -> bool {
+        let expr = Some(&self.kind).filter(|kind| **kind == RuleKind::Expression)
+            .and_then(|_| Expr::parse(&self.pattern).ok());
+        self.matches_parsed(expr.as_ref(), article)
+    }
+
+    /// `expr` being the parsed pattern of an expression rule, never matching when it is invalid
+    fn matches_parsed(&self,expr : Option<&Expr>,article : &NewsCardData) -> bool {
+        let text = format!("{} {}",article.title,article.desc).to_lowercase();
+        match self.kind {
+            RuleKind::Keywords => split_list(&self.pattern)
+                .any(|keyword| contains_words(&text, &keyword.to_lowercase())),
+            RuleKind::Expression => expr.is_some_and(|expr| expr.matches(&text)),
+            RuleKind::Source => {
+                let domain = article.domain().unwrap_or_default();
+                split_list(&self.pattern).any(|source| {
+                    let source = source.to_lowercase();
+                    article.source.to_lowercase() == source
+                        || domain == source
+                        || domain.ends_with(&format!(".{}",source))
+                })
+            }
+        }
+    }
+}
+
+/// An article matched by a watch rule.
+// toml wants plain values before tables, keep `article` last
+#[derive(Clone,Debug,PartialEq,Serialize,Deserialize)]
+pub struct Alert {
+    pub rule : u64,
+    pub matched_at : DateTime<Utc>,
+    pub read : bool,
+    pub article : NewsCardData
+}
+
+/// The watch rules and the articles they matched, newest first.
+#[derive(Default,Serialize,Deserialize)]
+#[serde(default)]
+pub struct Alerts {
+    next_id : u64,
+    rules : Vec<WatchRule>,
+    alerts : Vec<Alert>,
+    /// the parsed patterns of the expression rules, by rule id
+    #[serde(skip)]
+    exprs : HashMap<u64,Expr>
+}
+
+impl Alerts {
+    pub fn load() -> Alerts {
+        let mut alerts : Alerts = storage::load(STORAGE_NAME);
+        for rule in &alerts.rules {
+            alerts.exprs.extend(parse_rule(rule));
+        }
+        alerts
+    }
+
+    pub fn store(&self) {
+        storage::store(STORAGE_NAME, self)
+    }
+
+    pub fn rules(&self) -> &[WatchRule] {
+        &self.rules
+    }
+
+    pub fn rule(&self,id : u64) -> Option<&WatchRule> {
+        self.rules.iter().find(|r| r.id == id)
+    }
+
+    /// adds the rule, or replaces the one with the same id
+    pub fn save_rule(&mut self,mut rule : WatchRule) -> u64 {
+        rule.name = rule.name.trim().to_string();
+        rule.pattern = rule.pattern.trim().to_string();
+        let id = rule.id;
+        let id = match self.rules.iter_mut().find(|r| id != 0 && r.id == id) {
+            Some(existing) => {
+                *existing = rule;
+                id
+            }
+            None => {
+                self.next_id += 1;
+                rule.id = self.next_id;
+                self.rules.push(rule);
+                self.next_id
+            }
+        };
+        self.exprs.remove(&id);
+        let parsed = self.rule(id).and_then(parse_rule);
+        self.exprs.extend(parsed);
+        id
+    }
+
+    /// forgets the rule and its alerts
+    pub fn remove_rule(&mut self,id : u64) -> bool {
+        let len = self.rules.len();
+        self.rules.retain(|r| r.id != id);
+        self.alerts.retain(|a| a.rule != id);
+        self.exprs.remove(&id);
+        self.rules.len() != len
+    }
+
+    /// the alerts of `rule`, of every rule for `None`
+    pub fn alerts(&self,rule : Option<u64>) -> impl Iterator<Item = &Alert> {
+        self.alerts.iter().filter(move |a| rule.is_none_or(|id| a.rule == id))
+    }
+
+    pub fn unread(&self,rule : Option<u64>) -> usize {
+        self.alerts(rule).filter(|a| !a.read).count()
+    }
+
+    /// records the articles matched by the enabled rules, returns the new alerts
+    pub fn evaluate(&mut self,articles : &[NewsCardData]) -> Vec<Alert> {
+        let now = Utc::now();
+        let mut fresh = Vec::new();
+        for rule in self.rules.iter().filter(|r| r.enabled) {
+            let expr = self.exprs.get(&rule.id);
+            for article in articles.iter().filter(|a| rule.matches_parsed(expr, a)) {
+                let known = self.alerts.iter().chain(&fresh)
+                    .any(|a : &Alert| a.rule == rule.id && a.article.url == article.url);
+                if !known {
+                    fresh.push(Alert { rule : rule.id, matched_at : now, read : false, article : article.clone() });
+                }
+            }
+        }
+        self.alerts.splice(0..0, fresh.iter().cloned());
+        self.alerts.truncate(MAX_ALERTS);
+        fresh
+    }
+
+    /// marks the alerts of `rule` read, of every rule for `None`
+    pub fn mark_read(&mut self,rule : Option<u64>) -> bool {
+        let mut changed = false;
+        for alert in self.alerts.iter_mut().filter(|a| !a.read && rule.is_none_or(|id| a.rule == id)) {
+            alert.read = true;
+            changed = true;
+        }
+        changed
+    }
+
+    /// marks the alerts about the article at `url` read
+    pub fn mark_url_read(&mut self,url : &str) -> bool {
+        let mut changed = false;
+        for alert in self.alerts.iter_mut().filter(|a| !a.read && a.article.url == url) {
+            alert.read = true;
+            changed = true;
+        }
+        changed
+    }
+}
+
+/// Shows a desktop notification, off the calling thread since it may wait on the notification daemon.
+pub fn notify(summary : &str,body : &str) {
+    #[cfg(all(feature = "notifications", not(target_arch = "wasm32")))]
+    {
+        let summary = summary.to_string();
+        let body = body.to_string();
+        std::thread::spawn(move ||{
+            let shown = notify_rust::Notification::new()
+                .appname(storage::APP_NAME)
+                .summary(&summary)
+                .body(&body)
+                .show();
+            if let Err(e) = shown {
+                tracing::warn!("failed showing notification : {}",e);
+            }
+        });
+    }
+    #[cfg(not(all(feature = "notifications", not(target_arch = "wasm32"))))]
+    tracing::debug!("notifications are not available : {} {}",summary,body);
+}
+
+/// the id and parsed pattern of an expression rule
+fn parse_rule(rule : &WatchRule) -> Option<(u64,Expr)> {
+    if rule.kind != RuleKind::Expression {
+        return None;
+    }
+    match Expr::parse(&rule.pattern) {
+        Ok(expr) => Some((rule.id, expr)),
+        Err(e) => {
+            tracing::warn!("rule {} never matches : {}",rule.name,e);
+            None
+        }
+    }
+}
+
+fn split_list(list : &str) -> impl Iterator<Item = &str> {
+    list.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// whether `words` appears in `text` as whole words, both lowercase
+fn contains_words(text : &str,words : &str) -> bool {
+    let is_word = |c : Option<char>| c.is_some_and(char::is_alphanumeric);
+    text.match_indices(words).any(|(start, _)| {
+        let end = start + words.len();
+        !is_word(text[..start].chars().next_back()) && !is_word(text[end..].chars().next())
+    })
+}
+
+#[derive(Debug,PartialEq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Words(String)
+}
+
+fn tokenize(pattern : &str) -> Result<Vec<Token>,Message> {
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => { chars.next(); }
+            '(' => { chars.next(); tokens.push(Token::Open); }
+            ')' => { chars.next(); tokens.push(Token::Close); }
+            // required words are the default
+            '+' => { chars.next(); }
+            '-' => { chars.next(); tokens.push(Token::Not); }
+            '"' => {
+                chars.next();
+                let phrase : String = chars.by_ref().take_while(|&c| c != '"').collect();
+                if phrase.trim().is_empty() {
+                    return Err(message!("invalid-rule-empty-phrase"));
+                }
+                tokens.push(Token::Words(phrase.trim().to_lowercase()));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' || c == '"' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(match word.as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => Token::Words(word.to_lowercase())
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// A parsed [`RuleKind::Expression`], words next to each other must all be found.
+#[derive(Debug)]
+enum Expr {
+    Words(String),
+    Not(Box<Expr>),
+    And(Box<Expr>,Box<Expr>),
+    Or(Box<Expr>,Box<Expr>)
+}
+
+/// Parentheses and `NOT`s nested in each other at most, the parser and the parsed
+/// expression recurse once for each.
+const MAX_NESTING : usize = 32;
+/// Words and operators of an expression at most, chains of `AND` and `OR` nest too.
+const MAX_TOKENS : usize = 256;
+
+impl Expr {
+    fn parse(pattern : &str) -> Result<Expr,Message> {
+        let tokens = tokenize(pattern)?;
+        if tokens.len() > MAX_TOKENS {
+            return Err(message!("invalid-rule-too-long", max = MAX_TOKENS));
+        }
+        let mut parser = Parser { tokens : &tokens, position : 0, nesting : 0 };
+        let expr = parser.or()?;
+        // only a closing parenthesis stops the parser before the end
+        match parser.tokens.get(parser.position) {
+            None => Ok(expr),
+            Some(_) => Err(message!("invalid-rule-unexpected-close"))
+        }
+    }
+
+    fn matches(&self,text : &str) -> bool {
+        match self {
+            Expr::Words(words) => contains_words(text, words),
+            Expr::Not(expr) => !expr.matches(text),
+            Expr::And(left, right) => left.matches(text) && right.matches(text),
+            Expr::Or(left, right) => left.matches(text) || right.matches(text)
+        }
+    }
+}
+
+/// recursive descent, `OR` binds looser than `AND` which binds looser than `NOT`
+struct Parser<'a> {
+    tokens : &'a [Token],
+    position : usize,
+    /// parentheses and `NOT`s the current token is in
+    nesting : usize
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn or(&mut self) -> Result<Expr,Message> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr,Message> {
+        let mut expr = self.unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => self.position += 1,
+                Some(Token::Words(_) | Token::Not | Token::Open) => {}
+                _ => return Ok(expr)
+            }
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr,Message> {
+        let token = self.peek().ok_or_else(|| message!("invalid-rule-early-end"))?;
+        self.position += 1;
+        match token {
+            Token::Not => {
+                let expr = self.nested(Self::unary)?;
+                Ok(Expr::Not(Box::new(expr)))
+            }
+            Token::Words(words) => Ok(Expr::Words(words.clone())),
+            Token::Open => {
+                let expr = self.nested(Self::or)?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(message!("invalid-rule-missing-close"));
+                }
+                self.position += 1;
+                Ok(expr)
+            }
+            Token::Close => Err(message!("invalid-rule-unexpected-close")),
+            Token::And | Token::Or => Err(message!("invalid-rule-operator", operator = if *token == Token::And { "AND" } else { "OR" }))
+        }
+    }
+
+    /// `parse` one level deeper, unless that is too deep
+    fn nested(&mut self,parse : fn(&mut Self) -> Result<Expr,Message>) -> Result<Expr,Message> {
+        if self.nesting == MAX_NESTING {
+            return Err(message!("invalid-rule-too-deep", max = MAX_NESTING));
+        }
+        self.nesting += 1;
+        let expr = parse(self);
+        self.nesting -= 1;
+        expr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(title : &str,desc : &str) -> NewsCardData {
+        NewsCardData {
+            title : title.to_string(),
+            desc : desc.to_string(),
+            url : format!("https://example.com/{}",title.replace(' ', "-")),
+            image : None,
+            source : String::new(),
+            published_at : None,
+            category : None
+        }
+    }
+
+    fn matches(pattern : &str,text : &str) -> bool {
+        Expr::parse(pattern).unwrap().matches(&text.to_lowercase())
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        // a OR (b AND c)
+        assert!(matches("apple OR banana cherry", "apple"));
+        assert!(matches("apple OR banana cherry", "banana cherry"));
+        assert!(!matches("apple OR banana cherry", "banana"));
+        assert!(matches("(apple OR banana) cherry", "banana cherry"));
+        assert!(!matches("(apple OR banana) cherry", "apple"));
+    }
+
+    #[test]
+    fn not_binds_tightest() {
+        assert!(matches("NOT apple OR banana", "apple banana"));
+        assert!(!matches("NOT apple OR banana", "apple"));
+        assert!(!matches("NOT (apple OR banana)", "banana"));
+    }
+
+    #[test]
+    fn excluded_and_required_words() {
+        assert!(matches("apple -rumor", "Apple ships"));
+        assert!(!matches("apple -rumor", "apple rumor mill"));
+        assert!(matches("+apple +iphone", "the apple iphone"));
+        assert!(!matches("+apple +iphone", "apple"));
+    }
+
+    #[test]
+    fn quoted_phrases_match_as_a_whole() {
+        assert!(matches("\"vision pro\"", "The Vision Pro ships"));
+        assert!(!matches("\"vision pro\"", "pro vision"));
+        assert!(matches("apple AND (iphone OR \"vision pro\") -rumor", "apple vision pro review"));
+    }
+
+    #[test]
+    fn words_match_whole() {
+        assert!(!matches("art", "a smart move"));
+        assert!(matches("art", "modern art, again"));
+    }
+
+    #[test]
+    fn unbalanced_expressions_are_errors() {
+        assert_eq!(Expr::parse("(apple OR banana").unwrap_err(), message!("invalid-rule-missing-close"));
+        assert_eq!(Expr::parse("apple)").unwrap_err(), message!("invalid-rule-unexpected-close"));
+        assert_eq!(Expr::parse("apple AND").unwrap_err(), message!("invalid-rule-early-end"));
+        assert_eq!(Expr::parse("OR apple").unwrap_err(), message!("invalid-rule-operator", operator = "OR"));
+        assert_eq!(Expr::parse("\"  \"").unwrap_err(), message!("invalid-rule-empty-phrase"));
+    }
+
+    #[test]
+    fn deep_or_long_expressions_are_errors() {
+        let nested = |depth : usize| format!("{}apple{}","(".repeat(depth),")".repeat(depth));
+        assert!(Expr::parse(&nested(MAX_NESTING)).is_ok());
+        assert_eq!(Expr::parse(&nested(MAX_NESTING + 1)).unwrap_err(), message!("invalid-rule-too-deep", max = MAX_NESTING));
+        let negated = format!("{}apple","NOT ".repeat(100));
+        assert_eq!(Expr::parse(&negated).unwrap_err(), message!("invalid-rule-too-deep", max = MAX_NESTING));
+        let long = vec!["apple"; MAX_TOKENS + 1].join(" ");
+        assert_eq!(Expr::parse(&long).unwrap_err(), message!("invalid-rule-too-long", max = MAX_TOKENS));
+        assert_eq!(Expr::parse(&nested(100_000)).unwrap_err(), message!("invalid-rule-too-long", max = MAX_TOKENS));
+    }
+
+    #[test]
+    fn saved_rules_keep_their_expression_parsed() {
+        let mut alerts = Alerts::default();
+        let id = alerts.save_rule(WatchRule {
+            name : "apple".to_string(),
+            kind : RuleKind::Expression,
+            pattern : "apple -rumor".to_string(),
+            enabled : true,
+            ..WatchRule::default()
+        });
+        assert!(alerts.exprs.contains_key(&id));
+        let fresh = alerts.evaluate(&[article("apple ships", ""), article("apple rumor", "")]);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].article.title, "apple ships");
+
+        // editing the pattern parses it again
+        let mut rule = alerts.rule(id).unwrap().clone();
+        rule.pattern = "rumor".to_string();
+        alerts.save_rule(rule);
+        assert_eq!(alerts.evaluate(&[article("apple rumor", "")]).len(), 1);
+
+        alerts.remove_rule(id);
+        assert!(alerts.exprs.is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+use eframe::egui::{Button, ComboBox, Context, Grid, Hyperlink, Label, Layout, RichText, SelectableLabel, Separator, TextEdit, Ui, Window};
+
+use crate::{
+    accessibility::Describe,
+    alerts::{Alert, RuleKind, WatchRule, NOTIFICATIONS},
+    cards::Cards,
+    news::NewsCardData,
+    headlines::{preview, reader_link, GRAY, PADDING, RED},
+    state::Action,
+    tr
+};
+
+/// The watch rules with their unread counts and the alerts of the `selected` one.
+pub(crate) fn render_alerts(ui : &mut Ui,cards : &mut Cards,selected : &mut Option<u64>,editor : &mut Option<WatchRule>) -> Vec<Action> {
+    let mut actions = Vec::new();
+    let state = cards.state;
+    let alerts = &state.alerts;
+    ui.horizontal(|ui|{
+        ui.heading(tr!("rules"));
+        ui.with_layout(Layout::right_to_left(), |ui|{
+            if ui.button(tr!("rule-new")).clicked() {
+                *editor = Some(WatchRule { enabled : true, ..WatchRule::default() });
+            }
+        });
+    });
+    ui.add_space(PADDING);
+    if alerts.rules().is_empty() {
+        ui.vertical_centered_justified(|ui|{
+            ui.heading(tr!("rules-empty"));
+            ui.add_space(PADDING);
+            ui.label(tr!("rules-empty-hint"));
+        });
+        return actions;
+    }
+    let rule = *selected;
+    if selected.is_some_and(|id| alerts.rule(id).is_none()) {
+        *selected = None;
+    }
+
+    Grid::new("watch_rules").num_columns(5).spacing([10., 4.]).striped(true).show(ui, |ui|{
+        if ui.selectable_label(selected.is_none(), tr!("rules-all", count = alerts.unread(None))).clicked() {
+            *selected = None;
+        }
+        ui.end_row();
+        for rule in alerts.rules() {
+            let label = format!("{} ({})",rule.name,alerts.unread(Some(rule.id)));
+            let label = if rule.enabled { RichText::new(label) } else { RichText::new(label).color(GRAY) };
+            if ui.selectable_label(*selected == Some(rule.id), label).clicked() {
+                *selected = Some(rule.id);
+            }
+            ui.label(RichText::new(format!("{} : {}",kind_name(rule.kind),rule.pattern)).weak());
+            let (icon, hint) = if rule.notify { ("🔔", tr!("rule-notify")) } else { ("🔕", tr!("rule-no-notify")) };
+            ui.label(icon).on_hover_text(hint);
+            if ui.add(Button::new("✏").frame(false)).describe(tr!("rule-edit")).clicked() {
+                *editor = Some(rule.clone());
+            }
+            if ui.add(Button::new("🗑").frame(false)).describe(tr!("rule-delete")).clicked() {
+                actions.push(Action::RemoveWatchRule(rule.id));
+            }
+            ui.end_row();
+        }
+    });
+    ui.add_space(PADDING);
+    let unread = alerts.unread(*selected);
+    if ui.add_enabled(unread > 0, Button::new(tr!("mark-all-read")).small()).clicked() {
+        actions.push(Action::MarkAlertsRead(*selected));
+    }
+    ui.add(Separator::default().spacing(20.));
+
+    if *selected != rule {
+        cards.arranged.clear();
+    }
+    let selected = *selected;
+    let shown : Vec<&Alert> = alerts.alerts(selected).collect();
+    if shown.is_empty() {
+        ui.vertical_centered(|ui| ui.label(tr!("alerts-empty")));
+    }
+    let articles : Vec<&NewsCardData> = shown.iter().map(|alert| &alert.article).collect();
+    cards.show(ui, "alerts", &articles, |cards, ui, a|{
+        // several rules can match the same article
+        if let Some(alert) = shown.iter().find(|alert| std::ptr::eq(&alert.article, a)) {
+            alert_card(cards, ui, alert, selected.is_none());
+        }
+    });
+    actions.extend(std::mem::take(&mut cards.actions));
+    actions
+}
+
+/// an alert in the cards layout, with the rule that matched it when `show_rule`
+fn alert_card(cards : &mut Cards,ui : &mut Ui,alert : &Alert,show_rule : bool) {
+    let a = &alert.article;
+    ui.add_space(PADDING);
+    ui.horizontal_wrapped(|ui|{
+        if let Some(rule) = cards.state.alerts.rule(alert.rule).filter(|_| show_rule) {
+            ui.small(format!("[{}]",rule.name));
+        }
+        let title = if alert.read { RichText::new(&a.title).color(GRAY) } else { RichText::new(&a.title).strong() };
+        let title = ui.add(SelectableLabel::new(cards.is_selected(a), title)).on_hover_text(tr!("open-reader"));
+        cards.scroll(&title, a);
+        if title.clicked() {
+            cards.actions.push(Action::OpenReader(a.clone()));
+        }
+    });
+    ui.add(Label::new(preview(&a.desc)).wrap(true));
+    ui.horizontal(|ui|{
+        let matched = tr!("alert-matched", date = alert.matched_at.format("%Y-%m-%d %H:%M").to_string());
+        ui.small(if a.source.is_empty() { matched } else { format!("{} · {}",a.source,matched) });
+        ui.with_layout(Layout::right_to_left(), |ui|{
+            if reader_link(ui).clicked() {
+                cards.actions.push(Action::OpenReader(a.clone()));
+            }
+            if ui.add(Hyperlink::from_label_and_url("🌐",&a.url)).describe(tr!("open-browser")).clicked() {
+                cards.actions.push(Action::MarkRead(a.url.clone()));
+            }
+        });
+    });
+    ui.add_space(PADDING);
+    ui.add(Separator::default());
+}
+
+/// The window editing a watch rule, returns the rule to save once it is valid.
+pub(crate) fn rule_editor(ctx : &Context,editor : &mut Option<WatchRule>) -> Option<Action> {
+    let mut draft = editor.take()?;
+    let mut open = true;
+    let mut save = false;
+    let title = if draft.id == 0 { tr!("rule-new-title") } else { tr!("rule-title") };
+    Window::new(title).id(eframe::egui::Id::new("rule_editor")).open(&mut open).collapsible(false).show(ctx, |ui|{
+        Grid::new("rule_editor").num_columns(2).spacing([10., 8.]).show(ui, |ui|{
+            ui.label(tr!("rule-name"));
+            ui.add(TextEdit::singleline(&mut draft.name).hint_text(tr!("rule-name-hint")));
+            ui.end_row();
+
+            ui.label(tr!("rule-match"));
+            ComboBox::from_id_source("rule_kind")
+                .selected_text(kind_name(draft.kind))
+                .show_ui(ui, |ui|{
+                    for kind in RuleKind::ALL {
+                        ui.selectable_value(&mut draft.kind, kind, kind_name(kind));
+                    }
+                });
+            ui.end_row();
+
+            ui.label(tr!("rule-pattern"));
+            ui.add(TextEdit::singleline(&mut draft.pattern).hint_text(draft.kind.hint()));
+            ui.end_row();
+
+            ui.label("");
+            ui.checkbox(&mut draft.enabled, tr!("rule-enabled"));
+            ui.end_row();
+
+            ui.label("");
+            ui.add_enabled(NOTIFICATIONS, eframe::egui::Checkbox::new(&mut draft.notify, tr!("rule-notify")))
+                .on_disabled_hover_text(tr!("rule-notify-unavailable"));
+            ui.end_row();
+        });
+        ui.add_space(PADDING);
+        ui.small(match draft.kind {
+            RuleKind::Keywords => tr!("rule-keywords-note"),
+            RuleKind::Expression => tr!("rule-expression-note"),
+            RuleKind::Source => tr!("rule-source-note")
+        });
+        ui.add_space(PADDING);
+        let validation = draft.validate();
+        if let Err(e) = &validation {
+            ui.colored_label(RED, format!("⚠ {}",e));
+        }
+        if ui.add_enabled(validation.is_ok(), Button::new(tr!("rule-save"))).clicked() {
+            save = true;
+        }
+    });
+    if save {
+        return Some(Action::SaveWatchRule(draft));
+    }
+    if open {
+        *editor = Some(draft);
+    }
+    None
+}
+
+fn kind_name(kind : RuleKind) -> String {
+    match kind {
+        RuleKind::Keywords => tr!("rule-keywords"),
+        RuleKind::Expression => tr!("rule-expression"),
+        RuleKind::Source => tr!("rule-source")
+    }
+}
@@ -0,0 +1,72 @@
+use eframe::{epi::App, egui::CentralPanel};
+
+use crate::headlines::{Headlines, render_footer};
+
+impl App for Headlines{
+    fn setup(
+            &mut self,
+            ctx: &eframe::egui::Context,
+            frame: &eframe::epi::Frame,
+            _storage: Option<&dyn eframe::epi::Storage>,
+        ) {
+        // frames are only painted on input, state changes, fetch results and timed checks
+        let waker = frame.clone();
+        self.worker.set_waker(move || waker.request_repaint());
+        let waker = frame.clone();
+        self.thumbnails.set_waker(move || waker.request_repaint());
+        let waker = frame.clone();
+        self.alarm.set_waker(move || waker.request_repaint());
+        let effects = self.state.init();
+        self.run_effects(effects);
+        self.fonts.configure(ctx, &self.state.config);
+    }
+    fn update(&mut self, ctx: &eframe::egui::Context, frame: &eframe::epi::Frame) {
+        crate::l10n::set_locale(crate::Locale::from_setting(&self.state.config.locale));
+        self.apply_theme(ctx);
+        self.apply_accessibility(ctx);
+        self.fonts.configure(ctx, &self.state.config);
+        self.update_zoom(ctx, frame);
+        crate::thumbnails::set_cache_limit(self.state.config.thumbnail_cache_mb);
+        self.preload_articles();
+        self.thumbnails.poll(ctx);
+        self.update_focus(ctx);
+        if !self.state.api_key_initialized{
+            CentralPanel::default().show(ctx, |_ui|{});
+            self.render_settings(ctx);
+        }else{
+            self.handle_keys(ctx);
+            self.render_top_panel(ctx,frame); 
+            self.update_search(ctx);
+            self.render_query_editor(ctx);
+            self.render_rule_editor(ctx);
+            self.render_settings(ctx);
+            self.render_palette(ctx, frame);
+            self.render_error_banner(ctx);
+            render_footer(ctx);
+            self.render_central_panel(ctx);
+        }
+        self.request_repaint(ctx);
+    }
+
+    fn on_exit(&mut self) {
+        self.worker.shutdown();
+    }
+
+    fn name(&self) -> &str {
+        "Headlines by GhosT v1.0"
+    }
+}
+
+
+#[cfg(target_arch = "wasm32")]
+use eframe::wasm_bindgen::{self,prelude::*};
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn main_web(canvas_id : &str){
+    let headlines = Headlines::new();
+    tracing_wasm::set_as_global_default();
+    if let Err(e) = eframe::start_web(canvas_id,Box::new(headlines)) {
+        tracing::error!("failed starting web app : {:?}",e);
+    }
+}
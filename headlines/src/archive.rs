@@ -0,0 +1,261 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize,Deserialize};
+
+use crate::{error::FetchError, extract::Document, news::NewsCardData, storage};
+
+const STORAGE_NAME : &str = "archive";
+/// Images archived with each article at most.
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_IMAGES : usize = 20;
+/// Bigger images are left out of the archive.
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_IMAGE_BYTES : u64 = 5 * 1024 * 1024;
+
+/// An article readable offline.
+// toml wants plain values before tables, keep `article` last
+#[derive(Clone,Debug,PartialEq,Serialize,Deserialize)]
+pub struct ArchiveEntry {
+    pub archived_at : DateTime<Utc>,
+    /// images saved along with the text
+    pub images : usize,
+    pub article : NewsCardData
+}
+
+/// Index of the archived articles, newest first.
+///
+/// The extracted documents are json files in the data directory on native, next to
+/// their images, and local storage entries on wasm32 where images are not archived.
+#[derive(Default,Serialize,Deserialize)]
+#[serde(default)]
+pub struct Archive {
+    entries : Vec<ArchiveEntry>
+}
+
+impl Archive {
+    pub fn load() -> Archive {
+        storage::load(STORAGE_NAME)
+    }
+
+    pub fn store(&self) {
+        storage::store(STORAGE_NAME, self)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ArchiveEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self,url : &str) -> bool {
+        self.entries.iter().any(|e| e.article.url == url)
+    }
+
+    pub(crate) fn insert(&mut self,entry : ArchiveEntry) {
+        self.remove(&entry.article.url);
+        self.entries.insert(0, entry);
+    }
+
+    pub(crate) fn remove(&mut self,url : &str) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|e| e.article.url != url);
+        self.entries.len() != len
+    }
+}
+
+/// urls of the images worth archiving with `document`
+#[cfg(not(target_arch = "wasm32"))]
+fn image_urls(document : &Document) -> Vec<&str> {
+    let mut urls : Vec<&str> = document.lead_image.as_deref().into_iter().collect();
+    for block in &document.blocks {
+        if let crate::extract::Block::Image { src, .. } = block {
+            if !urls.contains(&src.as_str()) {
+                urls.push(src);
+            }
+        }
+    }
+    urls
+}
+
+fn not_archived(e : impl std::fmt::Display) -> FetchError {
+    FetchError::InvalidResponse(format!("archive : {}",e))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn archive_dir() -> Option<std::path::PathBuf> {
+    let project = directories::ProjectDirs::from("rs", "", storage::APP_NAME)?;
+    Some(project.data_dir().join("archive"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn document_path(url : &str) -> Option<std::path::PathBuf> {
+    Some(archive_dir()?.join(format!("{}.json",storage::url_key(url))))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn images_dir() -> Option<std::path::PathBuf> {
+    Some(archive_dir()?.join("images"))
+}
+
+/// where an archived image is saved, whether it exists or not
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn image_path(url : &str) -> Option<std::path::PathBuf> {
+    Some(images_dir()?.join(storage::url_key(url)))
+}
+
+/// writes `document` and its images, run by the worker off the UI thread
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn save(article : &NewsCardData,document : &Document) -> Result<ArchiveEntry,FetchError> {
+    use std::fs;
+
+    let path = document_path(&article.url).ok_or_else(|| not_archived("no data directory"))?;
+    let images_dir = images_dir().ok_or_else(|| not_archived("no data directory"))?;
+    fs::create_dir_all(images_dir).map_err(not_archived)?;
+    let json = serde_json::to_string(document).map_err(not_archived)?;
+    fs::write(&path, json).map_err(not_archived)?;
+
+    let mut images = 0;
+    for url in image_urls(document).into_iter().take(MAX_IMAGES) {
+        match download_image(url) {
+            Ok(()) => images += 1,
+            Err(e) => tracing::warn!("failed archiving image {} : {}",url,e)
+        }
+    }
+    Ok(ArchiveEntry { archived_at : Utc::now(), images, article : article.clone() })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn download_image(url : &str) -> Result<(),String> {
+    use std::io::Read;
+
+    let path = image_path(url).ok_or("no data directory")?;
+    if path.exists() {
+        return Ok(());
+    }
+    let response = crate::net::agent().get(url).call().map_err(|e| e.to_string())?;
+    let mut bytes = Vec::new();
+    response.into_reader()
+        .take(MAX_IMAGE_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| e.to_string())?;
+    if bytes.len() as u64 > MAX_IMAGE_BYTES {
+        return Err("image is too big".to_string());
+    }
+    std::fs::write(path, bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn load_document(url : &str) -> Result<Document,FetchError> {
+    let path = document_path(url).ok_or_else(|| not_archived("no data directory"))?;
+    let json = std::fs::read_to_string(path).map_err(not_archived)?;
+    serde_json::from_str(&json).map_err(not_archived)
+}
+
+/// deletes the document and the images no other archived document shows
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn delete(url : &str) {
+    let document = match load_document(url) {
+        Ok(document) => document,
+        Err(_) => return
+    };
+    if let Some(path) = document_path(url) {
+        if let Err(e) = std::fs::remove_file(path) {
+            tracing::warn!("failed deleting archived article : {}",e);
+            return;
+        }
+    }
+    for image in unused_images(&document, &archived_documents()) {
+        if let Some(path) = image_path(image) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// the documents left in the archive directory
+#[cfg(not(target_arch = "wasm32"))]
+fn archived_documents() -> Vec<Document> {
+    let entries = match archive_dir().map(std::fs::read_dir) {
+        Some(Ok(entries)) => entries,
+        _ => return Vec::new()
+    };
+    entries.filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .filter_map(|json| serde_json::from_str(&json).ok())
+        .collect()
+}
+
+/// the images of `document` none of `others` shows
+#[cfg(not(target_arch = "wasm32"))]
+fn unused_images<'a>(document : &'a Document,others : &[Document]) -> Vec<&'a str> {
+    let used : std::collections::HashSet<&str> = others.iter().flat_map(image_urls).collect();
+    image_urls(document).into_iter().filter(|url| !used.contains(url)).collect()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Result<web_sys::Storage,FetchError> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or_else(|| not_archived("local storage is not available"))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn document_key(url : &str) -> String {
+    format!("{}.archive.{}",storage::APP_NAME,storage::url_key(url))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn save(article : &NewsCardData,document : &Document) -> Result<ArchiveEntry,FetchError> {
+    let json = serde_json::to_string(document).map_err(not_archived)?;
+    local_storage()?.set_item(&document_key(&article.url), &json)
+        .map_err(|_| not_archived("local storage is full"))?;
+    Ok(ArchiveEntry { archived_at : Utc::now(), images : 0, article : article.clone() })
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn load_document(url : &str) -> Result<Document,FetchError> {
+    let json = local_storage()?.get_item(&document_key(url))
+        .ok()
+        .flatten()
+        .ok_or_else(|| not_archived("article is missing"))?;
+    serde_json::from_str(&json).map_err(not_archived)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn delete(url : &str) {
+    if let Ok(storage) = local_storage() {
+        let _ = storage.remove_item(&document_key(url));
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::extract::Block;
+
+    fn document(lead_image : &str,images : &[&str]) -> Document {
+        Document {
+            title : "title".to_string(),
+            byline : None,
+            lead_image : Some(lead_image.to_string()),
+            blocks : images.iter().map(|src| Block::Image { src : src.to_string(), alt : String::new() }).collect()
+        }
+    }
+
+    #[test]
+    fn images_shown_by_other_documents_are_kept() {
+        let deleted = document("https://img/lead.png", &["https://img/shared.png", "https://img/own.png"]);
+        let others = [
+            document("https://img/other.png", &["https://img/shared.png"]),
+            document("https://img/lead.png", &[])
+        ];
+        assert_eq!(unused_images(&deleted, &others), vec!["https://img/own.png"]);
+        assert_eq!(unused_images(&deleted, &[]).len(), 3);
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize,Deserialize};
+
+use crate::{news::NewsCardData, storage};
+
+const STORAGE_NAME : &str = "bookmarks";
+
+/// A saved article, kept as a snapshot so it outlives the feed it came from.
+// toml wants plain values before tables, keep `article` last
+#[derive(Clone,Debug,PartialEq,Serialize,Deserialize)]
+pub struct Bookmark {
+    pub tags : Vec<String>,
+    pub note : String,
+    pub saved_at : DateTime<Utc>,
+    pub article : NewsCardData
+}
+
+impl Bookmark {
+    /// tags as typed in the editor, comma separated
+    pub fn tags_text(&self) -> String {
+        self.tags.join(", ")
+    }
+}
+
+/// The read later list, newest first, one bookmark per article url.
+#[derive(Default,Serialize,Deserialize)]
+#[serde(default)]
+pub struct Bookmarks {
+    items : Vec<Bookmark>
+}
+
+impl Bookmarks {
+    pub fn load() -> Bookmarks {
+        storage::load(STORAGE_NAME)
+    }
+
+    pub fn store(&self) {
+        storage::store(STORAGE_NAME, self)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Bookmark> {
+        self.items.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self,url : &str) -> bool {
+        self.get(url).is_some()
+    }
+
+    pub fn get(&self,url : &str) -> Option<&Bookmark> {
+        self.items.iter().find(|b| b.article.url == url)
+    }
+
+    fn get_mut(&mut self,url : &str) -> Option<&mut Bookmark> {
+        self.items.iter_mut().find(|b| b.article.url == url)
+    }
+
+    /// saves the article or removes it, returns whether it is saved now
+    pub fn toggle(&mut self,article : &NewsCardData) -> bool {
+        if self.contains(&article.url) {
+            self.items.retain(|b| b.article.url != article.url);
+            false
+        } else {
+            self.items.insert(0, Bookmark {
+                tags : Vec::new(),
+                note : String::new(),
+                saved_at : Utc::now(),
+                article : article.clone()
+            });
+            true
+        }
+    }
+
+    /// replaces the tags from comma separated text, returns false for unknown urls
+    pub fn set_tags(&mut self,url : &str,tags : &str) -> bool {
+        let tags = parse_tags(tags);
+        match self.get_mut(url) {
+            Some(bookmark) => {
+                bookmark.tags = tags;
+                true
+            }
+            None => false
+        }
+    }
+
+    pub fn set_note(&mut self,url : &str,note : &str) -> bool {
+        match self.get_mut(url) {
+            Some(bookmark) => {
+                bookmark.note = note.trim().to_string();
+                true
+            }
+            None => false
+        }
+    }
+
+    /// a markdown list of the saved articles, with their tags and notes
+    pub fn to_markdown(&self) -> String {
+        let mut text = String::from("# Saved articles\n\n");
+        for bookmark in &self.items {
+            let article = &bookmark.article;
+            text.push_str(&format!("- [{}]({})",link_text(&article.title),link_url(&article.url)));
+            if !bookmark.tags.is_empty() {
+                text.push_str(&format!(" · {}",bookmark.tags_text()));
+            }
+            text.push('\n');
+            for line in bookmark.note.lines() {
+                text.push_str(format!("  > {}",line).trim_end());
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    /// every tag in use, sorted
+    pub fn tags(&self) -> BTreeSet<&str> {
+        self.items.iter().flat_map(|b| b.tags.iter().map(String::as_str)).collect()
+    }
+}
+
+/// `title` on one line, its brackets and backslashes escaped
+fn link_text(title : &str) -> String {
+    let mut text = String::new();
+    for c in title.split_whitespace().collect::<Vec<_>>().join(" ").chars() {
+        if matches!(c, '\\' | '[' | ']') {
+            text.push('\\');
+        }
+        text.push(c);
+    }
+    text
+}
+
+/// `url` with the characters ending a markdown link percent encoded
+fn link_url(url : &str) -> String {
+    url.replace(' ', "%20").replace('(', "%28").replace(')', "%29")
+}
+
+/// trimmed, lowercase and deduplicated, in the order typed
+fn parse_tags(text : &str) -> Vec<String> {
+    let mut tags : Vec<String> = Vec::new();
+    for tag in text.split(',').map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(title : &str,url : &str) -> NewsCardData {
+        NewsCardData {
+            title : title.to_string(),
+            desc : String::new(),
+            url : url.to_string(),
+            image : None,
+            source : String::new(),
+            published_at : None,
+            category : None
+        }
+    }
+
+    #[test]
+    fn tags_are_trimmed_lowercase_and_unique() {
+        assert_eq!(parse_tags(" Rust, web ,,RUST,  , Web Dev "), ["rust", "web", "web dev"]);
+        assert!(parse_tags(" , ").is_empty());
+    }
+
+    #[test]
+    fn set_tags_and_notes_only_of_saved_articles() {
+        let mut bookmarks = Bookmarks::default();
+        assert!(bookmarks.toggle(&article("a", "https://example.com/a")));
+        assert!(bookmarks.set_tags("https://example.com/a", "b, A"));
+        assert!(bookmarks.set_note("https://example.com/a", " read it \n"));
+        assert!(!bookmarks.set_tags("https://example.com/b", "b"));
+        let bookmark = bookmarks.get("https://example.com/a").unwrap();
+        assert_eq!(bookmark.tags_text(), "b, a");
+        assert_eq!(bookmark.note, "read it");
+        assert!(!bookmarks.toggle(&article("a", "https://example.com/a")));
+        assert!(bookmarks.is_empty());
+    }
+
+    #[test]
+    fn markdown_lists_the_newest_first() {
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.toggle(&article("first", "https://example.com/1"));
+        bookmarks.toggle(&article("second", "https://example.com/2"));
+        bookmarks.set_tags("https://example.com/2", "rust, web");
+        bookmarks.set_note("https://example.com/2", "a note\n\non two paragraphs");
+        assert_eq!(bookmarks.to_markdown(), "# Saved articles\n\n\
+            - [second](https://example.com/2) · rust, web\n  > a note\n  >\n  > on two paragraphs\n\
+            - [first](https://example.com/1)\n");
+    }
+
+    #[test]
+    fn markdown_escapes_titles_and_urls() {
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.toggle(&article("[Live] a\\b\n  c", "https://example.com/a (b)"));
+        bookmarks.set_note("https://example.com/a (b)", "> quoted\n- [x] done");
+        assert_eq!(bookmarks.to_markdown(), "# Saved articles\n\n\
+            - [\\[Live\\] a\\\\b c](https://example.com/a%20%28b%29)\n  > > quoted\n  > - [x] done\n");
+    }
+}
@@ -0,0 +1,504 @@
+use std::{collections::{HashMap, HashSet}, hash::Hash};
+
+use chrono::{Duration, Local};
+use eframe::egui::{Align, Button, Hyperlink, Id, Label, Layout, Rect, Response, RichText, ScrollArea, SelectableLabel, Sense, TextStyle, Ui, Vec2};
+
+use crate::{
+    accessibility::Describe,
+    headlines::{archive_button, bookmark_button, preview, reader_link, GRAY, ORANGE, PADDING},
+    l10n::category_name,
+    layout::{self, GroupBy, GroupKey, ViewLayout},
+    news::NewsCardData,
+    sort::{self, QuickFilter, SortOrder},
+    state::{Action, AppState, View},
+    theme::Theme,
+    thumbnails::Thumbnails,
+    tr
+};
+
+/// Narrowest column of the magazine layout.
+const TILE_WIDTH : f32 = 260.;
+/// How far from the end of the feed its next page is fetched, in screens.
+const LOAD_MORE_SCREENS : f32 = 1.;
+
+pub(crate) fn layout_name(layout : layout::Layout) -> String {
+    match layout {
+        layout::Layout::Compact => tr!("layout-compact"),
+        layout::Layout::Cards => tr!("layout-cards"),
+        layout::Layout::Magazine => tr!("layout-magazine")
+    }
+}
+
+pub(crate) fn group_by_name(group_by : GroupBy) -> String {
+    match group_by {
+        GroupBy::None => tr!("group-none"),
+        GroupBy::Source => tr!("group-source"),
+        GroupBy::Category => tr!("group-category"),
+        GroupBy::Date => tr!("group-date")
+    }
+}
+
+pub(crate) fn order_name(order : SortOrder) -> String {
+    match order {
+        SortOrder::Default => tr!("order-default"),
+        SortOrder::Newest => tr!("order-newest"),
+        SortOrder::Oldest => tr!("order-oldest"),
+        SortOrder::Source => tr!("order-source"),
+        SortOrder::Title => tr!("order-title"),
+        SortOrder::Relevance => tr!("order-relevance")
+    }
+}
+
+/// `articles` of `view` narrowed by `filter`, sorted and grouped as chosen for the view
+pub(crate) fn arrange<'b>(state : &AppState,view : View,filter : &QuickFilter,articles : impl IntoIterator<Item = (usize,&'b NewsCardData)>) -> Vec<(GroupKey,Vec<(usize,&'b NewsCardData)>)> {
+    let ViewLayout { group_by, sort, .. } = state.config.layouts.get(view);
+    let mut words = filter.words();
+    if view == View::Feed {
+        words.extend(sort::words(&state.query.keywords));
+    }
+    layout::group(sort::arrange(articles, sort, filter, &words), group_by, sort)
+}
+
+/// the header of a group
+fn group_name(key : &GroupKey) -> String {
+    match key {
+        GroupKey::All => String::new(),
+        GroupKey::Source(source) if source.is_empty() => tr!("group-no-source"),
+        GroupKey::Source(source) => source.clone(),
+        GroupKey::Category(Some(category)) => category_name(*category),
+        GroupKey::Category(None) => tr!("group-no-category"),
+        GroupKey::Date(None) => tr!("group-no-date"),
+        GroupKey::Date(Some(date)) => {
+            let today = Local::now().date_naive();
+            if *date == today {
+                tr!("group-today")
+            } else if *date == today - Duration::days(1) {
+                tr!("group-yesterday")
+            } else {
+                date.format("%Y-%m-%d").to_string()
+            }
+        }
+    }
+}
+
+/// The heights of the rows of a list as last laid out, the rows off screen are not.
+#[derive(Default)]
+pub(crate) struct RowHeights {
+    heights : HashMap<Id,f32>,
+    /// the layout and width they were measured in
+    measured_in : Option<(layout::Layout,f32)>
+}
+
+impl RowHeights {
+    /// forgets the heights measured in another layout or width
+    fn measure_in(&mut self,layout : layout::Layout,width : f32) {
+        if self.measured_in != Some((layout,width)) {
+            self.heights.clear();
+            self.measured_in = Some((layout,width));
+        }
+    }
+
+    /// the heights of `rows`, the average of the measured ones for those not laid out yet
+    fn of(&self,rows : &[Row],layout : layout::Layout) -> Vec<f32> {
+        let measured : Vec<f32> = rows.iter()
+            .filter(|row| matches!(row, Row::Articles { .. }))
+            .filter_map(|row| self.heights.get(&row.id()).copied())
+            .collect();
+        let estimate = if measured.is_empty() {
+            match layout {
+                layout::Layout::Compact => 20.,
+                layout::Layout::Cards => 140.,
+                layout::Layout::Magazine => 320.
+            }
+        } else {
+            measured.iter().sum::<f32>() / measured.len() as f32
+        };
+        rows.iter()
+            .map(|row| self.heights.get(&row.id()).copied().unwrap_or(match row {
+                Row::Header { .. } => 20.,
+                Row::Articles { .. } => estimate
+            }))
+            .collect()
+    }
+}
+
+/// A line of a list, only the ones on screen are laid out.
+enum Row {
+    Header { view : View, key : GroupKey, count : usize, open : bool },
+    /// one article, or a line of tiles in the magazine layout, by their index in the
+    /// articles of the view
+    Articles { id : Id, articles : Vec<usize> }
+}
+
+impl Row {
+    fn id(&self) -> Id {
+        match self {
+            Row::Header { view, key, .. } => Id::new((view,key)),
+            Row::Articles { id, .. } => *id
+        }
+    }
+
+    fn contains(&self,articles : &[&NewsCardData],url : &str) -> bool {
+        matches!(self, Row::Articles { articles : row, .. } if row.iter().any(|&i| articles[i].url == url))
+    }
+}
+
+/// The rows of a view as last arranged, kept until its articles, filter, sort, groups or
+/// layout change.
+#[derive(Default)]
+pub(crate) struct Arranged {
+    /// the rows, with the columns and the number of articles they were arranged for
+    rows : Option<(usize,usize,Vec<Row>)>
+}
+
+impl Arranged {
+    /// for changes the state does not know about, like the narrowing of a view
+    pub(crate) fn clear(&mut self) {
+        self.rows = None;
+    }
+
+    /// `articles` in the order they are listed, without those of folded groups,
+    /// `None` until they are arranged
+    pub(crate) fn listed<'b>(&self,articles : &[&'b NewsCardData]) -> Option<Vec<&'b NewsCardData>> {
+        let (_, _, rows) = self.rows.as_ref().filter(|(_, count, _)| *count == articles.len())?;
+        let listed = rows.iter()
+            .flat_map(|row| match row {
+                Row::Articles { articles : row, .. } => row.as_slice(),
+                Row::Header { .. } => &[]
+            })
+            .map(|&i| articles[i]);
+        Some(listed.collect())
+    }
+}
+
+/// What the articles of a view are drawn with, borrowed from [`crate::Headlines`]
+/// next to the articles themselves.
+pub(crate) struct Cards<'a> {
+    pub(crate) view : View,
+    pub(crate) state : &'a AppState,
+    pub(crate) theme : &'a Theme,
+    pub(crate) filter : &'a QuickFilter,
+    pub(crate) thumbnails : &'a mut Thumbnails,
+    /// urls of the cards showing their whole description
+    pub(crate) expanded : &'a mut HashSet<String>,
+    /// groups folded under their header
+    pub(crate) collapsed : &'a mut HashSet<(View,GroupKey)>,
+    pub(crate) selected : &'a mut HashMap<View,String>,
+    pub(crate) row_heights : &'a mut RowHeights,
+    /// the rows of the view, cleared when what they show changes
+    pub(crate) arranged : &'a mut Arranged,
+    pub(crate) scroll_to : &'a mut Option<String>,
+    pub(crate) scroll_to_selected : &'a mut bool,
+    pub(crate) actions : Vec<Action>
+}
+
+impl Cards<'_> {
+    /// `articles` in the layout of the view, under the headers of their group, scrolled
+    /// apart for each `id_source`. Only the rows on screen are laid out, `card` drawing an
+    /// article of them in the cards layout. Nearing the end of the feed fetches its next page.
+    pub(crate) fn show(&mut self,ui : &mut Ui,id_source : impl Hash,articles : &[&NewsCardData],mut card : impl FnMut(&mut Self,&mut Ui,&NewsCardData)) {
+        let layout = self.state.config.layouts.get(self.view).layout;
+        ScrollArea::vertical().id_source(id_source).show_viewport(ui, |ui, viewport|{
+            let width = ui.available_width();
+            let columns = match layout {
+                layout::Layout::Magazine => {
+                    let spacing = ui.spacing().item_spacing.x;
+                    (((width + spacing) / (TILE_WIDTH + spacing)) as usize).max(1)
+                }
+                _ => 1
+            };
+            let rows = match self.arranged.rows.take() {
+                Some((arranged_columns, count, rows)) if arranged_columns == columns && count == articles.len() => rows,
+                _ => self.rows(articles, columns)
+            };
+            if rows.is_empty() && self.filter.is_active() {
+                ui.vertical_centered(|ui| ui.label(tr!("filter-empty")));
+            }
+            self.row_heights.measure_in(layout, width);
+            let heights = self.row_heights.of(&rows, layout);
+            let spacing = ui.spacing().item_spacing.y;
+            let tops : Vec<f32> = heights.iter()
+                .scan(0., |top, height|{
+                    let row_top = *top;
+                    *top += height + spacing;
+                    Some(row_top)
+                })
+                .collect();
+            let total = (heights.iter().map(|height| height + spacing).sum::<f32>() - spacing).max(0.);
+            self.scroll_off_screen(ui, articles, &rows, &tops, &heights, viewport);
+
+            // where the next row goes, the rows above the screen are skipped over
+            let mut cursor = ui.max_rect().top();
+            let content_top = cursor;
+            let mut at_end = true;
+            let mut folded = false;
+            for ((row, top), height) in rows.iter().zip(&tops).zip(&heights) {
+                if *top > viewport.max.y {
+                    at_end = false;
+                    break;
+                }
+                if top + height < viewport.min.y {
+                    // the widgets of a row keep their ids as the rows above it are laid out or not
+                    ui.skip_ahead_auto_ids(1);
+                    continue;
+                }
+                let skipped = content_top + top - cursor;
+                if skipped > 0. {
+                    ui.add_space(skipped);
+                }
+                let scope = ui.scope(|ui| self.row(ui, layout, columns, articles, row, &mut card));
+                folded |= scope.inner;
+                let rect = scope.response.rect;
+                self.row_heights.heights.insert(row.id(), rect.height());
+                cursor = rect.bottom() + spacing;
+            }
+            // as tall as all the rows, the ones off screen included
+            ui.set_min_height(total);
+            if self.view == View::Feed {
+                let feed = self.state.feed();
+                if feed.loading_more && at_end {
+                    ui.vertical_centered(|ui| ui.label(tr!("loading-more")));
+                } else if self.state.can_load_more() && viewport.max.y + viewport.height() * LOAD_MORE_SCREENS >= total {
+                    self.actions.push(Action::LoadMore);
+                } else if at_end && !feed.more && !feed.loading && !rows.is_empty() {
+                    let end = if feed.is_full() { tr!("feed-full", count = feed.articles.len()) } else { tr!("feed-end") };
+                    ui.vertical_centered(|ui| ui.small(end));
+                }
+            }
+            // arranged again once a group is folded or unfolded
+            if !folded {
+                self.arranged.rows = Some((columns, articles.len(), rows));
+            }
+        });
+    }
+
+    /// the headers of the groups of `articles`, followed by their articles unless collapsed
+    fn rows(&self,articles : &[&NewsCardData],columns : usize) -> Vec<Row> {
+        let mut rows = Vec::new();
+        for (key, group) in arrange(self.state, self.view, self.filter, articles.iter().copied().enumerate()) {
+            if key != GroupKey::All {
+                let open = !self.collapsed.contains(&(self.view, key.clone()));
+                rows.push(Row::Header { view : self.view, key, count : group.len(), open });
+                if !open {
+                    continue;
+                }
+            }
+            rows.extend(group.chunks(columns).map(|chunk| Row::Articles {
+                id : chunk.iter().fold(Id::new("row"), |id, (_, a)| id.with(&a.url)),
+                articles : chunk.iter().map(|(i, _)| *i).collect()
+            }));
+        }
+        rows
+    }
+
+    /// scrolls to where the article asked for should be when its row is not laid out,
+    /// its title is scrolled to once it is
+    fn scroll_off_screen(&self,ui : &Ui,articles : &[&NewsCardData],rows : &[Row],tops : &[f32],heights : &[f32],viewport : Rect) {
+        let (url, align) = match (self.scroll_to.as_deref(), self.selected.get(&self.view)) {
+            (Some(url), _) => (url, Some(Align::TOP)),
+            (None, Some(url)) if *self.scroll_to_selected => (url.as_str(), None),
+            _ => return
+        };
+        let Some(i) = rows.iter().position(|row| row.contains(articles, url)) else { return };
+        if tops[i] + heights[i] < viewport.min.y || tops[i] > viewport.max.y {
+            let top = ui.max_rect().top() + tops[i];
+            let rect = Rect::from_x_y_ranges(ui.max_rect().x_range(), top..=top + heights[i]);
+            ui.scroll_to_rect(rect, align);
+        }
+    }
+
+    /// a group header, folding the group when clicked, or articles in `layout`.
+    /// Whether the group was folded or unfolded.
+    fn row(&mut self,ui : &mut Ui,layout : layout::Layout,columns : usize,articles : &[&NewsCardData],row : &Row,card : &mut impl FnMut(&mut Self,&mut Ui,&NewsCardData)) -> bool {
+        match row {
+            Row::Header { view, key, count, open } => {
+                let icon = if *open { "⏷" } else { "⏵" };
+                let header = ui.add(Label::new(RichText::new(format!("{} {} ({})",icon,group_name(key),count)).strong()).sense(Sense::click()));
+                if header.clicked() {
+                    let id = (*view, key.clone());
+                    if *open {
+                        self.collapsed.insert(id);
+                    } else {
+                        self.collapsed.remove(&id);
+                    }
+                }
+                header.clicked()
+            }
+            Row::Articles { articles : row, .. } => {
+                let row = row.iter().map(|&i| articles[i]);
+                match layout {
+                    layout::Layout::Compact => {
+                        for a in row {
+                            self.row_of(ui, a);
+                        }
+                    }
+                    layout::Layout::Cards => {
+                        for a in row {
+                            card(self, ui, a);
+                        }
+                    }
+                    layout::Layout::Magazine => {
+                        ui.columns(columns, |uis|{
+                            for (ui, a) in uis.iter_mut().zip(row) {
+                                self.tile(ui, a);
+                            }
+                        });
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    pub(crate) fn is_selected(&self,a : &NewsCardData) -> bool {
+        self.selected.get(&self.view) == Some(&a.url)
+    }
+
+    fn select(&mut self,a : &NewsCardData) {
+        self.selected.insert(self.view, a.url.clone());
+    }
+
+    /// scrolls to the title of the article when asked to
+    pub(crate) fn scroll(&mut self,title : &Response,a : &NewsCardData) {
+        if self.scroll_to.as_deref() == Some(a.url.as_str()) {
+            title.scroll_to_me(Some(Align::TOP));
+            *self.scroll_to = None;
+        }
+        if self.is_selected(a) && *self.scroll_to_selected {
+            title.scroll_to_me(None);
+            *self.scroll_to_selected = false;
+        }
+    }
+
+    /// the new and muted markers in front of a title
+    fn markers(&self,ui : &mut Ui,a : &NewsCardData) {
+        if self.state.history.is_new(&a.url) {
+            ui.colored_label(ORANGE, tr!("card-new")).on_hover_text(tr!("card-new-hint"));
+        }
+        if let Some(reason) = self.state.hidden_reason(a).filter(|_| self.state.show_hidden) {
+            ui.colored_label(GRAY, "🔇").on_hover_text(tr!("card-hidden", reason = reason.to_string()));
+        }
+    }
+
+    fn title_text(&self,a : &NewsCardData) -> RichText {
+        let read = self.state.history.is_read(&a.url);
+        let (marker, color) = if read { ("▫", GRAY) } else { ("🔹", self.theme.title()) };
+        RichText::new(format!("{} {}",marker,a.title)).color(color)
+    }
+
+    /// the links of a card, right to left
+    fn links(&mut self,ui : &mut Ui,a : &NewsCardData,mark_read : bool) {
+        let actions = &mut self.actions;
+        if reader_link(ui).clicked() {
+            actions.push(Action::OpenReader(a.clone()));
+        }
+        if ui.add(Hyperlink::from_label_and_url("🌐",&a.url)).describe(tr!("open-browser")).clicked() {
+            actions.push(Action::MarkRead(a.url.clone()));
+        }
+        if bookmark_button(ui, self.state.bookmarks.contains(&a.url)).clicked() {
+            actions.push(Action::ToggleBookmark(a.clone()));
+        }
+        actions.extend(archive_button(ui, self.state, a));
+        if mark_read {
+            let read = self.state.history.is_read(&a.url);
+            let (icon, hint) = if read { ("↺", tr!("mark-unread")) } else { ("✔", tr!("mark-read")) };
+            if ui.add(Button::new(icon).frame(false)).describe(hint).clicked() {
+                actions.push(if read { Action::MarkUnread(a.url.clone()) } else { Action::MarkRead(a.url.clone()) });
+            }
+        }
+    }
+
+    /// one line, the title opening the reader
+    fn row_of(&mut self,ui : &mut Ui,a : &NewsCardData) {
+        ui.horizontal(|ui|{
+            self.markers(ui, a);
+            let title = ui.add(SelectableLabel::new(self.is_selected(a), self.title_text(a)))
+                .on_hover_text(tr!("open-reader"));
+            self.scroll(&title, a);
+            if title.clicked() {
+                self.select(a);
+                self.actions.push(Action::OpenReader(a.clone()));
+            }
+            ui.with_layout(Layout::right_to_left(), |ui|{
+                self.links(ui, a, false);
+                if !a.source.is_empty() {
+                    ui.small(&a.source);
+                }
+            });
+        });
+    }
+
+    /// clicking the title expands the card, which like following its link marks it read
+    pub(crate) fn card(&mut self,ui : &mut Ui,a : &NewsCardData) {
+        let expanded = self.expanded.contains(&a.url);
+        let theme = self.theme;
+        theme.card(ui, self.is_selected(a), |ui|{
+            ui.horizontal_wrapped(|ui|{
+                self.markers(ui, a);
+                let title = ui.add(Label::new(self.title_text(a)).sense(Sense::click()))
+                    .on_hover_text(if expanded { tr!("card-collapse") } else { tr!("card-expand") });
+                self.scroll(&title, a);
+                if title.clicked() {
+                    self.select(a);
+                    if expanded {
+                        self.expanded.remove(&a.url);
+                    } else {
+                        self.expanded.insert(a.url.clone());
+                        self.actions.push(Action::MarkRead(a.url.clone()));
+                    }
+                }
+            });
+
+            //render desc, next to the thumbnail
+            ui.add_space(PADDING);
+            let desc = if expanded { a.desc.clone() } else { preview(&a.desc) };
+            let desc = Label::new(RichText::new(desc).text_style(TextStyle::Button)).wrap(true);
+            match a.image.as_deref().filter(|_| !self.state.config.hide_images) {
+                Some(image) => {
+                    ui.horizontal(|ui|{
+                        self.thumbnails.show(ui, image);
+                        ui.add(desc);
+                    });
+                }
+                None => { ui.add(desc); }
+            }
+
+            //render hyperlinks
+            ui.add_space(PADDING);
+            ui.with_layout(Layout::right_to_left(), |ui|{
+                self.links(ui, a, true);
+            });
+        });
+    }
+
+    /// the image across the column, then the title opening the reader
+    fn tile(&mut self,ui : &mut Ui,a : &NewsCardData) {
+        let theme = self.theme;
+        theme.card(ui, self.is_selected(a), |ui|{
+            if let Some(image) = a.image.as_deref().filter(|_| !self.state.config.hide_images) {
+                let width = ui.available_width();
+                self.thumbnails.show_sized(ui, image, Vec2::new(width, width * 0.56));
+                ui.add_space(PADDING);
+            }
+            ui.horizontal_wrapped(|ui|{
+                self.markers(ui, a);
+                let title = ui.add(Label::new(self.title_text(a).strong()).sense(Sense::click()))
+                    .on_hover_text(tr!("open-reader"));
+                self.scroll(&title, a);
+                if title.clicked() {
+                    self.select(a);
+                    self.actions.push(Action::OpenReader(a.clone()));
+                }
+            });
+            if !a.source.is_empty() {
+                ui.small(&a.source);
+            }
+            ui.add_space(PADDING);
+            ui.add(Label::new(RichText::new(preview(&a.desc)).text_style(TextStyle::Button)).wrap(true));
+            ui.add_space(PADDING);
+            ui.with_layout(Layout::right_to_left(), |ui|{
+                self.links(ui, a, true);
+            });
+        });
+    }
+}
@@ -0,0 +1,312 @@
+use newsApi::{Category, Country};
+
+use crate::{config::{validate_base_url, HeadlinesConfig}, profiles::validate_name};
+
+/// The options, printed by `--help`.
+pub const USAGE : &str = "\
+Options override the settings of the profile for this run only, changes made
+while the app runs are saved as usual.
+
+  --api-key <KEY>        newsapi.org api key
+  --country <CODE>       country of the top headlines, like us or fr
+  --category <NAME>      category of the top headlines, top for all of them
+  --query <KEYWORDS>     search the keywords instead of the top headlines
+  --profile <NAME>       profile to use, created if missing
+  --offline              only read the archive
+  --base-url <URL>       a server speaking the newsapi.org api
+  --window-size <WxH>    initial window size, like 1920x1080, window only
+  --theme <NAME>         a theme like dark or sepia, or system, window only
+  -h, --help             print this help
+
+Every option can also be set by a HEADLINES_* environment variable, like
+HEADLINES_API_KEY or HEADLINES_OFFLINE=true, the command line taking precedence.
+";
+
+/// Settings given on the command line or in the environment, layered over the
+/// persisted config without being saved with it.
+#[derive(Clone,Debug,Default,PartialEq)]
+pub struct Overrides {
+    pub api_key : Option<String>,
+    pub country : Option<Country>,
+    /// `Some(None)` for the top headlines of every category
+    pub category : Option<Option<Category>>,
+    pub query : Option<String>,
+    pub profile : Option<String>,
+    pub offline : Option<bool>,
+    pub base_url : Option<String>,
+    /// width and height of the window, in points
+    pub window_size : Option<(f32,f32)>,
+    /// a theme name, or `system` to follow the system one
+    pub theme : Option<String>,
+    /// `-h` or `--help` was given, the usage is printed instead of running
+    pub help : bool
+}
+
+impl Overrides {
+    /// the environment, then the command line over it, the command line alone once it asks for help
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(args : impl IntoIterator<Item = std::ffi::OsString>) -> Result<Overrides,String> {
+        let args = Overrides::from_args(args)?;
+        if args.help {
+            return Ok(args);
+        }
+        Ok(Overrides::from_env(std::env::vars())?.layer(args))
+    }
+
+    /// `HEADLINES_API_KEY` and the like, empty variables are left out
+    pub fn from_env(vars : impl Iterator<Item = (String,String)>) -> Result<Overrides,String> {
+        let mut overrides = Overrides::default();
+        for (key, value) in vars {
+            let option = match key.strip_prefix("HEADLINES_") {
+                Some(option) => option.to_lowercase().replace('_', "-"),
+                None => continue
+            };
+            if value.trim().is_empty() || !OPTIONS.contains(&option.as_str()) {
+                continue;
+            }
+            overrides.set(&option, &value).map_err(|e| format!("{} : {}",key,e))?;
+        }
+        Ok(overrides)
+    }
+
+    /// the arguments after the program name, up to `-h` or `--help` as an option
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_args(args : impl IntoIterator<Item = std::ffi::OsString>) -> Result<Overrides,String> {
+        use lexopt::prelude::*;
+
+        let mut overrides = Overrides::default();
+        let mut parser = lexopt::Parser::from_args(args);
+        while let Some(arg) = parser.next().map_err(|e| e.to_string())? {
+            match arg {
+                Short('h') | Long("help") => {
+                    overrides.help = true;
+                    return Ok(overrides);
+                }
+                Long("offline") => overrides.offline = Some(true),
+                Long(option) if OPTIONS.contains(&option) => {
+                    let option = option.to_string();
+                    let value = parser.value().and_then(|v| v.string()).map_err(|e| e.to_string())?;
+                    overrides.set(&option, &value).map_err(|e| format!("--{} : {}",option,e))?;
+                }
+                _ => return Err(arg.unexpected().to_string())
+            }
+        }
+        Ok(overrides)
+    }
+
+    fn set(&mut self,option : &str,value : &str) -> Result<(),String> {
+        let value = value.trim();
+        match option {
+            "api-key" => self.api_key = Some(value.to_string()),
+            "country" => self.country = Some(Country::ALL.into_iter()
+                .find(|c| c.code().eq_ignore_ascii_case(value))
+                .ok_or_else(|| format!("unknown country {}, use a code like us or fr",value))?),
+            "category" => self.category = Some(match value.to_lowercase().as_str() {
+                "top" | "all" => None,
+                name => Some(Category::ALL.into_iter()
+                    .find(|c| c.name().eq_ignore_ascii_case(name))
+                    .ok_or_else(|| format!("unknown category {}",value))?)
+            }),
+            "query" => self.query = Some(value.to_string()),
+            "profile" => {
+                validate_name(value)?;
+                self.profile = Some(value.to_string());
+            }
+            "offline" => self.offline = Some(match value.to_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => true,
+                "0" | "false" | "no" | "off" => false,
+                _ => return Err(format!("expected true or false, not {}",value))
+            }),
+            "base-url" => {
+                validate_base_url(value)?;
+                self.base_url = Some(value.to_string());
+            }
+            "window-size" => self.window_size = Some(value.split_once('x')
+                .and_then(|(w, h)| Some((w.trim().parse::<f32>().ok()?, h.trim().parse::<f32>().ok()?)))
+                .filter(|(w, h)| *w >= 200. && *h >= 200.)
+                .ok_or_else(|| format!("expected a size like 1920x1080, of at least 200x200, not {}",value))?),
+            "theme" => {
+                if value.is_empty() {
+                    return Err("expected a theme name".to_string());
+                }
+                self.theme = Some(value.to_string());
+            }
+            _ => return Err(format!("unknown option {}",option))
+        }
+        Ok(())
+    }
+
+    /// `over` wherever it is set, `self` elsewhere
+    pub fn layer(self,over : Overrides) -> Overrides {
+        Overrides {
+            api_key : over.api_key.or(self.api_key),
+            country : over.country.or(self.country),
+            category : over.category.or(self.category),
+            query : over.query.or(self.query),
+            profile : over.profile.or(self.profile),
+            offline : over.offline.or(self.offline),
+            base_url : over.base_url.or(self.base_url),
+            window_size : over.window_size.or(self.window_size),
+            theme : over.theme.or(self.theme),
+            help : over.help || self.help
+        }
+    }
+
+    /// `config` with the overrides applied
+    pub fn apply(&self,mut config : HeadlinesConfig) -> HeadlinesConfig {
+        if let Some(api_key) = &self.api_key {
+            config.api_key = api_key.clone();
+        }
+        if let Some(country) = self.country {
+            config.query.country = country;
+        }
+        if let Some(category) = self.category {
+            config.query = config.query.with_category(category);
+        }
+        if let Some(query) = &self.query {
+            config.query = config.query.with_keywords(query);
+        }
+        if let Some(offline) = self.offline {
+            config.offline = offline;
+        }
+        if let Some(base_url) = &self.base_url {
+            config.base_url = base_url.clone();
+        }
+        match self.theme.as_deref() {
+            Some(theme) if theme.eq_ignore_ascii_case("system") => config.follow_system_theme = true,
+            Some(theme) => {
+                config.theme = theme.to_string();
+                config.follow_system_theme = false;
+            }
+            None => {}
+        }
+        config
+    }
+
+    /// `config` as it should be saved, the overridden settings left untouched since
+    /// launch taking their value from `persisted` again
+    pub(crate) fn restore(&self,mut config : HeadlinesConfig,persisted : &HeadlinesConfig) -> HeadlinesConfig {
+        let launched = self.apply(persisted.clone());
+        if config.api_key == launched.api_key {
+            config.api_key = persisted.api_key.clone();
+        }
+        if config.query == launched.query {
+            config.query = persisted.query.clone();
+        }
+        if config.offline == launched.offline {
+            config.offline = persisted.offline;
+        }
+        if config.base_url == launched.base_url {
+            config.base_url = persisted.base_url.clone();
+        }
+        if config.theme == launched.theme && config.follow_system_theme == launched.follow_system_theme {
+            config.theme = persisted.theme.clone();
+            config.follow_system_theme = persisted.follow_system_theme;
+        }
+        config
+    }
+}
+
+/// the options taking a value, and `offline`
+const OPTIONS : [&str; 9] = ["api-key", "country", "category", "query", "profile", "offline", "base-url", "window-size", "theme"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn args(args : &[&str]) -> Result<Overrides,String> {
+        Overrides::from_args(args.iter().map(std::ffi::OsString::from))
+    }
+
+    fn env(vars : &[(&str,&str)]) -> Result<Overrides,String> {
+        Overrides::from_env(vars.iter().map(|(key, value)| (key.to_string(), value.to_string())))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn parses_the_command_line() {
+        let overrides = args(&["--api-key", "key", "--country", "FR", "--category", "technology", "--offline", "--window-size", "1280x720", "--theme=dark"]).unwrap();
+        assert_eq!(overrides, Overrides {
+            api_key : Some("key".to_string()),
+            country : Some(Country::Fr),
+            category : Some(Some(Category::Technology)),
+            offline : Some(true),
+            window_size : Some((1280., 720.)),
+            theme : Some("dark".to_string()),
+            ..Overrides::default()
+        });
+        assert_eq!(args(&["--category", "top"]).unwrap().category, Some(None));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn help_is_an_option_not_a_value() {
+        assert!(args(&["-h"]).unwrap().help);
+        assert!(args(&["--country", "fr", "--help", "--unknown"]).unwrap().help);
+        let overrides = args(&["--profile", "--help"]).unwrap();
+        assert!(!overrides.help);
+        assert_eq!(overrides.profile.as_deref(), Some("--help"));
+        let overrides = args(&["--query", "-h"]).unwrap();
+        assert!(!overrides.help);
+        assert_eq!(overrides.query.as_deref(), Some("-h"));
+        assert!(!args(&["--query=--help"]).unwrap().help);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(args(&["--country", "xx"]).unwrap_err().starts_with("--country : unknown country xx"));
+        assert!(args(&["--window-size", "100x100"]).is_err());
+        assert!(args(&["--profile", "no spaces"]).is_err());
+        assert!(args(&["--api-key"]).is_err());
+        assert!(args(&["--unknown"]).is_err());
+        assert!(args(&["positional"]).is_err());
+    }
+
+    #[test]
+    fn reads_the_environment() {
+        let overrides = env(&[("HEADLINES_API_KEY", "key"), ("HEADLINES_OFFLINE", "off"), ("HEADLINES_QUERY", " "), ("PATH", "/bin")]).unwrap();
+        assert_eq!(overrides, Overrides { api_key : Some("key".to_string()), offline : Some(false), ..Overrides::default() });
+        assert_eq!(env(&[("HEADLINES_OFFLINE", "maybe")]).unwrap_err(), "HEADLINES_OFFLINE : expected true or false, not maybe");
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn the_command_line_wins_over_the_environment() {
+        let env = env(&[("HEADLINES_API_KEY", "env key"), ("HEADLINES_COUNTRY", "de"), ("HEADLINES_THEME", "sepia")]).unwrap();
+        let layered = env.layer(args(&["--api-key", "arg key", "--offline"]).unwrap());
+        assert_eq!(layered.api_key.as_deref(), Some("arg key"));
+        assert_eq!(layered.country, Some(Country::De));
+        assert_eq!(layered.theme.as_deref(), Some("sepia"));
+        assert_eq!(layered.offline, Some(true));
+    }
+
+    #[test]
+    fn overrides_win_over_the_file() {
+        let persisted = HeadlinesConfig { api_key : "file key".to_string(), theme : "Light".to_string(), ..HeadlinesConfig::default() };
+        let overrides = Overrides { api_key : Some("env key".to_string()), theme : Some("system".to_string()), ..Overrides::default() };
+        let config = overrides.apply(persisted.clone());
+        assert_eq!(config.api_key, "env key");
+        assert_eq!(config.theme, "Light");
+        assert!(config.follow_system_theme);
+        assert_eq!(config.query, persisted.query);
+    }
+
+    #[test]
+    fn restore_keeps_the_overrides_out_of_the_saved_config() {
+        let persisted = HeadlinesConfig { api_key : "file key".to_string(), offline : false, ..HeadlinesConfig::default() };
+        let overrides = Overrides { api_key : Some("env key".to_string()), offline : Some(true), query : Some("rust".to_string()), ..Overrides::default() };
+        let mut config = overrides.apply(persisted.clone());
+        assert!(config.offline);
+        // changed while the app ran, an overridden setting included
+        config.query = config.query.with_keywords("go");
+        config.refresh_minutes = persisted.refresh_minutes + 5;
+
+        let saved = overrides.restore(config, &persisted);
+        assert_eq!(saved.api_key, "file key");
+        assert!(!saved.offline);
+        assert_eq!(saved.query.keywords, "go");
+        assert_eq!(saved.refresh_minutes, persisted.refresh_minutes + 5);
+    }
+}
@@ -0,0 +1,192 @@
+use serde::{Serialize,Deserialize};
+
+use crate::{filters::MuteFilters, l10n::{Locale, Message}, layout::Layouts, message, query::NewsQuery, shortcuts::Shortcuts};
+
+/// Fonts the text can be shown in, the first one is bundled with the app.
+pub const FONTS : [&str; 2] = ["Spartan", "Ubuntu-Light"];
+
+/// Bounds of [`HeadlinesConfig::zoom`].
+pub const MIN_ZOOM : f32 = 0.5;
+pub const MAX_ZOOM : f32 = 3.;
+
+/// Theme of new profiles, built in like [`DARK_THEME`].
+pub const DEFAULT_THEME : &str = "Light";
+pub const DARK_THEME : &str = "Dark";
+pub const HIGH_CONTRAST_THEME : &str = "High contrast";
+
+/// The settings of one profile, see [`crate::Profiles`].
+// missing fields fall back to their default so older config files keep loading
+#[derive(Clone,Debug,PartialEq,Serialize,Deserialize)]
+#[serde(default)]
+pub struct HeadlinesConfig {
+   /// language of the interface, like fr, empty for the one of the system
+   pub locale: String,
+   /// name of a built-in or user theme, see [`DEFAULT_THEME`]
+   pub theme: String,
+   /// use the light or dark theme the system is set to instead, where it reports it
+   pub follow_system_theme: bool,
+   /// the high contrast theme, whatever the two settings above
+   pub high_contrast: bool,
+   pub api_key: String,
+   /// text only cards, for low bandwidth
+   pub hide_images: bool,
+   /// serve everything from the archive, no network
+   pub offline: bool,
+   /// archive articles as soon as they are bookmarked
+   pub auto_archive: bool,
+   /// minutes between automatic refreshes, 0 to only refresh on demand
+   pub refresh_minutes: u32,
+   /// one of [`FONTS`]
+   pub font: String,
+   /// a ttf or otf file used before `font`, empty for none, native only
+   pub font_file: String,
+   /// ttf, otf or ttc files for the glyphs the fonts lack, after the CJK fonts found on the system
+   pub fallback_fonts: Vec<String>,
+   /// scale of the whole window, on top of the one of the screen
+   pub zoom: f32,
+   /// no animations
+   pub reduced_motion: bool,
+   /// describe the widgets clicked or focused to the screen reader, where the build supports it
+   pub screen_reader: bool,
+   /// size of the thumbnail disk cache, in megabytes
+   pub thumbnail_cache_mb: u32,
+   /// days the read and seen articles are remembered
+   pub history_days: u32,
+   /// seconds before a request is given up, native only
+   pub timeout_secs: u32,
+   /// `http://host:port`, empty for a direct connection, native only
+   pub proxy: String,
+   /// a server speaking the newsapi.org api, empty for newsapi.org itself
+   pub base_url: String,
+   /// sizes of the text styles, in points
+   pub text_sizes: TextSizes,
+   /// layout and grouping of each view
+   pub layouts: Layouts,
+   /// keyboard shortcuts of the window
+   pub shortcuts: Shortcuts,
+   /// articles hidden from every feed
+   pub filters: MuteFilters,
+   /// the last query, restored on start
+   pub query: NewsQuery
+}
+
+impl Default for HeadlinesConfig {
+    fn default() -> Self {
+        HeadlinesConfig {
+            locale : String::new(),
+            theme : DEFAULT_THEME.to_string(),
+            follow_system_theme : false,
+            high_contrast : false,
+            api_key : String::new(),
+            hide_images : false,
+            offline : false,
+            auto_archive : false,
+            refresh_minutes : 0,
+            font : FONTS[0].to_string(),
+            font_file : String::new(),
+            fallback_fonts : Vec::new(),
+            zoom : 1.,
+            reduced_motion : false,
+            screen_reader : false,
+            thumbnail_cache_mb : 50,
+            history_days : 30,
+            timeout_secs : 30,
+            proxy : String::new(),
+            base_url : String::new(),
+            text_sizes : TextSizes::default(),
+            layouts : Layouts::default(),
+            shortcuts : Shortcuts::default(),
+            filters : MuteFilters::default(),
+            query : NewsQuery::default()
+        }
+    }
+}
+
+impl HeadlinesConfig {
+    /// the settings of a new profile in the language `locale`, empty for the one of
+    /// the system, its feed in that language too
+    pub fn localized(locale : &str) -> HeadlinesConfig {
+        let mut config = HeadlinesConfig { locale : locale.to_string(), ..HeadlinesConfig::default() };
+        let (language, country) = Locale::from_setting(locale).news_defaults();
+        config.query.language = Some(language);
+        config.query.country = country;
+        config
+    }
+
+    /// the first invalid setting, if any
+    pub fn validate(&self) -> Result<(),Message> {
+        if !self.locale.is_empty() && Locale::from_tag(&self.locale).is_none() {
+            return Err(message!("invalid-locale", locale = self.locale.as_str(), locales = Locale::ALL.map(|l| l.code()).join(", ")));
+        }
+        if self.theme.trim().is_empty() {
+            return Err(message!("invalid-no-theme"));
+        }
+        if !FONTS.contains(&self.font.as_str()) {
+            return Err(message!("invalid-font", font = self.font.as_str()));
+        }
+        self.text_sizes.validate()?;
+        self.shortcuts.validate()?;
+        if !(MIN_ZOOM..=MAX_ZOOM).contains(&self.zoom) {
+            return Err(message!("invalid-zoom", min = MIN_ZOOM * 100., max = MAX_ZOOM * 100.));
+        }
+        for file in std::iter::once(&self.font_file).chain(&self.fallback_fonts).filter(|file| !file.is_empty()) {
+            let extension = std::path::Path::new(file).extension().and_then(|e| e.to_str()).unwrap_or_default();
+            if !["ttf", "otf", "ttc"].contains(&extension.to_lowercase().as_str()) {
+                return Err(message!("invalid-font-file", file = file.as_str()));
+            }
+        }
+        if !(1..=1024).contains(&self.thumbnail_cache_mb) {
+            return Err(message!("invalid-thumbnail-cache"));
+        }
+        if !(1..=365).contains(&self.history_days) {
+            return Err(message!("invalid-history-days"));
+        }
+        if !(1..=300).contains(&self.timeout_secs) {
+            return Err(message!("invalid-timeout"));
+        }
+        crate::net::validate_proxy(&self.proxy)?;
+        validate_base_url(&self.base_url)?;
+        self.filters.validate()?;
+        self.query.validate()
+    }
+}
+
+/// Sizes of the text styles, in points.
+#[derive(Clone,Debug,PartialEq,Serialize,Deserialize)]
+#[serde(default)]
+pub struct TextSizes {
+    pub small : f32,
+    pub body : f32,
+    /// buttons and the article descriptions
+    pub button : f32,
+    pub heading : f32,
+    pub monospace : f32
+}
+
+impl Default for TextSizes {
+    fn default() -> Self {
+        TextSizes { small : 10., body : 14., button : 14., heading : 20., monospace : 14. }
+    }
+}
+
+impl TextSizes {
+    pub fn validate(&self) -> Result<(),Message> {
+        for size in [self.small, self.body, self.button, self.heading, self.monospace] {
+            if !(6. ..=48.).contains(&size) {
+                return Err(message!("invalid-text-size"));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn validate_base_url(base_url : &str) -> Result<(),Message> {
+    if base_url.trim().is_empty() {
+        return Ok(());
+    }
+    match url::Url::parse(base_url.trim()) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(()),
+        Ok(_) => Err(message!("invalid-base-url-scheme")),
+        Err(e) => Err(message!("invalid-base-url", error = e.to_string()))
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+
+use newsApi::NewsApiError;
+
+use crate::tr;
+
+/// Why a fetch failed, in terms the UI can act on.
+#[derive(Clone,Debug,PartialEq)]
+pub enum FetchError {
+    MissingApiKey,
+    /// the key was rejected, the user has to enter another one
+    InvalidApiKey(String),
+    /// the daily quota is used up or too many requests were sent
+    RateLimited(String),
+    /// any other error reported by newsapi.org
+    Api { code : String, message : String },
+    /// the request never got an answer
+    Network(String),
+    /// the answer could not be understood
+    InvalidResponse(String)
+}
+
+impl FetchError {
+    pub fn is_invalid_api_key(&self) -> bool {
+        matches!(self, FetchError::MissingApiKey | FetchError::InvalidApiKey(_))
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::MissingApiKey => write!(f, "{}", tr!("error-no-api-key")),
+            FetchError::InvalidApiKey(message) => write!(f, "{}", message),
+            FetchError::RateLimited(message) => write!(f, "{}", message),
+            FetchError::Api { message, .. } => write!(f, "{}", message),
+            FetchError::Network(message) => write!(f, "{}", tr!("error-network", message = message.as_str())),
+            FetchError::InvalidResponse(message) => write!(f, "{}", tr!("error-response", message = message.as_str()))
+        }
+    }
+}
+
+impl From<NewsApiError> for FetchError {
+    fn from(e: NewsApiError) -> Self {
+        match e {
+            NewsApiError::Api { code, message } => match code.as_str() {
+                "apiKeyInvalid" | "apiKeyDisabled" | "apiKeyMissing" => FetchError::InvalidApiKey(message),
+                "apiKeyExhausted" | "rateLimited" => FetchError::RateLimited(message),
+                _ => FetchError::Api { code, message }
+            },
+            NewsApiError::FailedParsingArticle(_) | NewsApiError::FailedToresponseToString(_) => {
+                FetchError::InvalidResponse(e.to_string())
+            }
+            _ => FetchError::Network(e.to_string())
+        }
+    }
+}
@@ -0,0 +1,456 @@
+//! Readable content of an article page, independent of the network and of the UI.
+//!
+//! A small take on the readability algorithm: paragraphs score the element holding
+//! them, the best scoring element is kept as the article body and converted to
+//! [`Block`]s, leaving out navigation, ads, share buttons and other boilerplate.
+//! It only needs the page html, so saved pages can be extracted offline.
+
+use std::collections::HashMap;
+
+use ego_tree::{NodeId, NodeRef};
+use scraper::{ElementRef, Html, Node, Selector};
+use serde::{Serialize,Deserialize};
+use url::Url;
+
+/// Class or id words of boilerplate, unless they also look like content.
+/// Compared to the whole words of the hints, in the singular.
+const UNLIKELY : [&str; 27] = [
+    "ad", "advert", "advertisement", "banner", "breadcrumb", "comment", "cookie", "footer",
+    "header", "menu", "meta", "modal", "nav", "navbar", "navigation", "newsletter", "popup",
+    "promo", "related", "share", "sharing", "sidebar", "social", "sponsor", "subscribe", "tag",
+    "widget"
+];
+const LIKELY : [&str; 8] = ["article", "body", "content", "entry", "main", "page", "post", "story"];
+/// Elements never part of the readable content.
+const SKIPPED : [&str; 17] = [
+    "aside", "button", "canvas", "embed", "footer", "form", "head", "iframe", "input", "nav",
+    "noscript", "object", "script", "select", "style", "svg", "template"
+];
+/// Paragraphs shorter than this do not score their parents.
+const MIN_PARAGRAPH_LEN : usize = 25;
+
+/// The readable part of an article page.
+#[derive(Clone,Debug,Default,PartialEq,Serialize,Deserialize)]
+#[serde(default)]
+pub struct Document {
+    pub title : String,
+    pub byline : Option<String>,
+    /// absolute url of the main image
+    pub lead_image : Option<String>,
+    pub blocks : Vec<Block>
+}
+
+impl Document {
+    /// false when nothing readable was found
+    pub fn has_content(&self) -> bool {
+        self.blocks.iter().any(|b| matches!(b, Block::Paragraph(_) | Block::Quote(_) | Block::ListItem(_)))
+    }
+}
+
+#[derive(Clone,Debug,PartialEq,Serialize,Deserialize)]
+pub enum Block {
+    Heading { level : u8, spans : Vec<Span> },
+    Paragraph(Vec<Span>),
+    Quote(Vec<Span>),
+    ListItem(Vec<Span>),
+    Code(String),
+    Image { src : String, alt : String }
+}
+
+/// A run of text sharing the same formatting.
+#[derive(Clone,Debug,Default,PartialEq,Serialize,Deserialize)]
+#[serde(default)]
+pub struct Span {
+    pub text : String,
+    /// absolute url when the text is a link
+    pub link : Option<String>,
+    pub emphasis : bool,
+    pub strong : bool,
+    pub code : bool
+}
+
+impl Span {
+    fn same_style(&self,other : &Span) -> bool {
+        self.link == other.link && self.emphasis == other.emphasis && self.strong == other.strong && self.code == other.code
+    }
+}
+
+/// text of the spans, without formatting
+pub fn plain_text(spans : &[Span]) -> String {
+    spans.iter().map(|s| s.text.as_str()).collect()
+}
+
+/// Extracts the readable content of `html`, `base_url` resolves relative links and images.
+pub fn extract(html : &str,base_url : Option<&str>) -> Document {
+    let page = Html::parse_document(html);
+    let base = base_url.and_then(|u| Url::parse(u).ok());
+
+    let body = best_candidate(&page)
+        .or_else(|| select_first(&page, "body"))
+        .unwrap_or_else(|| page.root_element());
+    let mut builder = Builder { base : base.as_ref(), blocks : Vec::new(), spans : Vec::new(), quote_depth : 0 };
+    builder.walk(*body, &Style::default());
+    builder.flush(BlockKind::Paragraph);
+
+    let title = title(&page);
+    let lead_image = meta(&page, &["og:image", "twitter:image"])
+        .and_then(|src| resolve(base.as_ref(), &src))
+        .or_else(|| builder.blocks.iter().find_map(|b| match b {
+            Block::Image { src, .. } => Some(src.clone()),
+            _ => None
+        }));
+
+    let mut blocks = builder.blocks;
+    // the title and lead image are shown on their own
+    if let Some(Block::Heading { spans, .. }) = blocks.first() {
+        if plain_text(spans).trim() == title {
+            blocks.remove(0);
+        }
+    }
+    if let Some(lead) = &lead_image {
+        if let Some(i) = blocks.iter().position(|b| matches!(b, Block::Image { src, .. } if src == lead)) {
+            blocks.remove(i);
+        }
+    }
+
+    Document { title, byline : byline(&page), lead_image, blocks }
+}
+
+fn select_first<'a>(page : &'a Html,selector : &str) -> Option<ElementRef<'a>> {
+    let selector = Selector::parse(selector).ok()?;
+    page.select(&selector).next()
+}
+
+/// content of the first `<meta>` with one of these properties or names
+fn meta(page : &Html,names : &[&str]) -> Option<String> {
+    for name in names {
+        let selector = format!("meta[property=\"{0}\"], meta[name=\"{0}\"]",name);
+        let content = select_first(page, &selector)
+            .and_then(|el| el.value().attr("content"))
+            .map(collapse_whitespace)
+            .filter(|c| !c.is_empty());
+        if content.is_some() {
+            return content;
+        }
+    }
+    None
+}
+
+fn title(page : &Html) -> String {
+    meta(page, &["og:title", "twitter:title"])
+        .or_else(|| select_first(page, "title").map(|t| text_of(t)))
+        .or_else(|| select_first(page, "h1").map(|t| text_of(t)))
+        .unwrap_or_default()
+}
+
+fn byline(page : &Html) -> Option<String> {
+    let from_meta = meta(page, &["author", "article:author"])
+        .filter(|author| !author.starts_with("http"));
+    if from_meta.is_some() {
+        return from_meta;
+    }
+    let selector = Selector::parse("[rel=\"author\"], [itemprop=\"author\"], .byline, .author").ok()?;
+    page.select(&selector)
+        .map(text_of)
+        .find(|author| !author.is_empty() && author.len() <= 100)
+}
+
+/// the element holding the best scoring paragraphs
+fn best_candidate(page : &Html) -> Option<ElementRef<'_>> {
+    let paragraphs = Selector::parse("p, pre, td").ok()?;
+    let mut scores : HashMap<NodeId,f64> = HashMap::new();
+    for paragraph in page.select(&paragraphs) {
+        if paragraph.ancestors().filter_map(ElementRef::wrap).any(|el| is_skipped(&el) || is_unlikely(&el)) {
+            continue;
+        }
+        let text = text_of(paragraph);
+        if text.len() < MIN_PARAGRAPH_LEN {
+            continue;
+        }
+        let score = 1. + text.matches(',').count() as f64 + (text.len() as f64 / 100.).min(3.);
+        let mut ancestors = paragraph.ancestors().filter_map(ElementRef::wrap);
+        for (ancestor, share) in ancestors.by_ref().take(2).zip([1., 0.5]) {
+            *scores.entry(ancestor.id()).or_insert_with(|| base_score(&ancestor)) += score * share;
+        }
+    }
+    scores.into_iter()
+        .filter_map(|(id,score)| {
+            let el = ElementRef::wrap(page.tree.get(id)?)?;
+            Some((el, score * (1. - link_density(&el))))
+        })
+        .max_by(|(_,a),(_,b)| a.total_cmp(b))
+        .map(|(el,_)| el)
+}
+
+fn base_score(el : &ElementRef) -> f64 {
+    let tag = match el.value().name() {
+        "article" => 10.,
+        "div" => 5.,
+        "pre" | "td" | "blockquote" => 3.,
+        "ol" | "ul" | "dl" | "form" => -3.,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.,
+        _ => 0.
+    };
+    let hints = class_and_id(el);
+    let class = if has_hint(&hints, &LIKELY) {
+        25.
+    } else if has_hint(&hints, &UNLIKELY) {
+        -25.
+    } else {
+        0.
+    };
+    tag + class
+}
+
+fn class_and_id(el : &ElementRef) -> String {
+    let value = el.value();
+    format!("{} {}",value.attr("class").unwrap_or(""),value.id().unwrap_or("")).to_lowercase()
+}
+
+/// whether a word of `hints`, split on whitespace, `-` and `_`, is one of `words` or its plural
+fn has_hint(hints : &str,words : &[&str]) -> bool {
+    hints.split(|c : char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|hint| !hint.is_empty())
+        .any(|hint| words.iter().any(|w| hint == *w || hint.strip_suffix('s') == Some(*w)))
+}
+
+fn is_skipped(el : &ElementRef) -> bool {
+    let value = el.value();
+    SKIPPED.contains(&value.name())
+        || value.attr("hidden").is_some()
+        || value.attr("aria-hidden") == Some("true")
+        || value.attr("style").is_some_and(|s| s.replace(' ', "").contains("display:none"))
+}
+
+fn is_unlikely(el : &ElementRef) -> bool {
+    if matches!(el.value().name(), "body" | "article" | "main") {
+        return false;
+    }
+    let hints = class_and_id(el);
+    has_hint(&hints, &UNLIKELY) && !has_hint(&hints, &LIKELY)
+}
+
+/// share of the text that is links
+fn link_density(el : &ElementRef) -> f64 {
+    let total = text_of(*el).len();
+    if total == 0 {
+        return 0.;
+    }
+    let links : usize = Selector::parse("a").map(|a| el.select(&a).map(|a| text_of(a).len()).sum()).unwrap_or(0);
+    links as f64 / total as f64
+}
+
+fn text_of(el : ElementRef) -> String {
+    collapse_whitespace(&el.text().collect::<String>())
+}
+
+fn collapse_whitespace(text : &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn resolve(base : Option<&Url>,href : &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() || href.starts_with("javascript:") || href.starts_with("data:") || href.starts_with('#') {
+        return None;
+    }
+    match base {
+        Some(base) => base.join(href).ok().map(String::from),
+        None => Url::parse(href).ok().map(String::from)
+    }
+}
+
+#[derive(Clone,Default)]
+struct Style {
+    link : Option<String>,
+    emphasis : bool,
+    strong : bool,
+    code : bool
+}
+
+enum BlockKind {
+    Heading(u8),
+    Paragraph,
+    ListItem
+}
+
+/// turns the candidate tree into blocks, collecting inline text in `spans`
+struct Builder<'a> {
+    base : Option<&'a Url>,
+    blocks : Vec<Block>,
+    spans : Vec<Span>,
+    /// paragraphs inside a blockquote become quotes
+    quote_depth : usize
+}
+
+impl Builder<'_> {
+    fn walk(&mut self,node : NodeRef<Node>,style : &Style) {
+        match node.value() {
+            Node::Text(text) => self.push_text(text, style),
+            Node::Element(_) => {
+                if let Some(el) = ElementRef::wrap(node) {
+                    self.element(el, style);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn children(&mut self,el : ElementRef,style : &Style) {
+        for child in el.children() {
+            self.walk(child, style);
+        }
+    }
+
+    fn element(&mut self,el : ElementRef,style : &Style) {
+        if is_skipped(&el) || (is_unlikely(&el) && link_density(&el) > 0.3) {
+            return;
+        }
+        let name = el.value().name();
+        match name {
+            "br" => self.push_text("\n", style),
+            "img" => {
+                self.flush(BlockKind::Paragraph);
+                if let Some(image) = self.image(&el) {
+                    self.blocks.push(image);
+                }
+            }
+            "pre" => {
+                self.flush(BlockKind::Paragraph);
+                let code = el.text().collect::<String>();
+                if !code.trim().is_empty() {
+                    self.blocks.push(Block::Code(code.trim_end().to_string()));
+                }
+            }
+            "p" => self.block(el, style, BlockKind::Paragraph),
+            "li" | "dt" | "dd" => self.block(el, style, BlockKind::ListItem),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse().unwrap_or(2);
+                self.block(el, style, BlockKind::Heading(level));
+            }
+            "blockquote" => {
+                self.flush(BlockKind::Paragraph);
+                self.quote_depth += 1;
+                self.children(el, style);
+                self.flush(BlockKind::Paragraph);
+                self.quote_depth -= 1;
+            }
+            "a" => {
+                let link = el.value().attr("href").and_then(|href| resolve(self.base, href));
+                self.children(el, &Style { link : link.or_else(|| style.link.clone()), ..style.clone() });
+            }
+            "em" | "i" | "cite" => self.children(el, &Style { emphasis : true, ..style.clone() }),
+            "strong" | "b" => self.children(el, &Style { strong : true, ..style.clone() }),
+            "code" | "kbd" | "samp" => self.children(el, &Style { code : true, ..style.clone() }),
+            "span" | "small" | "sup" | "sub" | "time" | "abbr" | "mark" | "u" | "s" | "q" | "label" | "font" => {
+                self.children(el, style);
+            }
+            // block containers: div, section, article, figure, lists, tables...
+            _ => {
+                self.flush(BlockKind::Paragraph);
+                self.children(el, style);
+                self.flush(BlockKind::Paragraph);
+            }
+        }
+    }
+
+    fn block(&mut self,el : ElementRef,style : &Style,kind : BlockKind) {
+        self.flush(BlockKind::Paragraph);
+        self.children(el, style);
+        self.flush(kind);
+    }
+
+    fn image(&self,el : &ElementRef) -> Option<Block> {
+        let value = el.value();
+        // tracking pixels and icons
+        let tiny = ["width", "height"].iter()
+            .filter_map(|a| value.attr(a)?.trim_end_matches("px").parse::<u32>().ok())
+            .any(|size| size < 50);
+        if tiny {
+            return None;
+        }
+        let src = ["data-src", "src"].iter().find_map(|a| value.attr(a))?;
+        Some(Block::Image {
+            src : resolve(self.base, src)?,
+            alt : collapse_whitespace(value.attr("alt").unwrap_or(""))
+        })
+    }
+
+    fn push_text(&mut self,text : &str,style : &Style) {
+        // whitespace collapses to a single space, like a browser would
+        let mut collapsed = String::with_capacity(text.len());
+        let mut last_space = false;
+        for c in text.chars() {
+            if c == '\n' && text == "\n" {
+                collapsed.push('\n');
+            } else if c.is_whitespace() {
+                if !last_space {
+                    collapsed.push(' ');
+                }
+                last_space = true;
+                continue;
+            } else {
+                collapsed.push(c);
+            }
+            last_space = false;
+        }
+        if collapsed.is_empty() {
+            return;
+        }
+        let span = Span {
+            text : collapsed,
+            link : style.link.clone(),
+            emphasis : style.emphasis,
+            strong : style.strong,
+            code : style.code
+        };
+        match self.spans.last_mut() {
+            Some(last) if last.same_style(&span) => last.text.push_str(&span.text),
+            _ => self.spans.push(span)
+        }
+    }
+
+    /// ends the inline text collected so far as a block of `kind`
+    fn flush(&mut self,kind : BlockKind) {
+        let mut spans = std::mem::take(&mut self.spans);
+        if let Some(first) = spans.first_mut() {
+            first.text = first.text.trim_start().to_string();
+        }
+        if let Some(last) = spans.last_mut() {
+            last.text = last.text.trim_end().to_string();
+        }
+        spans.retain(|s| !s.text.is_empty());
+        if spans.iter().all(|s| s.text.trim().is_empty()) {
+            return;
+        }
+        let block = match kind {
+            BlockKind::Heading(level) => Block::Heading { level, spans },
+            BlockKind::ListItem => Block::ListItem(spans),
+            BlockKind::Paragraph if self.quote_depth > 0 => Block::Quote(spans),
+            BlockKind::Paragraph => {
+                // lists of links left in the body, like "read also" boxes
+                let text_len = plain_text(&spans).len();
+                let link_len : usize = spans.iter().filter(|s| s.link.is_some()).map(|s| s.text.len()).sum();
+                if link_len * 2 > text_len && text_len < 200 {
+                    return;
+                }
+                Block::Paragraph(spans)
+            }
+        };
+        self.blocks.push(block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_are_whole_words() {
+        assert!(has_hint("ad-slot banner", &UNLIKELY));
+        assert!(has_hint("top_ads", &UNLIKELY));
+        assert!(has_hint("comments", &UNLIKELY));
+        for hint in ["lead-story", "masthead-", "read-more", "uploads", "shared-content"] {
+            assert!(!has_hint(hint, &UNLIKELY), "{} looks like boilerplate",hint);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Serialize,Deserialize};
+
+use crate::{l10n::Message, message, news::NewsCardData};
+
+/// Articles never to be shown, persisted with the config.
+#[derive(Clone,Debug,Default,PartialEq,Eq,Serialize,Deserialize)]
+#[serde(default)]
+pub struct MuteFilters {
+    /// characters of description below which an article is hidden, 0 to keep them all
+    pub min_description : usize,
+    /// case insensitive regular expressions, matched against the title and the description
+    pub keywords : Vec<String>,
+    /// publication names, as newsapi.org reports them
+    pub sources : Vec<String>,
+    /// domains, their subdomains included
+    pub domains : Vec<String>
+}
+
+impl MuteFilters {
+    pub fn is_empty(&self) -> bool {
+        self.min_description == 0 && self.keywords.is_empty() && self.sources.is_empty() && self.domains.is_empty()
+    }
+
+    /// the first keyword that is not a valid regular expression, if any
+    pub fn validate(&self) -> Result<(),Message> {
+        for keyword in &self.keywords {
+            keyword_regex(keyword).map_err(|e| message!("invalid-muted-keyword", keyword = keyword.as_str(), error = e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+fn keyword_regex(keyword : &str) -> Result<Regex,regex::Error> {
+    RegexBuilder::new(keyword).case_insensitive(true).build()
+}
+
+/// One stage of the [`MutePipeline`].
+enum Stage {
+    Keyword(Regex),
+    Source(String),
+    Domain(String),
+    ShortDescription(usize)
+}
+
+impl Stage {
+    /// why the stage hides `article`, if it does
+    fn hides(&self,article : &NewsCardData) -> Option<Message> {
+        match self {
+            Stage::Keyword(regex) => (regex.is_match(&article.title) || regex.is_match(&article.desc))
+                .then(|| message!("hidden-keyword", keyword = regex.as_str().to_string())),
+            Stage::Source(source) => article.source.eq_ignore_ascii_case(source)
+                .then(|| message!("hidden-source", source = article.source.as_str())),
+            Stage::Domain(domain) => article.domain()
+                .filter(|d| d == domain || d.ends_with(&format!(".{}",domain)))
+                .map(|d| message!("hidden-domain", domain = d)),
+            Stage::ShortDescription(min) => (article.desc.trim().chars().count() < *min)
+                .then(|| message!("hidden-short-description", count = *min))
+        }
+    }
+}
+
+/// [`MuteFilters`] compiled once, run on the fetched articles before they reach the feeds.
+#[derive(Default)]
+pub struct MutePipeline {
+    stages : Vec<Stage>
+}
+
+impl MutePipeline {
+    /// invalid keywords are left out, [`MuteFilters::validate`] reports them
+    pub fn new(filters : &MuteFilters) -> MutePipeline {
+        let mut stages = Vec::new();
+        if filters.min_description > 0 {
+            stages.push(Stage::ShortDescription(filters.min_description));
+        }
+        stages.extend(filters.sources.iter().map(|s| Stage::Source(s.trim().to_string())));
+        stages.extend(filters.domains.iter().map(|d| Stage::Domain(d.trim().trim_start_matches("www.").to_lowercase())));
+        for keyword in &filters.keywords {
+            match keyword_regex(keyword) {
+                Ok(regex) => stages.push(Stage::Keyword(regex)),
+                Err(e) => tracing::warn!("ignoring muted keyword {} : {}",keyword,e)
+            }
+        }
+        MutePipeline { stages }
+    }
+
+    /// why `article` is hidden, `None` when it is shown
+    pub fn hides(&self,article : &NewsCardData) -> Option<Message> {
+        self.stages.iter().find_map(|stage| stage.hides(article))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(title : &str,desc : &str,url : &str,source : &str) -> NewsCardData {
+        NewsCardData {
+            title : title.to_string(),
+            desc : desc.to_string(),
+            url : url.to_string(),
+            image : None,
+            source : source.to_string(),
+            published_at : None,
+            category : None
+        }
+    }
+
+    fn hidden_by(filters : &MuteFilters,article : &NewsCardData) -> Option<&'static str> {
+        MutePipeline::new(filters).hides(article).map(|m| m.id)
+    }
+
+    #[test]
+    fn nothing_is_hidden_without_filters() {
+        let filters = MuteFilters::default();
+        assert!(filters.is_empty());
+        assert_eq!(hidden_by(&filters, &article("title", "", "https://example.com/a", "")), None);
+    }
+
+    #[test]
+    fn sources_match_whatever_the_case() {
+        let filters = MuteFilters { sources : vec![" the daily ".to_string()], ..MuteFilters::default() };
+        assert_eq!(hidden_by(&filters, &article("t", "d", "https://example.com/a", "The Daily")), Some("hidden-source"));
+        assert_eq!(hidden_by(&filters, &article("t", "d", "https://example.com/a", "The Daily Planet")), None);
+        assert_eq!(hidden_by(&filters, &article("t", "d", "https://example.com/a", "")), None);
+    }
+
+    #[test]
+    fn domains_match_their_subdomains() {
+        let filters = MuteFilters { domains : vec!["www.Example.com".to_string()], ..MuteFilters::default() };
+        for url in ["https://example.com/a", "https://www.example.com/a", "https://news.EXAMPLE.com/a"] {
+            assert_eq!(hidden_by(&filters, &article("t", "d", url, "")), Some("hidden-domain"), "{}", url);
+        }
+        for url in ["https://notexample.com/a", "https://example.com.au/a", "not a url"] {
+            assert_eq!(hidden_by(&filters, &article("t", "d", url, "")), None, "{}", url);
+        }
+    }
+
+    #[test]
+    fn keywords_are_case_insensitive_regexes() {
+        let filters = MuteFilters { keywords : vec![r"\bcrypto(currency)?\b".to_string()], ..MuteFilters::default() };
+        assert_eq!(hidden_by(&filters, &article("CRYPTO winter", "", "https://example.com/a", "")), Some("hidden-keyword"));
+        assert_eq!(hidden_by(&filters, &article("markets", "a Cryptocurrency crash", "https://example.com/a", "")), Some("hidden-keyword"));
+        assert_eq!(hidden_by(&filters, &article("cryptography", "", "https://example.com/a", "")), None);
+    }
+
+    #[test]
+    fn invalid_keywords_are_reported_and_skipped() {
+        let filters = MuteFilters { keywords : vec!["(".to_string(), "war".to_string()], ..MuteFilters::default() };
+        assert_eq!(filters.validate().unwrap_err().id, "invalid-muted-keyword");
+        assert_eq!(hidden_by(&filters, &article("the war", "", "https://example.com/a", "")), Some("hidden-keyword"));
+        assert_eq!(hidden_by(&filters, &article("(", "", "https://example.com/a", "")), None);
+    }
+
+    #[test]
+    fn short_or_missing_descriptions_are_hidden() {
+        let filters = MuteFilters { min_description : 5, ..MuteFilters::default() };
+        assert_eq!(hidden_by(&filters, &article("t", "", "https://example.com/a", "")), Some("hidden-short-description"));
+        assert_eq!(hidden_by(&filters, &article("t", "  abcd  ", "https://example.com/a", "")), Some("hidden-short-description"));
+        assert_eq!(hidden_by(&filters, &article("t", "abcdé", "https://example.com/a", "")), None);
+    }
+
+    #[test]
+    fn missing_descriptions_only_fail_the_length_stage() {
+        let filters = MuteFilters { keywords : vec!["war".to_string()], ..MuteFilters::default() };
+        assert_eq!(hidden_by(&filters, &article("peace", "", "https://example.com/a", "")), None);
+    }
+
+    #[test]
+    fn the_first_matching_stage_gives_the_reason() {
+        let filters = MuteFilters {
+            min_description : 3,
+            keywords : vec!["war".to_string()],
+            sources : vec!["Daily".to_string()],
+            domains : vec!["example.com".to_string()]
+        };
+        assert_eq!(hidden_by(&filters, &article("war", "", "https://example.com/a", "Daily")), Some("hidden-short-description"));
+        assert_eq!(hidden_by(&filters, &article("war", "long enough", "https://example.com/a", "Daily")), Some("hidden-source"));
+        assert_eq!(hidden_by(&filters, &article("war", "long enough", "https://example.com/a", "")), Some("hidden-domain"));
+        assert_eq!(hidden_by(&filters, &article("war", "long enough", "https://example.org/a", "")), Some("hidden-keyword"));
+        assert_eq!(hidden_by(&filters, &article("peace", "long enough", "https://example.org/a", "")), None);
+    }
+}
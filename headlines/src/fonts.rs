@@ -0,0 +1,132 @@
+use eframe::egui::{Context, FontData, FontDefinitions, FontFamily, FontId, TextStyle};
+
+use crate::config::{HeadlinesConfig, TextSizes};
+
+/// The bundled font, egui ships the other ones of [`crate::FONTS`].
+const SPARTAN : &[u8] = include_bytes!("../../Spartan-VariableFont_wght.ttf");
+
+/// Fonts with the Chinese, Japanese and Korean glyphs the others lack, the first
+/// one found of each group is used. egui ships the emoji ones.
+#[cfg(not(target_arch = "wasm32"))]
+const SYSTEM_FALLBACKS : [&[&str]; 3] = [
+    &[
+        "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+        "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+        "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+        "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+        "/usr/share/fonts/truetype/droid/DroidSansFallbackFull.ttf",
+        "/System/Library/Fonts/PingFang.ttc",
+        "C:\\Windows\\Fonts\\msyh.ttc"
+    ],
+    &[
+        "/System/Library/Fonts/Hiragino Sans GB.ttc",
+        "C:\\Windows\\Fonts\\msgothic.ttc"
+    ],
+    &[
+        "/System/Library/Fonts/AppleSDGothicNeo.ttc",
+        "C:\\Windows\\Fonts\\malgun.ttf"
+    ]
+];
+
+/// Configures the egui fonts and text styles after the settings, again whenever
+/// they change.
+#[derive(Default)]
+pub(crate) struct Fonts {
+    /// font, font file and fallbacks the context has
+    fonts : Option<(String,String,Vec<String>)>,
+    sizes : Option<TextSizes>,
+    /// the CJK fonts found on the system, read once
+    system : Option<Vec<(String,Vec<u8>)>>,
+    /// fallback fonts in use, system ones first
+    pub(crate) fallbacks : Vec<String>,
+    /// font files that could not be used
+    pub(crate) errors : Vec<String>
+}
+
+impl Fonts {
+    pub(crate) fn configure(&mut self,ctx : &Context,config : &HeadlinesConfig) {
+        let fonts = (config.font.clone(), config.font_file.clone(), config.fallback_fonts.clone());
+        if self.fonts.as_ref() != Some(&fonts) {
+            self.set_fonts(ctx, config);
+            self.fonts = Some(fonts);
+        }
+        if self.sizes.as_ref() != Some(&config.text_sizes) {
+            let sizes = &config.text_sizes;
+            let mut style = (*ctx.style()).clone();
+            style.text_styles = [
+                (TextStyle::Small, sizes.small, FontFamily::Proportional),
+                (TextStyle::Body, sizes.body, FontFamily::Proportional),
+                (TextStyle::Button, sizes.button, FontFamily::Proportional),
+                (TextStyle::Heading, sizes.heading, FontFamily::Proportional),
+                (TextStyle::Monospace, sizes.monospace, FontFamily::Monospace)
+            ].into_iter().map(|(text_style, size, family)| (text_style, FontId::new(size, family))).collect();
+            ctx.set_style(style);
+            self.sizes = Some(sizes.clone());
+        }
+    }
+
+    /// the font file, then the chosen font, then egui's emoji fonts and the fallbacks
+    fn set_fonts(&mut self,ctx : &Context,config : &HeadlinesConfig) {
+        self.errors.clear();
+        self.fallbacks.clear();
+        let mut font_def = FontDefinitions::default();
+        font_def.font_data.insert("Spartan".to_string(), FontData::from_static(SPARTAN));
+        // egui ships Ubuntu-Light as its default proportional font
+        if config.font == "Spartan" {
+            font_def.families.get_mut(&FontFamily::Proportional).unwrap().insert(0, "Spartan".to_string());
+        }
+        if !config.font_file.is_empty() {
+            match read_font(&config.font_file) {
+                Ok(data) => {
+                    font_def.font_data.insert(config.font_file.clone(), FontData::from_owned(data));
+                    font_def.families.get_mut(&FontFamily::Proportional).unwrap().insert(0, config.font_file.clone());
+                }
+                Err(e) => self.errors.push(format!("{} : {}",config.font_file,e))
+            }
+        }
+        let mut fallbacks = self.system_fallbacks().to_vec();
+        for file in &config.fallback_fonts {
+            match read_font(file) {
+                Ok(data) => fallbacks.push((file.clone(), data)),
+                Err(e) => self.errors.push(format!("{} : {}",file,e))
+            }
+        }
+        for (name, data) in fallbacks {
+            for family in [FontFamily::Proportional, FontFamily::Monospace] {
+                font_def.families.get_mut(&family).unwrap().push(name.clone());
+            }
+            font_def.font_data.insert(name.clone(), FontData::from_owned(data));
+            self.fallbacks.push(name);
+        }
+        for error in &self.errors {
+            tracing::warn!("ignoring font {}",error);
+        }
+        ctx.set_fonts(font_def);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn system_fallbacks(&mut self) -> &[(String,Vec<u8>)] {
+        self.system.get_or_insert_with(|| SYSTEM_FALLBACKS.iter()
+            .filter_map(|group| group.iter().find_map(|path| Some((path.to_string(), read_font(path).ok()?))))
+            .collect())
+    }
+
+    /// the browser gives no access to the system fonts
+    #[cfg(target_arch = "wasm32")]
+    fn system_fallbacks(&mut self) -> &[(String,Vec<u8>)] {
+        self.system.get_or_insert_with(Vec::new)
+    }
+}
+
+/// the bytes of a font file, checked since egui panics on the ones it cannot parse
+#[cfg(not(target_arch = "wasm32"))]
+fn read_font(path : &str) -> Result<Vec<u8>,String> {
+    let data = std::fs::read(path).map_err(|e| e.to_string())?;
+    ab_glyph::FontRef::try_from_slice(&data).map_err(|_| "not a font egui can read".to_string())?;
+    Ok(data)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_font(_path : &str) -> Result<Vec<u8>,String> {
+    Err("font files are not available in the browser".to_string())
+}
@@ -1,103 +1,423 @@
 
-use std::{borrow::Cow, sync::mpsc::{Receiver, SyncSender}, option};
-use eframe::{egui::{FontDefinitions, FontFamily, Color32, Label, Layout, Hyperlink, Separator, Ui, TopBottomPanel, Context, TextStyle, self, Button, Window, CentralPanel, FontData, RichText}};
-use serde::{Serialize,Deserialize};
+use std::collections::{HashMap, HashSet};
 
-const PADDING : f32 = 5.0;
-const WHITE: Color32 = Color32::from_rgb(255, 255, 255);
-const CYAN: Color32 = Color32::from_rgb(0, 250, 250);
-const BLACK : Color32 =  Color32::from_rgb(0, 0, 0) ;
+use eframe::egui::{Color32, Label, Layout, Hyperlink, Separator, Ui, TopBottomPanel, Context, TextStyle, self, Button, Window, CentralPanel, RichText, TextEdit, ComboBox, Grid};
+use newsApi::{Category, Country, Language, SortBy};
 
-pub enum Msg {
-    ApiKeySet(String),
-    Refresh
+use crate::{accessibility::Describe, bookmarks::Bookmark, cards::{self, group_by_name, layout_name, order_name, Arranged, Cards, RowHeights}, layout::{self, GroupBy, GroupKey, ViewLayout}, l10n::{category_name, country_name, language_name, sort_name}, tr, alerts::WatchRule, alerts_panel::{render_alerts, rule_editor}, cli::Overrides, news::NewsCardData, query::NewsQuery, reader::render_reader, config::{DARK_THEME, DEFAULT_THEME, HIGH_CONTRAST_THEME}, settings::{settings_window, Settings, SettingsTab}, state::{Action, AppState, Effect, View}, fonts::Fonts, keyboard::{self, Command, Palette, Run}, shortcuts::Binding, sort::{QuickFilter, SortOrder}, theme::{self, Theme, Themes}, thumbnails::Thumbnails, worker::{Event, Waker, Worker}};
+
+pub(crate) const PADDING : f32 = 5.0;
+pub(crate) const RED : Color32 = Color32::from_rgb(230, 60, 60);
+pub(crate) const GRAY : Color32 = Color32::from_rgb(140, 140, 140);
+pub(crate) const ORANGE : Color32 = Color32::from_rgb(255, 160, 40);
+/// characters of description shown on a collapsed card
+const DESC_PREVIEW : usize = 140;
+/// seconds without typing before the search is sent
+const SEARCH_DEBOUNCE : f64 = 0.6;
+/// seconds without input, pointer outside of the window, after which the app counts as in the background
+#[cfg(not(target_arch = "wasm32"))]
+const IDLE_AFTER : f64 = 60.;
+/// choices of the automatic refresh menu, in minutes
+pub(crate) const REFRESH_INTERVALS : [u32; 6] = [0, 5, 10, 15, 30, 60];
+/// seconds egui animations last, unless the motion is reduced
+const ANIMATION_TIME : f32 = 1. / 12.;
+/// seconds between two looks at the system theme, while following it
+const SYSTEM_THEME_INTERVAL : f64 = 5.;
+
+/// the [`Cards`] of a view, a macro rather than a method to leave the other fields
+/// of [`Headlines`] free to borrow
+macro_rules! cards {
+    ($headlines:ident, $view:expr, $theme:expr) => {
+        Cards {
+            view : $view,
+            state : &$headlines.state,
+            theme : $theme,
+            filter : &$headlines.quick_filter,
+            thumbnails : &mut $headlines.thumbnails,
+            expanded : &mut $headlines.expanded,
+            collapsed : &mut $headlines.collapsed,
+            selected : &mut $headlines.selected,
+            scroll_to : &mut $headlines.scroll_to,
+            scroll_to_selected : &mut $headlines.scroll_to_selected,
+            row_heights : $headlines.row_heights.entry($view).or_default(),
+            arranged : $headlines.arranged.entry($view).or_default(),
+            actions : Vec::new()
+        }
+    };
 }
 
-#[derive(Serialize,Deserialize)]
-pub struct HeadlinesConfig {
-   pub  dark_mode: bool,
-   pub api_key: String
+/// egui view over [`AppState`], it only renders the state and dispatches actions
+pub struct Headlines{
+    pub state : AppState,
+    search_input : String,
+    search_edited_at : Option<f64>,
+    /// draft of the advanced query window, `Some` while it is open
+    query_editor : Option<NewsQuery>,
+    /// tag the saved view is narrowed to
+    saved_tag : Option<String>,
+    /// tags and note being edited, per bookmark url
+    bookmark_drafts : HashMap<String,BookmarkDraft>,
+    /// urls of the cards showing their whole description
+    expanded : HashSet<String>,
+    /// narrows the articles of the view on screen
+    quick_filter : QuickFilter,
+    /// groups folded under their header, per view
+    collapsed : HashSet<(View,GroupKey)>,
+    /// the rows of each view as last laid out
+    row_heights : HashMap<View,RowHeights>,
+    /// the rows of each view as last arranged, forgotten as the state changes
+    arranged : HashMap<View,Arranged>,
+    pub(crate) thumbnails : Thumbnails,
+    /// time of the last input, to tell when the app is in the background
+    #[cfg(not(target_arch = "wasm32"))]
+    last_input : f64,
+    /// the first new article, scrolled to on the next frame
+    scroll_to : Option<String>,
+    /// rule the alerts view is narrowed to
+    alert_rule : Option<u64>,
+    /// draft of the watch rule window, `Some` while it is open
+    rule_editor : Option<WatchRule>,
+    /// the settings window, `Some` while it is open
+    settings : Option<Settings>,
+    /// url of the card selected by the keyboard, per view
+    selected : HashMap<View,String>,
+    /// the selection moved, to be scrolled to on the next frame
+    scroll_to_selected : bool,
+    /// gives the focus to the search field on the next frame
+    focus_search : bool,
+    palette : Option<Palette>,
+    show_help : bool,
+    pub(crate) fonts : Fonts,
+    pub(crate) themes : Themes,
+    /// the theme the egui context is configured with
+    theme : Option<Theme>,
+    /// whether the system theme is dark, and when that was last checked
+    system_dark : Option<(bool,f64)>,
+    /// set when actions changed the state after the frame was laid out
+    repaint : bool,
+    /// wakes the app up for the system theme and idle checks
+    pub(crate) alarm : Alarm,
+    pub(crate) worker : Worker
+}
+/// the bookmark fields as typed, stored once the text edit loses focus
+struct BookmarkDraft {
+    tags : String,
+    note : String
 }
 
-impl Default for HeadlinesConfig{
-    fn default() -> Self {
-        Self { 
-            dark_mode: Default::default(),
-            api_key : String::new()
-         }
+/// Wakes the app up when a check is due at a time rather than on input, egui paints
+/// frames only on input or when asked to.
+#[derive(Default)]
+pub(crate) struct Alarm {
+    waker : Waker,
+    /// input time the app is woken up at, if it is
+    due : Option<f64>,
+    /// the deadlines of the timer thread, started for the first one and ending with the alarm
+    #[cfg(not(target_arch = "wasm32"))]
+    timer : Option<std::sync::mpsc::Sender<std::time::Instant>>
+}
+
+impl Alarm {
+    pub(crate) fn set_waker(&self,wake : impl Fn() + Send + Sync + 'static) {
+        self.waker.set(wake);
+    }
+
+    /// wakes the app up at `due`, unless it already is by then
+    fn ring_at(&mut self,now : f64,due : f64) {
+        if self.due.is_some_and(|at| at > now && at <= due) {
+            return;
+        }
+        self.due = Some(due);
+        let waker = self.waker.clone();
+        let secs = (due - now).max(0.);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs_f64(secs);
+            let sent = match &self.timer {
+                Some(timer) => timer.send(deadline).is_ok(),
+                None => false
+            };
+            if !sent {
+                let (timer, deadlines) = std::sync::mpsc::channel();
+                let spawned = std::thread::Builder::new()
+                    .name("headlines-alarm".to_string())
+                    .spawn(move || ring(&deadlines, &waker));
+                match spawned {
+                    Ok(_) => {
+                        let _ = timer.send(deadline);
+                        self.timer = Some(timer);
+                    }
+                    Err(e) => tracing::warn!("failed scheduling a repaint : {}",e)
+                }
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use wasm_bindgen::{closure::Closure, JsCast};
+
+            let wake = Closure::once_into_js(move || waker.wake());
+            let millis = (secs * 1000.).min(i32::MAX as f64) as i32;
+            if let Some(window) = web_sys::window() {
+                if let Err(e) = window.set_timeout_with_callback_and_timeout_and_arguments_0(wake.unchecked_ref(), millis) {
+                    tracing::warn!("failed scheduling a repaint : {:?}",e);
+                }
+            }
+        }
     }
 }
 
-pub struct Headlines{
-    pub articles : Vec<NewsCardData>,
-    pub config : HeadlinesConfig,
-    pub api_key_initialized: bool,
-    pub news_rx : Option<Receiver<NewsCardData>>,
-    pub app_tx : Option<SyncSender<Msg>>
+/// wakes the app up at the earliest of `deadlines`, until the [`Alarm`] is dropped
+#[cfg(not(target_arch = "wasm32"))]
+fn ring(deadlines : &std::sync::mpsc::Receiver<std::time::Instant>,waker : &Waker) {
+    use std::sync::mpsc::RecvTimeoutError;
+
+    let mut next : Option<std::time::Instant> = None;
+    loop {
+        let received = match next {
+            Some(at) => deadlines.recv_timeout(at.saturating_duration_since(std::time::Instant::now())),
+            None => deadlines.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        };
+        match received {
+            Ok(at) => next = Some(next.map_or(at, |next| next.min(at))),
+            Err(RecvTimeoutError::Timeout) => {
+                next = None;
+                waker.wake();
+            }
+            Err(RecvTimeoutError::Disconnected) => return
+        }
+    }
+}
+
+impl Default for Headlines {
+    fn default() -> Self {
+        Self::new()
+    }
 }
+
 impl Headlines {
     pub fn new() -> Headlines {
-        let config : HeadlinesConfig = confy::load("headlines").unwrap_or_default();
+        Self::with_overrides(Overrides::default())
+    }
+
+    /// starts with `overrides` over the persisted settings
+    pub fn with_overrides(overrides : Overrides) -> Headlines {
+        let state = AppState::load(overrides);
         Headlines {
-            api_key_initialized : !config.api_key.is_empty(),
-            articles : vec![],
-            config,
-            news_rx : None,
-            app_tx : None
+            search_input : state.config.query.keywords.to_string(),
+            search_edited_at : None,
+            query_editor : None,
+            saved_tag : None,
+            bookmark_drafts : HashMap::new(),
+            expanded : HashSet::new(),
+            quick_filter : QuickFilter::default(),
+            collapsed : HashSet::new(),
+            row_heights : HashMap::new(),
+            arranged : HashMap::new(),
+            thumbnails : Thumbnails::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            last_input : 0.,
+            scroll_to : None,
+            alert_rule : None,
+            rule_editor : None,
+            settings : None,
+            selected : HashMap::new(),
+            scroll_to_selected : false,
+            focus_search : false,
+            palette : None,
+            show_help : false,
+            fonts : Fonts::default(),
+            themes : Themes::load(),
+            theme : None,
+            system_dark : None,
+            alarm : Alarm::default(),
+            repaint : false,
+            state,
+            worker : Worker::spawn()
          }
     }
-    pub fn configure_fonts(&self,ctx: &eframe::egui::Context) -> () {
-        // create font def object
-        let mut font_def = FontDefinitions::default();
-        // then we we'll load the font
-        font_def.font_data.insert("Spartan".to_string(), 
-            FontData::from_static(include_bytes!("../../Spartan-VariableFont_wght.ttf")));
-        // then set the sowe of different text styles
 
-        //deprecated
-        //font_def.family_and_size.insert(eframe::egui::TextStyle::Heading,(FontFamily::Proportional,35.));
-        //.family_and_size.insert(eframe::egui::TextStyle::Body,(FontFamily::Proportional,16.));
-        
-        font_def.families
-                .get_mut(&FontFamily::Proportional)
-                .unwrap()
-                .insert(0,"Spartan".to_string());
-        // load font using context object
-        ctx.set_fonts(font_def)   ;                      
+    pub fn dispatch(&mut self,action : Action) {
+        if let Action::ToggleBookmark(article) = &action {
+            // a bookmark saved again starts without tags nor note
+            self.bookmark_drafts.remove(&article.url);
+        }
+        let rearranged = rearranged(&action, self.state.view);
+        let effects = self.state.update(action);
+        self.run_effects(effects);
+        for view in rearranged {
+            self.arranged.remove(&view);
+        }
+        self.repaint = true;
+    }
 
+    /// asks for another frame when this one dispatched actions or the search waits to be sent,
+    /// and for one when the next system theme or idle check is due. The worker and the
+    /// thumbnail loaders wake the app up themselves.
+    pub(crate) fn request_repaint(&mut self,ctx : &Context) {
+        if std::mem::take(&mut self.repaint) || self.search_edited_at.is_some() {
+            ctx.request_repaint();
+        }
+        let theme_check = self.system_dark
+            .filter(|_| self.state.config.follow_system_theme)
+            .map(|(_, checked)| checked + SYSTEM_THEME_INTERVAL);
+        #[cfg(not(target_arch = "wasm32"))]
+        let idle = Some(self.last_input + IDLE_AFTER).filter(|_| self.state.focused);
+        #[cfg(target_arch = "wasm32")]
+        let idle = None;
+        if let Some(due) = theme_check.into_iter().chain(idle).reduce(f64::min) {
+            self.alarm.ring_at(ctx.input().time, due);
+        }
     }
 
-    
+    pub(crate) fn run_effects(&mut self,effects : Vec<Effect>) {
+        for effect in effects {
+            match effect {
+                Effect::Worker(command) => self.worker.send(command),
+                Effect::StoreConfig => self.state.store_config(),
+                Effect::StoreBookmarks => self.state.bookmarks.store(),
+                Effect::StoreHistory => self.state.history.store(),
+                Effect::StoreArchive => self.state.archive.store(),
+                Effect::StoreAlerts => self.state.alerts.store(),
+                Effect::Notify { summary, body } => crate::alerts::notify(&summary, &body)
+            }
+        }
+    }
+    /// Ctrl + and Ctrl - zoom the whole window by steps of 10%, Ctrl 0 resets it
+    pub fn update_zoom(&mut self,ctx : &Context,frame : &eframe::epi::Frame) {
+        let zoom = self.state.config.zoom;
+        let step = {
+            let input = ctx.input();
+            let text = |texts : &[&str]| input.events.iter().any(|event| matches!(event, egui::Event::Text(t) if texts.contains(&t.as_str())));
+            if !input.modifiers.command {
+                None
+            } else if text(&["+", "="]) {
+                Some(zoom + 0.1)
+            } else if text(&["-"]) {
+                Some(zoom - 0.1)
+            } else if input.key_pressed(egui::Key::Num0) {
+                Some(1.)
+            } else {
+                None
+            }
+        };
+        if let Some(zoom) = step {
+            self.dispatch(Action::SetZoom(zoom));
+        }
+        let pixels_per_point = frame.info().native_pixels_per_point.unwrap_or(1.) * self.state.config.zoom;
+        if (ctx.pixels_per_point() - pixels_per_point).abs() > 0.001 {
+            ctx.set_pixels_per_point(pixels_per_point);
+        }
+    }
 
-    pub fn render_news_cards(&self,ui : &mut eframe::egui::Ui) -> () {
-        for a in &self.articles{
-            //Add padding top
-            ui.add_space(PADDING);
-            //print!("title : {}",a.title);
-            //render title
-            let title = format!("🔹 {}",a.title);
-            if self.config.dark_mode {
-                ui.colored_label(WHITE, title);
-            }else{
-                ui.colored_label(BLACK, title);
+    /// the theme of the settings, or the light or dark one the system is set to
+    pub(crate) fn theme(&self) -> &Theme {
+        let config = &self.state.config;
+        if config.high_contrast {
+            return self.themes.get(HIGH_CONTRAST_THEME);
+        }
+        match self.system_dark.filter(|_| config.follow_system_theme) {
+            Some((true, _)) => self.themes.get(DARK_THEME),
+            Some((false, _)) => self.themes.get(DEFAULT_THEME),
+            None => self.themes.get(&config.theme)
+        }
+    }
+
+    /// configures the colours and shapes again once the theme changed
+    pub fn apply_theme(&mut self,ctx : &Context) {
+        if self.state.config.follow_system_theme {
+            let now = ctx.input().time;
+            if self.system_dark.is_none_or(|(_, checked)| now - checked > SYSTEM_THEME_INTERVAL) {
+                self.system_dark = Some((theme::system_is_dark(), now));
             }
-            
-            //render desc
-            ui.add_space(PADDING);
-            let desc = Label::new(RichText::new(&a.desc).text_style(eframe::egui::TextStyle::Button));
-            ui.add(desc);
-            
-            //render hyperlinks
-            ui.style_mut().visuals.hyperlink_color = CYAN;
-            ui.add_space(PADDING);
-            ui.with_layout(Layout::right_to_left(), |ui|{
-                ui.add(Hyperlink::from_label_and_url("read more 🔜",&a.url));
+        }
+        let theme = self.theme();
+        if self.theme.as_ref() == Some(theme) {
+            return;
+        }
+        let theme = theme.clone();
+        let mut style = (*ctx.style()).clone();
+        theme.apply(&mut style);
+        ctx.set_style(style);
+        self.theme = Some(theme);
+    }
+
+    /// reduced motion and the screen reader output, once the settings changed them
+    pub fn apply_accessibility(&mut self,ctx : &Context) {
+        let config = &self.state.config;
+        let animation_time = if config.reduced_motion { 0. } else { ANIMATION_TIME };
+        if ctx.style().animation_time != animation_time {
+            let mut style = (*ctx.style()).clone();
+            style.animation_time = animation_time;
+            ctx.set_style(style);
+        }
+        ctx.memory().options.screen_reader = config.screen_reader;
+    }
+
+    /// the articles of the feed in the layout chosen for it, under the headers of their group
+    pub fn render_news_cards(&mut self,ui : &mut Ui) -> Vec<Action> {
+        let theme = self.theme().clone();
+        let mut cards = cards!(self, View::Feed, &theme);
+        let articles : Vec<&NewsCardData> = cards.state.articles().iter().collect();
+        // one scroll position per feed
+        cards.show(ui, &cards.state.query, &articles, |cards, ui, a| cards.card(ui, a));
+        cards.actions
+    }
+
+    /// the bookmarks with their tags and notes, narrowed to `saved_tag`
+    fn render_saved(&mut self,ui : &mut Ui) -> Vec<Action> {
+        let mut actions = Vec::new();
+        let mut auto_archive = self.state.config.auto_archive;
+        if ui.checkbox(&mut auto_archive, tr!("saved-auto-archive")).changed() {
+            actions.push(Action::ToggleAutoArchive);
+        }
+        ui.add_space(PADDING);
+        let bookmarks = &self.state.bookmarks;
+        if bookmarks.is_empty() {
+            ui.vertical_centered_justified(|ui|{
+                ui.heading(tr!("saved-empty"));
+                ui.add_space(PADDING);
+                ui.label(tr!("saved-empty-hint"));
             });
-            ui.add_space(PADDING);
-            ui.add(Separator::default());
+            return actions;
+        }
+        let saved_tag = self.saved_tag.clone();
+        if self.saved_tag.as_deref().is_some_and(|tag| !bookmarks.tags().contains(tag)) {
+            self.saved_tag = None;
+        }
+        ui.horizontal_wrapped(|ui|{
+            ui.label(tr!("saved-tags"));
+            if ui.selectable_label(self.saved_tag.is_none(), tr!("saved-all-tags")).clicked() {
+                self.saved_tag = None;
+            }
+            for tag in bookmarks.tags() {
+                if ui.selectable_label(self.saved_tag.as_deref() == Some(tag), tag).clicked() {
+                    self.saved_tag = Some(tag.to_string());
+                }
+            }
+        });
+        ui.add(Separator::default().spacing(20.));
+        if self.saved_tag != saved_tag {
+            self.arranged.remove(&View::Saved);
         }
+
+        let theme = self.theme().clone();
+        let saved_tag = self.saved_tag.as_deref();
+        let drafts = &mut self.bookmark_drafts;
+        let mut cards = cards!(self, View::Saved, &theme);
+        let shown : Vec<&NewsCardData> = bookmarks.iter()
+            .filter(|b| saved_tag.is_none_or(|tag| b.tags.iter().any(|t| t == tag)))
+            .map(|b| &b.article)
+            .collect();
+        cards.show(ui, "saved", &shown, |cards, ui, a|{
+            if let Some(bookmark) = bookmarks.get(&a.url) {
+                saved_card(cards, ui, bookmark, drafts);
+            }
+        });
+        actions.extend(cards.actions);
+        actions
     }
-    pub(crate) fn render_top_panel(&mut self,ctx : &Context,frame : &eframe::epi::Frame) -> () {
+
+    pub(crate) fn render_top_panel(&mut self,ctx : &Context,frame : &eframe::epi::Frame) {
         //define a topBottomPanel wodget
         TopBottomPanel::top("top_panel").show(ctx, |ui|{
             //then two layout widgets
@@ -111,30 +431,131 @@ impl Headlines {
                 ui.with_layout(Layout::right_to_left(), |ui|{
                     
                     if !cfg!(target_arch = "wasm32") {
-                        let close_btn = ui.add(Button::new(RichText::new("❌").text_style(egui::TextStyle::Body)));
+                        let close_btn = ui.add(Button::new(RichText::new("❌").text_style(egui::TextStyle::Body)))
+                            .describe(tr!("quit"));
                         if close_btn.clicked(){
                             frame.quit();
                         }
                     }
 
-                    let refresh_btn = ui.add(Button::new(RichText::new("🔄").text_style(egui::TextStyle::Body)));
+                    let refresh_btn = ui.add(Button::new(RichText::new("🔄").text_style(egui::TextStyle::Body)))
+                        .describe(tr!("refresh"));
                     if refresh_btn.clicked() {
-                        if let Some(tx)  = &self.app_tx{
-                            self.articles.clear();
-                            tx.send(Msg::Refresh);
-                        }
+                        self.dispatch(Action::Refresh);
                     }
                     
 
+                    let dark = self.theme().dark;
                     let theme_btn = ui.add(Button::new(RichText::new({
-                        if self.config.dark_mode {
+                        if dark {
                             "🌞"
                         }else{
                             "🌙"
                         }
-                    })));
-                    if(theme_btn.clicked()){
-                        self.config.dark_mode = !self.config.dark_mode;
+                    }))).describe(if dark {
+                        tr!("theme-to-light", theme = self.theme().name.clone())
+                    } else {
+                        tr!("theme-to-dark", theme = self.theme().name.clone())
+                    });
+                    if theme_btn.clicked() {
+                        self.dispatch(Action::SetTheme(if dark { DEFAULT_THEME } else { DARK_THEME }.to_string()));
+                    }
+
+                    let refresh_minutes = self.state.config.refresh_minutes;
+                    let mut interval = None;
+                    ui.menu_button("⏱", |ui|{
+                        ui.label(tr!("auto-refresh"));
+                        for minutes in REFRESH_INTERVALS {
+                            let label = if minutes == 0 { tr!("auto-refresh-never") } else { tr!("auto-refresh-every", minutes = minutes) };
+                            if ui.selectable_label(refresh_minutes == minutes, label).clicked() {
+                                interval = Some(minutes);
+                                ui.close_menu();
+                            }
+                        }
+                    }).response.describe(if refresh_minutes == 0 {
+                        tr!("auto-refresh-off")
+                    } else {
+                        tr!("auto-refresh-on", minutes = refresh_minutes)
+                    });
+                    if let Some(minutes) = interval {
+                        self.dispatch(Action::SetRefreshInterval(minutes));
+                    }
+
+                    let (offline_icon, offline_hint) = if self.state.config.offline {
+                        ("✈", tr!("offline-on"))
+                    } else {
+                        ("📡", tr!("offline-off"))
+                    };
+                    let offline_btn = ui.add(Button::new(RichText::new(offline_icon).text_style(egui::TextStyle::Body)))
+                        .describe(offline_hint);
+                    if offline_btn.clicked() {
+                        self.dispatch(Action::ToggleOffline);
+                    }
+
+                    let (images_icon, images_hint) = if self.state.config.hide_images {
+                        ("🖼", tr!("show-images"))
+                    } else {
+                        ("📄", tr!("hide-images-hint"))
+                    };
+                    let images_btn = ui.add(Button::new(RichText::new(images_icon).text_style(egui::TextStyle::Body)))
+                        .describe(images_hint);
+                    if images_btn.clicked() {
+                        self.dispatch(Action::ToggleImages);
+                    }
+
+                    let ViewLayout { layout : current_layout, group_by : current_group_by, .. } = self.state.config.layouts.get(self.state.view);
+                    let mut layout_action = None;
+                    ui.menu_button("⊞", |ui|{
+                        ui.label(tr!("layout"));
+                        for layout in layout::Layout::ALL {
+                            if ui.selectable_label(current_layout == layout, layout_name(layout)).clicked() {
+                                layout_action = Some(Action::SetLayout(layout));
+                                ui.close_menu();
+                            }
+                        }
+                        ui.separator();
+                        ui.label(tr!("group-by"));
+                        for group_by in GroupBy::ALL {
+                            if ui.selectable_label(current_group_by == group_by, group_by_name(group_by)).clicked() {
+                                layout_action = Some(Action::SetGroupBy(group_by));
+                                ui.close_menu();
+                            }
+                        }
+                    }).response.describe(tr!("layout-menu", layout = layout_name(current_layout), group = group_by_name(current_group_by)));
+                    if let Some(action) = layout_action {
+                        self.dispatch(action);
+                    }
+
+                    let settings_btn = ui.add(Button::new(RichText::new("⚙").text_style(egui::TextStyle::Body)))
+                        .describe(tr!("settings"));
+                    if settings_btn.clicked() {
+                        self.settings = Some(Settings::new(&self.state, SettingsTab::Account));
+                    }
+
+                    let mute_btn = ui.add(Button::new(RichText::new("🔇").text_style(egui::TextStyle::Body)))
+                        .describe(tr!("mute-filters"));
+                    if mute_btn.clicked() {
+                        self.settings = Some(Settings::new(&self.state, SettingsTab::Filters));
+                    }
+
+                    let advanced_btn = ui.add(Button::new(RichText::new("🔧").text_style(egui::TextStyle::Body)))
+                        .describe(tr!("advanced-search"));
+                    if advanced_btn.clicked() {
+                        self.query_editor = Some(self.state.query.clone());
+                    }
+
+                    let search = ui.add(TextEdit::singleline(&mut self.search_input)
+                        .hint_text(tr!("search-hint"))
+                        .desired_width(180.));
+                    if std::mem::take(&mut self.focus_search) {
+                        search.request_focus();
+                    }
+                    if search.changed() {
+                        self.search_edited_at = Some(ui.input().time);
+                    }
+                    if search.lost_focus() && ui.input().key_pressed(egui::Key::Enter) {
+                        // no need to wait for the debounce
+                        self.search_edited_at = Some(f64::NEG_INFINITY);
                     }
                 }); 
                 //padding before after the pannel
@@ -146,61 +567,622 @@ impl Headlines {
     
     }
 
-    pub fn preload_articles(&mut self){
-        if let Some(rx) = &self.news_rx {
-            match rx.try_recv() {
-                Ok(news_data) => {
-                    self.articles.push(news_data)
-                },
-                Err(e) => {
-                    tracing::warn!("Error receiving msg : {}",e);
+    /// the shortcuts of the settings, see [`crate::Shortcuts`]
+    pub(crate) fn handle_keys(&mut self,ctx : &Context) {
+        let typing = ctx.wants_keyboard_input();
+        let focused = ctx.memory().focus().is_some();
+        let (binding, escape) = {
+            let input = ctx.input();
+            let shortcuts = &self.state.config.shortcuts;
+            let binding = Binding::ALL.into_iter().find(|binding| keyboard::pressed(&input, shortcuts.get(*binding), typing, focused));
+            (binding, !typing && input.key_pressed(egui::Key::Escape))
+        };
+        if escape && self.palette.is_none() {
+            if self.state.reader.is_some() {
+                self.dispatch(Action::CloseReader);
+            } else if self.show_help {
+                self.show_help = false;
+            } else {
+                self.selected.remove(&self.state.view);
+            }
+        }
+        let binding = match binding {
+            Some(binding) => binding,
+            None => return
+        };
+        // the reader hides the articles of the view
+        let listed = self.state.reader.is_none();
+        let article = self.selected.get(&self.state.view)
+            .and_then(|url| self.listed().into_iter().find(|a| a.url == *url))
+            .filter(|_| listed)
+            .cloned();
+        match binding {
+            Binding::Next | Binding::Previous if listed => self.move_selection(if binding == Binding::Next { 1 } else { -1 }),
+            Binding::Open => if let Some(article) = article {
+                self.dispatch(Action::OpenReader(article));
+            },
+            Binding::OpenExternal => if let Some(article) = article {
+                ctx.output().open_url(&article.url);
+                self.dispatch(Action::MarkRead(article.url));
+            },
+            Binding::Save => if let Some(article) = article {
+                self.dispatch(Action::ToggleBookmark(article));
+            },
+            Binding::Refresh => self.dispatch(Action::Refresh),
+            Binding::Search => self.focus_search = true,
+            Binding::Palette => self.palette = match self.palette {
+                Some(_) => None,
+                None => Some(Palette::default())
+            },
+            Binding::Help => self.show_help = !self.show_help,
+            Binding::Next | Binding::Previous => {}
+        }
+    }
+
+    /// the articles of the view in the order they are listed, without those of folded groups
+    fn listed(&self) -> Vec<&NewsCardData> {
+        let view = self.state.view;
+        let articles : Vec<&NewsCardData> = match view {
+            View::Feed => self.state.articles().iter().collect(),
+            View::Saved => self.state.bookmarks.iter()
+                .filter(|b| self.saved_tag.as_deref().is_none_or(|tag| b.tags.iter().any(|t| t == tag)))
+                .map(|b| &b.article)
+                .collect(),
+            View::Alerts => self.state.alerts.alerts(self.alert_rule).map(|alert| &alert.article).collect()
+        };
+        if let Some(listed) = self.arranged.get(&view).and_then(|arranged| arranged.listed(&articles)) {
+            return listed;
+        }
+        cards::arrange(&self.state, view, &self.quick_filter, articles.into_iter().enumerate()).into_iter()
+            .filter(|(key,_)| !self.collapsed.contains(&(view, key.clone())))
+            .flat_map(|(_,articles)| articles)
+            .map(|(_, a)| a)
+            .collect()
+    }
+
+    /// selects the card `step` after the selected one, or the first one
+    fn move_selection(&mut self,step : isize) {
+        let articles = self.listed();
+        if articles.is_empty() {
+            return;
+        }
+        let index = match self.selected.get(&self.state.view).and_then(|url| articles.iter().position(|a| a.url == *url)) {
+            Some(index) => index.saturating_add_signed(step).min(articles.len() - 1),
+            None => 0
+        };
+        let url = articles[index].url.clone();
+        self.selected.insert(self.state.view, url);
+        self.scroll_to_selected = true;
+    }
+
+    /// the command palette and the shortcuts help, while they are open
+    pub(crate) fn render_palette(&mut self,ctx : &Context,frame : &eframe::epi::Frame) {
+        let mut show_help = self.show_help;
+        keyboard::help_window(ctx, &self.state.config.shortcuts, &mut show_help);
+        self.show_help = show_help;
+        if self.palette.is_none() {
+            return;
+        }
+        let commands = self.palette_commands();
+        match keyboard::palette_window(ctx, &mut self.palette, commands) {
+            Some(Run::Actions(actions)) => {
+                for action in actions {
+                    self.dispatch(action);
                 }
             }
+            Some(Run::Settings(tab)) => self.settings = Some(Settings::new(&self.state, tab)),
+            Some(Run::Search) => self.focus_search = true,
+            Some(Run::AdvancedSearch) => self.query_editor = Some(self.state.query.clone()),
+            Some(Run::Help) => self.show_help = true,
+            Some(Run::ExportSaved) => ctx.output().copied_text = self.state.bookmarks.to_markdown(),
+            Some(Run::Quit) => frame.quit(),
+            None => {}
         }
     }
-    
-    pub fn render_config(&mut self,ctx:&Context){
-        CentralPanel::default().show(ctx, |ui|{
-            Window::new("Configuration").show(ctx,|ui|{
-                ui.label("Enter your API_KEY for newsapi.org");
-                let text_input = ui.text_edit_singleline(&mut self.config.api_key);
-                tracing::error!("{}",&self.config.api_key);
-                ui.label("If you havn-t registered for the API_KEY,head over to");
-            if text_input.lost_focus() && ui.input().key_pressed(egui::Key::Enter){
-                if let Err(e) = confy::store("headlines", HeadlinesConfig {
-                        dark_mode: self.config.dark_mode,
-                        api_key: self.config.api_key.to_string()
-                }){
-                     tracing::error!("failed saving app state : {}",e);
+
+    /// every command of the palette, the category ones included
+    fn palette_commands(&self) -> Vec<Command> {
+        let state = &self.state;
+        let shortcuts = &state.config.shortcuts;
+        let mut commands = vec![
+            Command::action(tr!("refresh"), Action::Refresh).shortcut(&shortcuts.refresh),
+            Command::action(tr!("command-cancel"), Action::Cancel),
+            Command::new(tr!("command-search"), Run::Search).shortcut(&shortcuts.search),
+            Command::new(tr!("advanced-search"), Run::AdvancedSearch)
+        ];
+        let feed = |query : NewsQuery| Run::Actions(vec![Action::ShowView(View::Feed), Action::ChangeQuery(query)]);
+        commands.push(Command::new(tr!("command-top"), feed(state.query.with_category(None))));
+        for category in Category::ALL {
+            commands.push(Command::new(tr!("command-category", category = category_name(category)), feed(state.query.with_category(Some(category)))));
+        }
+        commands.push(Command::action(tr!("command-saved"), Action::ShowView(View::Saved)));
+        commands.push(Command::action(tr!("command-alerts"), Action::ShowView(View::Alerts)));
+        commands.push(Command::action(tr!("mark-all-read"), Action::MarkAllRead));
+        commands.push(Command::action(if state.show_hidden { tr!("command-hide-muted") } else { tr!("command-show-muted") }, Action::ToggleShowHidden));
+        commands.push(Command::action(if state.config.offline { tr!("command-online") } else { tr!("command-offline") }, Action::ToggleOffline));
+        commands.push(Command::action(if state.config.hide_images { tr!("show-images") } else { tr!("hide-images") }, Action::ToggleImages));
+        for layout in layout::Layout::ALL {
+            commands.push(Command::action(tr!("command-layout", layout = layout_name(layout)), Action::SetLayout(layout)));
+        }
+        for group_by in GroupBy::ALL {
+            commands.push(Command::action(tr!("command-group-by", group = group_by_name(group_by)), Action::SetGroupBy(group_by)));
+        }
+        for order in SortOrder::ALL {
+            commands.push(Command::action(tr!("command-order", order = order_name(order)), Action::SetSort(order)));
+        }
+        let dark = self.theme().dark;
+        commands.push(Command::action(tr!("command-dark-theme"), Action::SetTheme(if dark { DEFAULT_THEME } else { DARK_THEME }.to_string())));
+        for name in self.themes.names() {
+            commands.push(Command::action(tr!("command-theme", theme = name.to_string()), Action::SetTheme(name.to_string())));
+        }
+        commands.push(Command::action(tr!("zoom-in"), Action::SetZoom(state.config.zoom + 0.1)).shortcut("Ctrl +"));
+        commands.push(Command::action(tr!("zoom-out"), Action::SetZoom(state.config.zoom - 0.1)).shortcut("Ctrl -"));
+        commands.push(Command::action(tr!("zoom-reset"), Action::SetZoom(1.)).shortcut("Ctrl 0"));
+        for tab in SettingsTab::ALL {
+            commands.push(Command::new(tr!("command-settings", tab = tab.name()), Run::Settings(tab)));
+        }
+        for name in state.profiles.names().filter(|name| *name != state.profiles.active()) {
+            commands.push(Command::action(tr!("command-profile", profile = name.to_string()), Action::SwitchProfile(name.to_string())));
+        }
+        commands.push(Command::new(tr!("command-export"), Run::ExportSaved));
+        commands.push(Command::new(tr!("binding-keyboard-shortcuts"), Run::Help).shortcut(&shortcuts.help));
+        if !cfg!(target_arch = "wasm32") {
+            commands.push(Command::new(tr!("quit"), Run::Quit));
+        }
+        commands
+    }
+
+    /// pauses the automatic refreshes while the app is in the background
+    ///
+    /// eframe does not report the window focus, on native the app counts as in the
+    /// background once the pointer left the window and nothing was typed for a while,
+    /// noticed on the frame the [`Alarm`] wakes it up for.
+    pub(crate) fn update_focus(&mut self,ctx : &Context) {
+        #[cfg(not(target_arch = "wasm32"))]
+        let focused = {
+            let input = ctx.input();
+            if input.pointer.has_pointer() || !input.events.is_empty() {
+                self.last_input = input.time;
+            }
+            input.time - self.last_input < IDLE_AFTER
+        };
+        #[cfg(target_arch = "wasm32")]
+        let focused = {
+            let _ = ctx;
+            web_sys::window()
+                .and_then(|window| window.document())
+                .and_then(|document| document.has_focus().ok())
+                .unwrap_or(true)
+        };
+        if focused != self.state.focused {
+            self.dispatch(Action::SetFocused(focused));
+        }
+    }
+
+    /// send the search once the user stopped typing
+    pub(crate) fn update_search(&mut self,ctx : &Context) {
+        if let Some(edited_at) = self.search_edited_at {
+            if ctx.input().time - edited_at >= SEARCH_DEBOUNCE {
+                self.search_edited_at = None;
+                let query = self.state.query.with_keywords(&self.search_input);
+                self.dispatch(Action::ChangeQuery(query));
+            }
+        }
+    }
+
+    pub(crate) fn render_query_editor(&mut self,ctx : &Context) {
+        let mut draft = match self.query_editor.take() {
+            Some(draft) => draft,
+            None => return
+        };
+        let mut open = true;
+        let mut apply = false;
+        Window::new(tr!("advanced-search")).id(egui::Id::new("query_editor")).open(&mut open).collapsible(false).show(ctx, |ui|{
+            Grid::new("query_editor").num_columns(2).spacing([10., 8.]).show(ui, |ui|{
+                ui.label(tr!("query-keywords"));
+                ui.add(TextEdit::singleline(&mut draft.keywords).hint_text("bitcoin AND (ethereum OR litecoin)"));
+                ui.end_row();
+
+                ui.label(tr!("query-language"));
+                ComboBox::from_id_source("query_language")
+                    .selected_text(draft.language.map_or_else(|| tr!("query-any"), language_name))
+                    .show_ui(ui, |ui|{
+                        ui.selectable_value(&mut draft.language, None, tr!("query-any"));
+                        for language in Language::ALL {
+                            ui.selectable_value(&mut draft.language, Some(language), language_name(language));
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(tr!("query-sort-by"));
+                ComboBox::from_id_source("query_sort_by")
+                    .selected_text(draft.sort_by.map_or_else(|| tr!("query-default-sort"), sort_name))
+                    .show_ui(ui, |ui|{
+                        ui.selectable_value(&mut draft.sort_by, None, tr!("query-default-sort"));
+                        for sort_by in SortBy::ALL {
+                            ui.selectable_value(&mut draft.sort_by, Some(sort_by), sort_name(sort_by));
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(tr!("query-from"));
+                ui.add(TextEdit::singleline(&mut draft.from).hint_text("YYYY-MM-DD"));
+                ui.end_row();
+
+                ui.label(tr!("query-to"));
+                ui.add(TextEdit::singleline(&mut draft.to).hint_text("YYYY-MM-DD"));
+                ui.end_row();
+
+                ui.label(tr!("query-sources"));
+                ui.add(TextEdit::singleline(&mut draft.sources).hint_text("bbc-news,the-verge"));
+                ui.end_row();
+
+                ui.label(tr!("query-domains"));
+                ui.add(TextEdit::singleline(&mut draft.domains).hint_text("bbc.co.uk,techcrunch.com"));
+                ui.end_row();
+            });
+            ui.add_space(PADDING);
+            ui.small(tr!("query-note"));
+            ui.add_space(PADDING);
+            let validation = draft.validate();
+            if let Err(e) = &validation {
+                ui.colored_label(RED, format!("⚠ {}",e));
+            }
+            ui.horizontal(|ui|{
+                if ui.add_enabled(validation.is_ok(), Button::new(tr!("apply"))).clicked() {
+                    apply = true;
                 }
-                self.api_key_initialized = true;
-    
-                if let Some(tx) =  &self.app_tx {
-                    tx.send(Msg::ApiKeySet(self.config.api_key.to_string()));
+                if ui.button(tr!("reset")).clicked() {
+                    draft = NewsQuery { country : draft.country, ..NewsQuery::default() };
                 }
+            });
+        });
+        if apply {
+            self.search_input = draft.keywords.to_string();
+            self.search_edited_at = None;
+            self.dispatch(Action::ChangeQuery(draft));
+        } else if open {
+            self.query_editor = Some(draft);
+        }
+    }
+
+    /// opens itself on the account tab until an api key is set
+    pub(crate) fn render_settings(&mut self,ctx : &Context) {
+        let first_run = !self.state.api_key_initialized;
+        if first_run && self.settings.is_none() {
+            self.settings = Some(Settings::new(&self.state, SettingsTab::Account));
+        }
+        for action in settings_window(ctx, &self.state, &mut self.settings, &mut self.themes, &self.fonts) {
+            self.dispatch(action);
+        }
+        if first_run && self.state.api_key_initialized {
+            self.settings = None;
+        }
+        // the drafts were taken from the profile switched away from
+        if let Some(tab) = self.settings.as_ref().filter(|s| s.profile != self.state.profiles.active()).map(|s| s.tab) {
+            self.settings = Some(Settings::new(&self.state, tab));
+        }
+    }
+
+    pub(crate) fn render_rule_editor(&mut self,ctx : &Context) {
+        if let Some(action) = rule_editor(ctx, &mut self.rule_editor) {
+            self.dispatch(action);
+        }
+    }
+
+    pub fn preload_articles(&mut self){
+        for event in self.worker.poll() {
+            self.dispatch(Action::Worker(event));
+        }
+    }
     
-                tracing::error!("api key set");
+    pub(crate) fn render_error_banner(&mut self,ctx : &Context) {
+        let message = match self.state.error() {
+            Some(error) => error.to_string(),
+            None => return
+        };
+        let mut action = None;
+        TopBottomPanel::top("error_banner").show(ctx, |ui|{
+            ui.add_space(PADDING);
+            ui.horizontal(|ui|{
+                ui.colored_label(RED, format!("⚠ {}",message));
+                ui.with_layout(Layout::right_to_left(), |ui|{
+                    if ui.button("✖").describe(tr!("dismiss")).clicked() {
+                        action = Some(Action::DismissError);
+                    }
+                    if ui.button(tr!("retry")).clicked() {
+                        action = Some(Action::Refresh);
+                    }
+                });
+            });
+            ui.add_space(PADDING);
+        });
+        if let Some(action) = action {
+            self.dispatch(action);
+        }
+    }
+
+    /// category tabs and the saved view on the left, country and language on the right
+    fn render_feed_tabs(&mut self,ui : &mut Ui) -> Vec<Action> {
+        let query = &self.state.query;
+        let feed_view = self.state.view == View::Feed;
+        let mut selected = if query.is_search() || !feed_view { None } else { Some(query.category) };
+        let mut view = self.state.view;
+        let mut country = query.country;
+        let mut language = query.language;
+        // newsapi.org only narrows searches to a language, not the top headlines
+        let search = query.is_search();
+        let offline = self.state.config.offline;
+        ui.horizontal_wrapped(|ui|{
+            if offline {
+                // the archive is the only feed while offline
+                let archived = tr!("tab-archive", count = self.state.archive.len());
+                if ui.selectable_label(feed_view, archived).clicked() {
+                    view = View::Feed;
+                }
+                view_tabs(ui, &self.state, &mut view);
+                return;
+            }
+            let mut tab = |ui : &mut Ui,category : Option<Category>,label : String| {
+                if ui.selectable_label(selected == Some(category), label).clicked() {
+                    selected = Some(category);
+                    view = View::Feed;
+                }
+            };
+            tab(ui, None, tr!("tab-top"));
+            for category in Category::ALL {
+                tab(ui, Some(category), category_name(category));
+            }
+            view_tabs(ui, &self.state, &mut view);
+            if !feed_view {
+                return;
+            }
+            ui.with_layout(Layout::right_to_left(), |ui|{
+                if search {
+                    ComboBox::from_id_source("feed_language")
+                        .selected_text(language.map_or_else(|| tr!("any-language"), language_name))
+                        .show_ui(ui, |ui|{
+                            ui.selectable_value(&mut language, None, tr!("any-language"));
+                            for l in Language::ALL {
+                                ui.selectable_value(&mut language, Some(l), language_name(l));
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr!("feed-language-hint"));
+                }
+                ComboBox::from_id_source("feed_country")
+                    .selected_text(country_name(country))
+                    .show_ui(ui, |ui|{
+                        for c in Country::ALL {
+                            ui.selectable_value(&mut country, c, country_name(c));
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr!("feed-country-hint"));
+            });
+        });
+        ui.add_space(PADDING);
+
+        let mut actions = Vec::new();
+        if view != self.state.view {
+            actions.push(Action::ShowView(view));
+        }
+        let query = &self.state.query;
+        let mut next = match selected {
+            Some(category) if query.is_search() || category != query.category => {
+                self.search_input.clear();
+                query.with_category(category)
+            }
+            _ => query.clone()
+        };
+        next.country = country;
+        next.language = language;
+        if next != *query {
+            actions.push(Action::ChangeQuery(next));
+        }
+        actions
+    }
+
+    /// the quick filter and the order of the view, they apply to the articles already loaded
+    fn render_list_bar(&mut self,ui : &mut Ui) -> Option<Action> {
+        let mut text = self.quick_filter.text().to_string();
+        let mut regex = self.quick_filter.is_regex();
+        let current = self.state.config.layouts.get(self.state.view).sort;
+        let mut sort = current;
+        ui.horizontal(|ui|{
+            ui.add(TextEdit::singleline(&mut text)
+                .hint_text(tr!("filter-hint"))
+                .desired_width(200.));
+            if ui.selectable_label(regex, ".*").on_hover_text(tr!("filter-regex")).clicked() {
+                regex = !regex;
+            }
+            if !text.is_empty() && ui.add(Button::new("✖").frame(false)).describe(tr!("filter-clear")).clicked() {
+                text.clear();
+            }
+            if let Some(error) = self.quick_filter.error() {
+                ui.colored_label(RED, tr!("filter-invalid")).on_hover_text(error);
             }
-                ui.hyperlink("https://newsapi.org");
+            ui.with_layout(Layout::right_to_left(), |ui|{
+                ComboBox::from_id_source("list_sort")
+                    .selected_text(order_name(sort))
+                    .show_ui(ui, |ui|{
+                        for order in SortOrder::ALL {
+                            ui.selectable_value(&mut sort, order, order_name(order));
+                        }
+                    });
+                ui.label(tr!("order"));
             });
         });
+        ui.add_space(PADDING);
+        if text != self.quick_filter.text() || regex != self.quick_filter.is_regex() {
+            self.quick_filter = QuickFilter::new(&text, regex);
+            self.arranged.clear();
+        }
+        Some(Action::SetSort(sort)).filter(|_| sort != current)
     }
+
+    pub(crate) fn render_central_panel(&mut self,ctx : &Context) {
+        let mut actions = Vec::new();
+        CentralPanel::default().show(ctx, |ui|{
+            if let Some(reader) = &self.state.reader {
+                let thumbnails = if self.state.config.hide_images { None } else { Some(&mut self.thumbnails) };
+                actions.extend(render_reader(ui, &self.state, reader, thumbnails));
+                return;
+            }
+            actions.extend(self.render_feed_tabs(ui));
+            actions.extend(self.render_list_bar(ui));
+            if self.state.view == View::Saved {
+                actions.extend(self.render_saved(ui));
+            } else if self.state.view == View::Alerts {
+                let theme = self.theme().clone();
+                let mut cards = cards!(self, View::Alerts, &theme);
+                actions.extend(render_alerts(ui, &mut cards, &mut self.alert_rule, &mut self.rule_editor));
+            } else if self.state.articles().is_empty() {
+                ui.vertical_centered_justified(|ui|{
+                    if self.state.loading() {
+                        ui.heading(tr!("loading"));
+                        if ui.button(tr!("cancel")).clicked() {
+                            actions.push(Action::Cancel);
+                        }
+                    } else if let Some(error) = self.state.error() {
+                        ui.heading(tr!("feed-failed"));
+                        ui.add_space(PADDING);
+                        ui.label(error.to_string());
+                        ui.add_space(PADDING);
+                        if ui.button(tr!("retry")).clicked() {
+                            actions.push(Action::Refresh);
+                        }
+                    } else if self.state.config.offline {
+                        ui.heading(tr!("archive-empty"));
+                        ui.add_space(PADDING);
+                        ui.label(tr!("archive-empty-hint"));
+                    } else {
+                        ui.heading(tr!("feed-empty"));
+                        ui.add_space(PADDING);
+                        if ui.button(format!("🔄 {}",tr!("refresh"))).clicked() {
+                            actions.push(Action::Refresh);
+                        }
+                    }
+                });
+            } else {
+                actions.extend(render_header(ui, &self.state));
+                if self.state.loading() {
+                    ui.vertical_centered(|ui|{
+                        ui.label(tr!("loading"));
+                    });
+                }
+                let new_articles = self.state.new_articles().len();
+                if new_articles > 0 {
+                    ui.vertical_centered(|ui|{
+                        let label = tr!("new-articles", count = new_articles);
+                        let button = Button::new(RichText::new(label).color(self.theme().background())).fill(ui.visuals().hyperlink_color);
+                        if ui.add(button).clicked() {
+                            // refreshes put them on top of the feed
+                            let new = self.state.new_articles();
+                            self.scroll_to = self.state.feed().articles.iter()
+                                .find(|a| new.contains(&a.url))
+                                .map(|a| a.url.clone());
+                            actions.push(Action::DismissNewArticles);
+                        }
+                    });
+                    ui.add_space(PADDING);
+                }
+                actions.extend(self.render_news_cards(ui));
+            }
+        });
+        for action in actions {
+            self.dispatch(action);
+        }
+    }
+}
+
+/// the views whose articles, their order, groups or layout `action` may change,
+/// `view` being the one shown
+fn rearranged(action : &Action,view : View) -> Vec<View> {
+    match action {
+        Action::SetLayout(_) | Action::SetGroupBy(_) | Action::SetSort(_) => vec![view],
+        Action::Refresh | Action::ChangeQuery(_) | Action::Archive(_) | Action::Unarchive(_) | Action::ToggleOffline
+        | Action::ToggleShowHidden | Action::SetMuteFilters(_) | Action::Worker(Event::Archived { .. }) => vec![View::Feed],
+        Action::Worker(Event::Progress { .. }) => vec![View::Feed, View::Alerts],
+        Action::ToggleBookmark(_) | Action::SetBookmarkTags { .. } => vec![View::Saved],
+        Action::SaveWatchRule(_) | Action::RemoveWatchRule(_) => vec![View::Alerts],
+        Action::SetConfig(_) | Action::SwitchProfile(_) | Action::CreateProfile(_) => vec![View::Feed, View::Saved, View::Alerts],
+        _ => vec![]
+    }
+}
+
+/// a bookmark in the cards layout, with its tags and note
+fn saved_card(cards : &mut Cards,ui : &mut Ui,bookmark : &Bookmark,drafts : &mut HashMap<String,BookmarkDraft>) {
+    let a = &bookmark.article;
+    let theme = cards.theme;
+    theme.card(ui, cards.is_selected(a), |ui|{
+        let title = ui.colored_label(theme.title(), format!("🔹 {}",a.title));
+        cards.scroll(&title, a);
+        ui.add_space(PADDING);
+        let desc = Label::new(RichText::new(&a.desc).text_style(TextStyle::Button)).wrap(true);
+        match a.image.as_deref().filter(|_| !cards.state.config.hide_images) {
+            Some(image) => {
+                ui.horizontal(|ui|{
+                    cards.thumbnails.show(ui, image);
+                    ui.add(desc);
+                });
+            }
+            None => { ui.add(desc); }
+        }
+        ui.add_space(PADDING);
+
+        let actions = &mut cards.actions;
+        let draft = drafts.entry(a.url.clone()).or_insert_with(|| BookmarkDraft {
+            tags : bookmark.tags_text(),
+            note : bookmark.note.clone()
+        });
+        let tags = ui.add(TextEdit::singleline(&mut draft.tags)
+            .id_source(("saved_tags", &a.url))
+            .hint_text(tr!("saved-tags-hint"))
+            .desired_width(f32::INFINITY));
+        if tags.lost_focus() && draft.tags != bookmark.tags_text() {
+            actions.push(Action::SetBookmarkTags { url : a.url.clone(), tags : draft.tags.clone() });
+        }
+        let note = ui.add(TextEdit::multiline(&mut draft.note)
+            .id_source(("saved_note", &a.url))
+            .hint_text(tr!("saved-note-hint"))
+            .desired_rows(2)
+            .desired_width(f32::INFINITY));
+        if note.lost_focus() && draft.note.trim() != bookmark.note {
+            actions.push(Action::SetBookmarkNote { url : a.url.clone(), note : draft.note.clone() });
+        }
+
+        ui.add_space(PADDING);
+        ui.horizontal(|ui|{
+            ui.small(tr!("saved-at", date = bookmark.saved_at.format("%Y-%m-%d %H:%M").to_string()));
+            ui.with_layout(Layout::right_to_left(), |ui|{
+                if reader_link(ui).clicked() {
+                    actions.push(Action::OpenReader(a.clone()));
+                }
+                ui.add(Hyperlink::from_label_and_url("🌐",&a.url)).describe(tr!("open-browser"));
+                if bookmark_button(ui, true).clicked() {
+                    actions.push(Action::ToggleBookmark(a.clone()));
+                }
+                actions.extend(archive_button(ui, cards.state, a));
+            });
+        });
+    });
 }
 
-pub fn render_footer(ctx : &Context) -> () {
+pub fn render_footer(ctx : &Context) {
     TopBottomPanel::bottom("footer").show(ctx, |ui|{
         ui.vertical_centered(|ui|{
             ui.add_space(10.);
             //add api source
             ui.add(
-                Label::new(RichText::new("API source : newsapi.org")
+                Label::new(RichText::new(tr!("footer-source"))
                     .small()
                     .text_style(TextStyle::Monospace)
             )); 
                     
             //add link to egui framwork
             ui.add(Hyperlink::from_label_and_url(
-                "Made with egui",
+                tr!("footer-egui"),
                  "https://github.com/emilk/egui")     
             );
             //put github link to source code
@@ -214,16 +1196,92 @@ pub fn render_footer(ctx : &Context) -> () {
     });
 }
 
-pub fn render_header(ui : &mut Ui) -> () {
+/// tabs of the saved articles and of the alerts
+fn view_tabs(ui : &mut Ui,state : &AppState,view : &mut View) {
+    let saved = tr!("tab-saved", count = state.bookmarks.len());
+    if ui.selectable_label(*view == View::Saved, saved).clicked() {
+        *view = View::Saved;
+    }
+    let alerts = match state.alerts.unread(None) {
+        0 => tr!("tab-alerts"),
+        unread => tr!("tab-alerts-unread", count = unread)
+    };
+    if ui.selectable_label(*view == View::Alerts, alerts).on_hover_text(tr!("tab-alerts-hint")).clicked() {
+        *view = View::Alerts;
+    }
+}
+
+/// 💾 archives the article, or removes it from the archive once it is there
+pub(crate) fn archive_button(ui : &mut Ui,state : &AppState,article : &NewsCardData) -> Option<Action> {
+    let url = &article.url;
+    if state.archiving.contains(url) {
+        ui.add_enabled(false, Button::new("⏳").frame(false)).describe(tr!("archiving"));
+        return None;
+    }
+    if state.archive.contains(url) {
+        let clicked = ui.add(Button::new(RichText::new("💾").color(ui.visuals().hyperlink_color)).frame(false))
+            .describe(tr!("archived"))
+            .clicked();
+        return clicked.then(|| Action::Unarchive(url.clone()));
+    }
+    if state.config.offline {
+        return None;
+    }
+    let hint = match state.archive_errors.get(url) {
+        Some(error) => tr!("archive-failed", error = error.to_string()),
+        None => tr!("archive")
+    };
+    let icon = if state.archive_errors.contains_key(url) { "⚠" } else { "💾" };
+    let clicked = ui.add(Button::new(icon).frame(false)).describe(hint).clicked();
+    clicked.then(|| Action::Archive(article.clone()))
+}
+
+/// "read more", opening the article in the reader
+pub(crate) fn reader_link(ui : &mut Ui) -> egui::Response {
+    let color = ui.visuals().hyperlink_color;
+    ui.add(Button::new(RichText::new(tr!("read-more")).color(color)).frame(false))
+        .describe(tr!("open-reader"))
+}
+
+/// ☆ to save the article, ★ once it is saved
+pub(crate) fn bookmark_button(ui : &mut Ui,saved : bool) -> egui::Response {
+    let (icon, hint) = if saved { ("★", tr!("unsave")) } else { ("☆", tr!("save")) };
+    ui.add(Button::new(RichText::new(icon).text_style(TextStyle::Body)).frame(false))
+        .describe(hint)
+}
+
+/// the start of `desc`, cut on a char boundary
+pub(crate) fn preview(desc : &str) -> String {
+    match desc.char_indices().nth(DESC_PREVIEW) {
+        Some((end,_)) => format!("{}…",desc[..end].trim_end()),
+        None => desc.to_string()
+    }
+}
+
+/// the unread count and the muted articles of the feed on screen
+pub fn render_header(ui : &mut Ui,state : &AppState) -> Vec<Action> {
+    let mut actions = Vec::new();
+    let unread = state.unread_count();
+    let hidden = state.hidden_count();
     ui.vertical_centered(|ui|{
             ui.heading("headlines");
+            ui.horizontal(|ui|{
+                ui.label(tr!("unread", count = unread));
+                if ui.add_enabled(unread > 0, Button::new(tr!("mark-all-read")).small()).clicked() {
+                    actions.push(Action::MarkAllRead);
+                }
+                if hidden > 0 || state.show_hidden {
+                    let mut show_hidden = state.show_hidden;
+                    if ui.checkbox(&mut show_hidden, tr!("show-hidden", count = hidden))
+                        .on_hover_text(tr!("show-hidden-hint"))
+                        .changed() {
+                        actions.push(Action::ToggleShowHidden);
+                    }
+                }
+            });
     });
     ui.add_space(PADDING);
     let sep = Separator::default().spacing(20.);
     ui.add(sep);
-}
-pub struct NewsCardData{
-    pub title : String,
-    pub desc : String,
-    pub url : String
+    actions
 }
\ No newline at end of file
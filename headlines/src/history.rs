@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Serialize,Deserialize};
+
+use crate::{news::NewsCardData, storage};
+
+const STORAGE_NAME : &str = "history";
+
+/// Which articles were read, and when each one was first seen, keyed by url.
+// toml wants plain values before tables, keep `last_visit` first
+#[derive(Default,Serialize,Deserialize)]
+#[serde(default)]
+pub struct History {
+    /// start of the last session, stored for the next one
+    last_visit : Option<DateTime<Utc>>,
+    read : HashMap<String,DateTime<Utc>>,
+    first_seen : HashMap<String,DateTime<Utc>>,
+    /// start of the previous session, `None` on the first one
+    #[serde(skip)]
+    previous_visit : Option<DateTime<Utc>>,
+    #[serde(skip)]
+    session_start : Option<DateTime<Utc>>
+}
+
+impl History {
+    /// the stored history, starting a new session, forgetting articles neither read nor seen for `days`
+    pub fn load(days : u32) -> History {
+        let mut history : History = storage::load(STORAGE_NAME);
+        let now = Utc::now();
+        history.previous_visit = history.last_visit;
+        history.session_start = Some(now);
+        history.last_visit = Some(now);
+        history.forget_older_than(days);
+        history
+    }
+
+    pub fn store(&self) {
+        storage::store(STORAGE_NAME, self)
+    }
+
+    pub fn is_read(&self,url : &str) -> bool {
+        self.read.contains_key(url)
+    }
+
+    /// first seen during this session while there was a session before it
+    pub fn is_new(&self,url : &str) -> bool {
+        match (self.previous_visit, self.session_start, self.first_seen.get(url)) {
+            (Some(_), Some(start), Some(seen)) => *seen >= start,
+            _ => false
+        }
+    }
+
+    /// returns false if it was read already
+    pub fn mark_read(&mut self,url : &str) -> bool {
+        if self.is_read(url) {
+            return false;
+        }
+        self.read.insert(url.to_string(), Utc::now());
+        true
+    }
+
+    pub fn mark_unread(&mut self,url : &str) -> bool {
+        self.read.remove(url).is_some()
+    }
+
+    /// remembers when these articles were first seen, returns false if none is new
+    pub fn see(&mut self,articles : &[NewsCardData]) -> bool {
+        let now = Utc::now();
+        let mut changed = false;
+        for article in articles {
+            if !self.first_seen.contains_key(&article.url) {
+                self.first_seen.insert(article.url.clone(), now);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    pub fn unread_count(&self,articles : &[NewsCardData]) -> usize {
+        articles.iter().filter(|a| !self.is_read(&a.url)).count()
+    }
+
+    /// returns whether anything was forgotten
+    pub fn forget_older_than(&mut self,days : u32) -> bool {
+        let oldest = Utc::now() - Duration::days(days.into());
+        let len = self.read.len() + self.first_seen.len();
+        self.read.retain(|_,at| *at >= oldest);
+        let read = &self.read;
+        self.first_seen.retain(|url,at| *at >= oldest || read.contains_key(url));
+        self.read.len() + self.first_seen.len() != len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(url : &str) -> NewsCardData {
+        NewsCardData {
+            title : String::new(),
+            desc : String::new(),
+            url : url.to_string(),
+            image : None,
+            source : String::new(),
+            published_at : None,
+            category : None
+        }
+    }
+
+    /// a session started an hour ago, after one a day before it when `visited`
+    fn session(visited : bool) -> History {
+        let start = Utc::now() - Duration::hours(1);
+        History {
+            last_visit : Some(start),
+            previous_visit : visited.then(|| start - Duration::days(1)),
+            session_start : Some(start),
+            ..History::default()
+        }
+    }
+
+    #[test]
+    fn articles_first_seen_this_session_are_new() {
+        let mut history = session(true);
+        history.first_seen.insert("old".to_string(), Utc::now() - Duration::hours(2));
+        assert!(history.see(&[article("old"), article("new")]));
+        assert!(!history.see(&[article("new")]));
+        assert!(history.is_new("new"));
+        assert!(!history.is_new("old"));
+        assert!(!history.is_new("unseen"));
+    }
+
+    #[test]
+    fn nothing_is_new_on_the_first_session() {
+        let mut history = session(false);
+        history.see(&[article("new")]);
+        assert!(!history.is_new("new"));
+        assert!(!History::default().is_new("new"));
+    }
+
+    #[test]
+    fn reading_is_remembered_once() {
+        let mut history = session(true);
+        assert!(history.mark_read("a"));
+        assert!(!history.mark_read("a"));
+        assert_eq!(history.unread_count(&[article("a"), article("b")]), 1);
+        assert!(history.mark_unread("a"));
+        assert!(!history.mark_unread("a"));
+    }
+
+    #[test]
+    fn old_articles_are_forgotten_unless_read_recently() {
+        let mut history = session(true);
+        let now = Utc::now();
+        history.first_seen.insert("recent".to_string(), now - Duration::days(2));
+        history.first_seen.insert("old".to_string(), now - Duration::days(10));
+        history.first_seen.insert("old but read".to_string(), now - Duration::days(10));
+        history.read.insert("old but read".to_string(), now - Duration::days(1));
+        history.read.insert("read long ago".to_string(), now - Duration::days(10));
+
+        assert!(!history.forget_older_than(30));
+        assert!(history.forget_older_than(7));
+        let mut seen : Vec<&str> = history.first_seen.keys().map(String::as_str).collect();
+        seen.sort_unstable();
+        assert_eq!(seen, ["old but read", "recent"]);
+        assert!(history.is_read("old but read"));
+        assert!(!history.is_read("read long ago"));
+        assert!(!history.forget_older_than(7));
+    }
+}
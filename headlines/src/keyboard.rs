@@ -0,0 +1,201 @@
+use eframe::egui::{Align, Align2, Context, Event, Grid, Id, InputState, Key, RichText, ScrollArea, TextEdit, Window};
+
+use crate::{
+    headlines::{GRAY, PADDING},
+    l10n::binding_name,
+    settings::SettingsTab,
+    shortcuts::{Binding, KeyCombo, Shortcuts},
+    state::Action,
+    tr
+};
+
+/// whether `shortcut` was pressed this frame, invalid shortcuts never are
+///
+/// Those without Ctrl nor Alt are left to the text field `typing`, and the named
+/// keys like Enter or Space to the widget `focused`, which they activate.
+pub(crate) fn pressed(input : &InputState,shortcut : &str,typing : bool,focused : bool) -> bool {
+    let combo = match KeyCombo::parse(shortcut) {
+        Ok(combo) => combo,
+        Err(_) => return false
+    };
+    let named = combo.key.chars().count() > 1;
+    if !combo.ctrl && !combo.alt && (typing || (named && focused)) {
+        return false;
+    }
+    let modifiers = input.modifiers;
+    if !combo.ctrl && !combo.alt && !named {
+        // typed characters tell shift and the keyboard layout themselves
+        return !modifiers.command && !modifiers.alt
+            && input.events.iter().any(|event| matches!(event, Event::Text(text) if *text == combo.key));
+    }
+    let key = match egui_key(&combo.key) {
+        Some(key) => key,
+        None => return false
+    };
+    input.events.iter().any(|event| matches!(event,
+        Event::Key { key : pressed, pressed : true, modifiers }
+            if *pressed == key && modifiers.command == combo.ctrl && modifiers.alt == combo.alt && modifiers.shift == combo.shift))
+}
+
+/// the egui key of a letter, a digit or one of [`crate::NAMED_KEYS`]
+fn egui_key(name : &str) -> Option<Key> {
+    const LETTERS : [Key; 26] = [Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I, Key::J, Key::K, Key::L, Key::M,
+        Key::N, Key::O, Key::P, Key::Q, Key::R, Key::S, Key::T, Key::U, Key::V, Key::W, Key::X, Key::Y, Key::Z];
+    const DIGITS : [Key; 10] = [Key::Num0, Key::Num1, Key::Num2, Key::Num3, Key::Num4, Key::Num5, Key::Num6, Key::Num7, Key::Num8, Key::Num9];
+    let key = match name {
+        "Enter" => Key::Enter,
+        "Escape" => Key::Escape,
+        "Tab" => Key::Tab,
+        "Space" => Key::Space,
+        "Backspace" => Key::Backspace,
+        "Delete" => Key::Delete,
+        "Insert" => Key::Insert,
+        "Home" => Key::Home,
+        "End" => Key::End,
+        "PageUp" => Key::PageUp,
+        "PageDown" => Key::PageDown,
+        "Up" => Key::ArrowUp,
+        "Down" => Key::ArrowDown,
+        "Left" => Key::ArrowLeft,
+        "Right" => Key::ArrowRight,
+        _ => {
+            let c = name.chars().next()?;
+            return match c {
+                'A'..='Z' => Some(LETTERS[c as usize - 'A' as usize]),
+                '0'..='9' => Some(DIGITS[c as usize - '0' as usize]),
+                _ => None
+            };
+        }
+    };
+    Some(key)
+}
+
+/// the shortcuts of the settings, and the fixed ones
+pub(crate) fn help_window(ctx : &Context,shortcuts : &Shortcuts,open : &mut bool) {
+    Window::new(tr!("binding-keyboard-shortcuts")).id(Id::new("shortcuts_help")).open(open).collapsible(false).resizable(false).show(ctx, |ui|{
+        Grid::new("shortcuts_help").num_columns(2).spacing([20., 6.]).striped(true).show(ui, |ui|{
+            for binding in Binding::ALL {
+                ui.label(RichText::new(shortcuts.get(binding)).monospace());
+                ui.label(binding_name(binding));
+                ui.end_row();
+            }
+            for (keys, what) in [
+                ("Escape", tr!("help-escape")),
+                ("Ctrl + / Ctrl -", tr!("help-zoom")),
+                ("Ctrl 0", tr!("zoom-reset"))
+            ] {
+                ui.label(RichText::new(keys).monospace());
+                ui.label(what);
+                ui.end_row();
+            }
+        });
+        ui.add_space(PADDING);
+        ui.colored_label(GRAY, tr!("help-note"));
+    });
+}
+
+/// What a command of the palette does.
+pub(crate) enum Run {
+    Actions(Vec<Action>),
+    Settings(SettingsTab),
+    Search,
+    AdvancedSearch,
+    Help,
+    /// copies the saved articles as markdown
+    ExportSaved,
+    Quit
+}
+
+pub(crate) struct Command {
+    pub(crate) label : String,
+    pub(crate) shortcut : Option<String>,
+    pub(crate) run : Run
+}
+
+impl Command {
+    pub(crate) fn new(label : impl Into<String>,run : Run) -> Command {
+        Command { label : label.into(), shortcut : None, run }
+    }
+
+    pub(crate) fn action(label : impl Into<String>,action : Action) -> Command {
+        Command::new(label, Run::Actions(vec![action]))
+    }
+
+    pub(crate) fn shortcut(self,shortcut : &str) -> Command {
+        Command { shortcut : Some(shortcut.to_string()), ..self }
+    }
+}
+
+/// The command palette, `Some` while it is open.
+#[derive(Default)]
+pub(crate) struct Palette {
+    filter : String,
+    /// index in the commands matching the filter
+    selected : usize
+}
+
+/// the commands matching every word typed, run with Enter or a click
+pub(crate) fn palette_window(ctx : &Context,palette : &mut Option<Palette>,commands : Vec<Command>) -> Option<Run> {
+    let current = palette.as_mut()?;
+    let words : Vec<String> = current.filter.split_whitespace().map(str::to_lowercase).collect();
+    let mut matching : Vec<Command> = commands.into_iter()
+        .filter(|command| {
+            let label = command.label.to_lowercase();
+            words.iter().all(|word| label.contains(word.as_str()))
+        })
+        .collect();
+    let (up, down, enter, escape) = {
+        let input = ctx.input();
+        (input.key_pressed(Key::ArrowUp), input.key_pressed(Key::ArrowDown), input.key_pressed(Key::Enter), input.key_pressed(Key::Escape))
+    };
+    if down {
+        current.selected += 1;
+    }
+    if up {
+        current.selected = current.selected.saturating_sub(1);
+    }
+    current.selected = current.selected.min(matching.len().saturating_sub(1));
+    let mut clicked = None;
+    let mut open = !escape;
+    Window::new(tr!("binding-command-palette"))
+        .id(Id::new("command_palette"))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_TOP, [0., 60.])
+        .default_width(360.)
+        .show(ctx, |ui|{
+            let filter = ui.add(TextEdit::singleline(&mut current.filter)
+                .hint_text(tr!("palette-hint"))
+                .desired_width(f32::INFINITY));
+            filter.request_focus();
+            if filter.changed() {
+                current.selected = 0;
+            }
+            ui.add_space(PADDING);
+            ScrollArea::vertical().max_height(300.).show(ui, |ui|{
+                for (i, command) in matching.iter().enumerate() {
+                    ui.horizontal(|ui|{
+                        let row = ui.selectable_label(i == current.selected, &command.label);
+                        if i == current.selected && (up || down) {
+                            row.scroll_to_me(Some(Align::Center));
+                        }
+                        if row.clicked() {
+                            clicked = Some(i);
+                        }
+                        if let Some(shortcut) = &command.shortcut {
+                            ui.colored_label(GRAY, RichText::new(shortcut).monospace());
+                        }
+                    });
+                }
+                if matching.is_empty() {
+                    ui.colored_label(GRAY, tr!("palette-empty"));
+                }
+            });
+        });
+    let chosen = clicked.or_else(|| Some(current.selected).filter(|_| enter && !matching.is_empty()));
+    if !open || chosen.is_some() {
+        *palette = None;
+    }
+    chosen.map(|i| matching.swap_remove(i).run)
+}
@@ -0,0 +1,262 @@
+use std::{cell::RefCell, fmt, sync::OnceLock};
+
+use fluent::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use newsApi::{Category, Country, Language, SortBy};
+use unic_langid::LanguageIdentifier;
+
+use crate::shortcuts::Binding;
+
+/// Languages the interface is translated to, English being complete.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum Locale {
+    En,
+    Fr
+}
+
+impl Locale {
+    pub const ALL : [Locale; 2] = [Locale::En, Locale::Fr];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Fr => "fr"
+        }
+    }
+
+    /// the name of the language in that language
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Fr => "Français"
+        }
+    }
+
+    fn messages(&self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.ftl"),
+            Locale::Fr => include_str!("../locales/fr.ftl")
+        }
+    }
+
+    /// the locale of a language tag like `fr-CA`
+    pub fn from_tag(tag : &str) -> Option<Locale> {
+        let language = tag.parse::<LanguageIdentifier>().ok()?;
+        Locale::ALL.into_iter().find(|locale| locale.code() == language.language.as_str())
+    }
+
+    /// the one of the system, English where it is not translated
+    pub fn system() -> Locale {
+        system_tag().and_then(|tag| Locale::from_tag(&tag)).unwrap_or(Locale::En)
+    }
+
+    /// the locale of [`crate::HeadlinesConfig::locale`], empty for the system one
+    pub fn from_setting(setting : &str) -> Locale {
+        Locale::from_tag(setting).unwrap_or_else(Locale::system)
+    }
+
+    /// the newsapi language and country of the locale, the country of the system
+    /// where it speaks that language
+    pub fn news_defaults(&self) -> (Language, Country) {
+        let language = Language::ALL.into_iter().find(|l| l.code() == self.code()).unwrap_or(Language::En);
+        let region = system_tag()
+            .and_then(|tag| tag.parse::<LanguageIdentifier>().ok())
+            .filter(|system| system.language.as_str() == self.code())
+            .and_then(|system| system.region)
+            .and_then(|region| Country::ALL.into_iter().find(|c| c.code().eq_ignore_ascii_case(region.as_str())));
+        let country = region.unwrap_or(match self {
+            Locale::En => Country::Us,
+            Locale::Fr => Country::Fr
+        });
+        (language, country)
+    }
+}
+
+/// like `fr-FR`, read once
+fn system_tag() -> Option<String> {
+    static TAG : OnceLock<Option<String>> = OnceLock::new();
+    TAG.get_or_init(|| {
+        // posix locales look like fr_FR.UTF-8
+        let tag = sys_locale::get_locale()?;
+        Some(tag.split('.').next().unwrap_or_default().replace('_', "-"))
+    }).clone()
+}
+
+/// The messages of a locale, and the English ones for those it lacks.
+struct Messages {
+    locale : Locale,
+    bundle : FluentBundle<FluentResource>,
+    english : Option<FluentBundle<FluentResource>>
+}
+
+impl Messages {
+    fn new(locale : Locale) -> Messages {
+        Messages {
+            locale,
+            bundle : bundle(locale),
+            english : (locale != Locale::En).then(|| bundle(Locale::En))
+        }
+    }
+
+    fn format(&self,id : &str,args : Option<&FluentArgs>) -> Option<String> {
+        std::iter::once(&self.bundle).chain(&self.english).find_map(|bundle| {
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, args, &mut errors).into_owned();
+            for error in errors {
+                tracing::warn!("message {} : {}",id,error);
+            }
+            Some(text)
+        })
+    }
+}
+
+fn bundle(locale : Locale) -> FluentBundle<FluentResource> {
+    let language = locale.code().parse::<LanguageIdentifier>().expect("locale codes are valid language tags");
+    let mut bundle = FluentBundle::new(vec![language]);
+    // egui draws the unicode isolation marks fluent puts around arguments
+    bundle.set_use_isolating(false);
+    let resource = FluentResource::try_new(locale.messages().to_string())
+        .unwrap_or_else(|(resource, errors)| {
+            tracing::error!("invalid {} messages : {:?}",locale.code(),errors);
+            resource
+        });
+    if let Err(errors) = bundle.add_resource(resource) {
+        tracing::error!("duplicate {} messages : {:?}",locale.code(),errors);
+    }
+    bundle
+}
+
+thread_local! {
+    static MESSAGES : RefCell<Messages> = RefCell::new(Messages::new(Locale::En));
+}
+
+/// Translates the messages of this thread to `locale` from now on.
+pub fn set_locale(locale : Locale) {
+    MESSAGES.with(|messages| {
+        if messages.borrow().locale != locale {
+            *messages.borrow_mut() = Messages::new(locale);
+        }
+    });
+}
+
+pub fn locale() -> Locale {
+    MESSAGES.with(|messages| messages.borrow().locale)
+}
+
+/// The message `id` in the current locale, see [`tr!`](crate::tr!).
+pub fn tr(id : &str) -> String {
+    tr_args(id, &[])
+}
+
+pub fn tr_args(id : &str,args : &[(&str,FluentValue)]) -> String {
+    let args = (!args.is_empty()).then(|| {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        fluent_args
+    });
+    MESSAGES.with(|messages| messages.borrow().format(id, args.as_ref()))
+        .unwrap_or_else(|| {
+            tracing::warn!("no message {}",id);
+            id.to_string()
+        })
+}
+
+/// A message translated when it is shown rather than when it is made, for the errors
+/// of the settings which outlive a change of locale. See [`message!`](crate::message!).
+#[derive(Clone,Debug,PartialEq)]
+pub struct Message {
+    pub id : &'static str,
+    args : Vec<(&'static str,FluentValue<'static>)>
+}
+
+impl Message {
+    pub fn new(id : &'static str,args : Vec<(&'static str,FluentValue<'static>)>) -> Message {
+        Message { id, args }
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self,f : &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&tr_args(self.id, &self.args))
+    }
+}
+
+/// for the errors of the command line, which are shown at once
+impl From<Message> for String {
+    fn from(message : Message) -> String {
+        message.to_string()
+    }
+}
+
+/// the translated name, or the English one of newsapi
+fn tr_or(id : &str,english : &str) -> String {
+    MESSAGES.with(|messages| messages.borrow().format(id, None)).unwrap_or_else(|| english.to_string())
+}
+
+pub fn country_name(country : Country) -> String {
+    tr_or(&format!("country-{}",country.code()), country.name())
+}
+
+pub fn language_name(language : Language) -> String {
+    tr_or(&format!("language-{}",language.code()), language.name())
+}
+
+pub fn category_name(category : Category) -> String {
+    tr_or(&format!("category-{}",category.name().to_lowercase()), category.name())
+}
+
+pub fn sort_name(sort_by : SortBy) -> String {
+    tr_or(&format!("sort-{}",sort_by.name().to_lowercase().replace(' ', "-")), sort_by.name())
+}
+
+pub fn binding_name(binding : Binding) -> String {
+    tr_or(&format!("binding-{}",binding.name().to_lowercase().replace(' ', "-")), binding.name())
+}
+
+/// The message `id` of the current locale, with its arguments if any.
+///
+/// ```ignore
+/// tr!("loading")
+/// tr!("unread", count = 3)
+/// ```
+#[macro_export]
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::tr($id)
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::tr_args($id, &[$((stringify!($name), $crate::FluentValue::from($value))),+])
+    };
+}
+
+/// A [`Message`] to translate once it is shown, with the arguments of [`tr!`](crate::tr!).
+///
+/// ```ignore
+/// Err(message!("invalid-profile-name-length"))
+/// ```
+#[macro_export]
+macro_rules! message {
+    ($id:literal) => {
+        $crate::Message::new($id, Vec::new())
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::Message::new($id, vec![$((stringify!($name), $crate::FluentValue::from($value).into_owned())),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_are_translated_when_shown() {
+        let zoom = crate::message!("invalid-zoom", min = 50_f32, max = 300_f32);
+        assert_eq!(zoom.to_string(), "zoom must be between 50% and 300%");
+        set_locale(Locale::Fr);
+        assert_eq!(zoom.to_string(), "le zoom va de 50 % à 300 %");
+        set_locale(Locale::En);
+        assert_eq!(String::from(crate::message!("invalid-rule-operator", operator = "OR")), "OR needs words on both sides");
+    }
+}
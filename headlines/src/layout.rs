@@ -0,0 +1,197 @@
+use chrono::{Datelike, Local, NaiveDate};
+use newsApi::Category;
+use serde::{Serialize,Deserialize};
+
+use crate::{news::NewsCardData, sort::SortOrder, state::View};
+
+/// How the articles of the feed are laid out.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Serialize,Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Layout {
+    /// one line per headline
+    Compact,
+    /// a card per article with its thumbnail and description
+    #[default]
+    Cards,
+    /// columns of tiles with big images, as many as the window fits
+    Magazine
+}
+
+impl Layout {
+    pub const ALL : [Layout; 3] = [Layout::Compact, Layout::Cards, Layout::Magazine];
+}
+
+/// What the articles of the feed are grouped by, under collapsible headers.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Serialize,Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    #[default]
+    None,
+    Source,
+    Category,
+    /// the day they were published, in the local time zone
+    Date
+}
+
+impl GroupBy {
+    pub const ALL : [GroupBy; 4] = [GroupBy::None, GroupBy::Source, GroupBy::Category, GroupBy::Date];
+}
+
+/// The layout, grouping and order chosen for a view.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Serialize,Deserialize)]
+#[serde(default)]
+pub struct ViewLayout {
+    pub layout : Layout,
+    pub group_by : GroupBy,
+    pub sort : SortOrder
+}
+
+/// The [`ViewLayout`] of each view, remembered apart.
+#[derive(Clone,Debug,Default,PartialEq,Serialize,Deserialize)]
+#[serde(default)]
+pub struct Layouts {
+    pub feed : ViewLayout,
+    pub saved : ViewLayout,
+    pub alerts : ViewLayout
+}
+
+impl Layouts {
+    pub fn get(&self,view : View) -> ViewLayout {
+        match view {
+            View::Feed => self.feed,
+            View::Saved => self.saved,
+            View::Alerts => self.alerts
+        }
+    }
+
+    pub fn get_mut(&mut self,view : View) -> &mut ViewLayout {
+        match view {
+            View::Feed => &mut self.feed,
+            View::Saved => &mut self.saved,
+            View::Alerts => &mut self.alerts
+        }
+    }
+}
+
+/// The header articles are grouped under.
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub enum GroupKey {
+    /// the only group when they are not grouped
+    All,
+    /// the publication, empty when newsapi.org does not know it
+    Source(String),
+    Category(Option<Category>),
+    Date(Option<NaiveDate>)
+}
+
+impl GroupKey {
+    fn of(article : &NewsCardData,group_by : GroupBy) -> GroupKey {
+        match group_by {
+            GroupBy::None => GroupKey::All,
+            GroupBy::Source => GroupKey::Source(article.source.clone()),
+            GroupBy::Category => GroupKey::Category(article.category),
+            GroupBy::Date => GroupKey::Date(article.published_at.map(|date| date.with_timezone(&Local).date_naive()))
+        }
+    }
+}
+
+/// `articles` grouped by `group_by`, in the order of their first article, the days
+/// newest first unless sorted `order` oldest first. The articles keep their order and their
+/// index within a group.
+pub fn group<'a>(articles : impl IntoIterator<Item = (usize,&'a NewsCardData)>,group_by : GroupBy,order : SortOrder) -> Vec<(GroupKey,Vec<(usize,&'a NewsCardData)>)> {
+    let mut groups : Vec<(GroupKey,Vec<(usize,&NewsCardData)>)> = Vec::new();
+    for (i, article) in articles {
+        let key = GroupKey::of(article, group_by);
+        match groups.iter_mut().find(|(k,_)| *k == key) {
+            Some((_,group)) => group.push((i, article)),
+            None => groups.push((key, vec![(i, article)]))
+        }
+    }
+    if group_by == GroupBy::Date {
+        // the unknown dates last
+        let oldest_first = order == SortOrder::Oldest;
+        groups.sort_by_key(|(key,_)| match key {
+            GroupKey::Date(Some(date)) => (false, if oldest_first { date.num_days_from_ce() } else { -date.num_days_from_ce() }),
+            _ => (true, 0)
+        });
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn article(title : &str,source : &str,day : Option<u32>) -> NewsCardData {
+        NewsCardData {
+            title : title.to_string(),
+            desc : String::new(),
+            url : format!("https://example.com/{}",title),
+            image : None,
+            source : source.to_string(),
+            published_at : day.map(|day| Utc.with_ymd_and_hms(2022, 3, day, 12, 0, 0).unwrap()),
+            category : None
+        }
+    }
+
+    fn groups(articles : &[NewsCardData],group_by : GroupBy,order : SortOrder) -> Vec<(GroupKey,Vec<String>)> {
+        group(articles.iter().enumerate(), group_by, order).into_iter()
+            .map(|(key, group)| (key, group.into_iter().map(|(i, a)| {
+                assert_eq!(articles[i], *a);
+                a.title.clone()
+            }).collect()))
+            .collect()
+    }
+
+    fn day(day : u32) -> GroupKey {
+        GroupKey::Date(article("", "", Some(day)).published_at.map(|date| date.with_timezone(&Local).date_naive()))
+    }
+
+    fn articles() -> Vec<NewsCardData> {
+        vec![
+            article("a", "Wire", Some(2)),
+            article("b", "", None),
+            article("c", "Daily", Some(3)),
+            article("d", "Wire", Some(3)),
+            article("e", "", Some(2))
+        ]
+    }
+
+    #[test]
+    fn a_single_group_without_grouping() {
+        assert_eq!(groups(&articles(), GroupBy::None, SortOrder::Default), [(GroupKey::All, vec!["a".into(), "b".into(), "c".into(), "d".into(), "e".into()])]);
+        assert!(groups(&[], GroupBy::None, SortOrder::Default).is_empty());
+    }
+
+    #[test]
+    fn sources_in_the_order_of_their_first_article() {
+        assert_eq!(groups(&articles(), GroupBy::Source, SortOrder::Default), [
+            (GroupKey::Source("Wire".into()), vec!["a".to_string(), "d".into()]),
+            (GroupKey::Source(String::new()), vec!["b".into(), "e".into()]),
+            (GroupKey::Source("Daily".into()), vec!["c".into()])
+        ]);
+    }
+
+    #[test]
+    fn days_newest_first_and_unknown_dates_last() {
+        let expected = [
+            (day(3), vec!["c".to_string(), "d".into()]),
+            (day(2), vec!["a".into(), "e".into()]),
+            (GroupKey::Date(None), vec!["b".into()])
+        ];
+        for order in [SortOrder::Default, SortOrder::Newest, SortOrder::Title] {
+            assert_eq!(groups(&articles(), GroupBy::Date, order), expected, "{:?}", order);
+        }
+    }
+
+    #[test]
+    fn days_oldest_first_when_sorted_so() {
+        assert_eq!(groups(&articles(), GroupBy::Date, SortOrder::Oldest), [
+            (day(2), vec!["a".to_string(), "e".into()]),
+            (day(3), vec!["c".into(), "d".into()]),
+            (GroupKey::Date(None), vec!["b".into()])
+        ]);
+    }
+}
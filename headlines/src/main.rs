@@ -1,10 +1,26 @@
+use std::process::ExitCode;
+
 use eframe::{NativeOptions,egui::Vec2, run_native};
-use headlines::Headlines;
+use headlines::{Headlines, Overrides, USAGE};
 
-fn main() -> () {
+fn main() -> ExitCode {
     tracing_subscriber::fmt::init();
-    let app = Headlines::new();
-    let mut win_options = NativeOptions::default();
-    win_options.initial_window_size = Some(Vec2::new(600., 650.));
+    let overrides = match Overrides::load(std::env::args_os().skip(1)) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("{}\n\nUsage : headlines [OPTIONS]\n\n{}",e,USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    if overrides.help {
+        print!("Usage : headlines [OPTIONS]\n\n{}",USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let (width, height) = overrides.window_size.unwrap_or((600., 650.));
+    let app = Headlines::with_overrides(overrides);
+    let win_options = NativeOptions {
+        initial_window_size: Some(Vec2::new(width, height)),
+        ..Default::default()
+    };
     run_native(Box::new(app),win_options);
 }
@@ -0,0 +1,60 @@
+//! The http agent shared by every native request, configured from the settings.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{sync::RwLock, time::Duration};
+
+use crate::l10n::Message;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::message;
+
+#[cfg(not(target_arch = "wasm32"))]
+static AGENT : RwLock<Option<ureq::Agent>> = RwLock::new(None);
+
+/// Seconds before a request is given up until [`configure`] is called.
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_TIMEOUT_SECS : u32 = 30;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn build(timeout_secs : u32,proxy : &str) -> Result<ureq::Agent,String> {
+    let mut builder = ureq::AgentBuilder::new().timeout(Duration::from_secs(timeout_secs.into()));
+    if !proxy.trim().is_empty() {
+        let proxy = ureq::Proxy::new(proxy.trim()).map_err(|e| format!("invalid proxy : {}",e))?;
+        builder = builder.proxy(proxy);
+    }
+    Ok(builder.build())
+}
+
+/// Applies to the requests started from now on.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn configure(timeout_secs : u32,proxy : &str) {
+    let agent = match build(timeout_secs, proxy) {
+        Ok(agent) => agent,
+        Err(e) => {
+            tracing::error!("{}, connecting directly",e);
+            // validated settings never get here, keep the timeout at least
+            build(timeout_secs, "").unwrap_or_else(|_| ureq::agent())
+        }
+    };
+    if let Ok(mut shared) = AGENT.write() {
+        *shared = Some(agent);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn agent() -> ureq::Agent {
+    if let Some(agent) = AGENT.read().ok().and_then(|shared| shared.clone()) {
+        return agent;
+    }
+    build(DEFAULT_TIMEOUT_SECS, "").unwrap_or_else(|_| ureq::agent())
+}
+
+/// The browser handles proxies on wasm32, any value is accepted.
+pub(crate) fn validate_proxy(proxy : &str) -> Result<(),Message> {
+    #[cfg(not(target_arch = "wasm32"))]
+    if !proxy.trim().is_empty() {
+        ureq::Proxy::new(proxy.trim()).map_err(|e| message!("invalid-proxy", error = e.to_string()))?;
+    }
+    #[cfg(target_arch = "wasm32")]
+    let _ = proxy;
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use newsApi::{Article, Category};
+use serde::{Serialize,Deserialize};
+
+#[derive(Clone,Debug,PartialEq,Serialize,Deserialize)]
+pub struct NewsCardData{
+    pub title : String,
+    pub desc : String,
+    pub url : String,
+    /// thumbnail url, when the article has one
+    #[serde(default)]
+    pub image : Option<String>,
+    /// name of the publication, empty when newsapi.org does not know it
+    #[serde(default)]
+    pub source : String,
+    #[serde(default)]
+    pub published_at : Option<DateTime<Utc>>,
+    /// the top headlines category it was fetched from, `None` for searches
+    #[serde(default)]
+    pub category : Option<Category>
+}
+
+impl NewsCardData {
+    /// host of the article url, without `www.`
+    pub fn domain(&self) -> Option<String> {
+        let url = url::Url::parse(&self.url).ok()?;
+        let host = url.host_str()?;
+        Some(host.trim_start_matches("www.").to_string())
+    }
+}
+
+impl From<&Article> for NewsCardData {
+    fn from(a : &Article) -> Self {
+        NewsCardData{
+            title : a.title().to_string(),
+            url: a.url().to_string(),
+            desc : a.desc().unwrap_or("...").to_string(),
+            image : a.url_to_image().filter(|u| !u.is_empty()).map(str::to_string),
+            source : a.source().map(|s| s.name().to_string()).unwrap_or_default(),
+            published_at : a.published_at()
+                .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+                .map(|date| date.with_timezone(&Utc)),
+            category : None
+        }
+    }
+}
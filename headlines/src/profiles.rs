@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+
+use serde::{Serialize,Deserialize};
+use serde_json::{json, Value};
+
+use crate::{config::HeadlinesConfig, l10n::Message, message, storage::{self, APP_NAME}};
+
+/// Version of the config file written by this build.
+const CURRENT_VERSION : u64 = 3;
+/// Profile of the configs written before profiles existed.
+pub const DEFAULT_PROFILE : &str = "default";
+
+/// Upgrades the stored config by one version, `MIGRATIONS[n]` going from version `n` to `n + 1`.
+const MIGRATIONS : [fn(Value) -> Value; CURRENT_VERSION as usize] = [
+    // version 0 was a single unversioned `HeadlinesConfig`
+    |config| json!({
+        "version" : 1,
+        "active" : DEFAULT_PROFILE,
+        "profiles" : { DEFAULT_PROFILE : config }
+    }),
+    // version 1 had a dark mode switch where themes are now
+    |mut config| {
+        if let Some(profiles) = config.get_mut("profiles").and_then(Value::as_object_mut) {
+            for profile in profiles.values_mut().filter_map(Value::as_object_mut) {
+                let dark = profile.remove("dark_mode").and_then(|dark| dark.as_bool()).unwrap_or(false);
+                profile.insert("theme".to_string(), json!(if dark { "Dark" } else { "Light" }));
+            }
+        }
+        config["version"] = json!(2);
+        config
+    },
+    // version 2 had a single text size the other styles scaled with
+    |mut config| {
+        if let Some(profiles) = config.get_mut("profiles").and_then(Value::as_object_mut) {
+            for profile in profiles.values_mut().filter_map(Value::as_object_mut) {
+                if let Some(size) = profile.remove("text_size").and_then(|size| size.as_f64()) {
+                    let scale = size / 14.;
+                    profile.insert("text_sizes".to_string(), json!({
+                        "small" : 10. * scale,
+                        "body" : size,
+                        "button" : size,
+                        "heading" : 20. * scale,
+                        "monospace" : size
+                    }));
+                }
+            }
+        }
+        config["version"] = json!(3);
+        config
+    }
+];
+
+/// The config file, one [`HeadlinesConfig`] per named profile.
+///
+/// Files written by older versions are migrated on load, after a copy of them is
+/// backed up next to the file as `headlines.v<version>.bak.toml`.
+// toml wants plain values before tables, keep `profiles` last
+#[derive(Clone,Debug,Serialize,Deserialize)]
+pub struct Profiles {
+    version : u64,
+    /// the profile used on start
+    active : String,
+    profiles : BTreeMap<String,HeadlinesConfig>
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Profiles {
+            version : CURRENT_VERSION,
+            active : DEFAULT_PROFILE.to_string(),
+            profiles : BTreeMap::from([(DEFAULT_PROFILE.to_string(), HeadlinesConfig::localized(""))])
+        }
+    }
+}
+
+impl Profiles {
+    pub fn load() -> Profiles {
+        let value = match storage::load_value(APP_NAME) {
+            Ok(Some(value)) => value,
+            Ok(None) => return Profiles::default(),
+            Err(e) => return unreadable(&e)
+        };
+        let version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+        if version > CURRENT_VERSION {
+            tracing::warn!("config version {} is newer than this build, settings it does not know are dropped",version);
+        }
+        let outdated = version < CURRENT_VERSION;
+        if outdated {
+            tracing::info!("migrating config from version {} to {}",version,CURRENT_VERSION);
+            if let Err(e) = storage::backup(APP_NAME, &format!("v{}",version)) {
+                tracing::error!("failed backing up the config before migrating it : {}",e);
+            }
+        }
+        let value = migrate(value, version);
+        let mut profiles : Profiles = match serde_json::from_value(value) {
+            Ok(profiles) => profiles,
+            Err(e) => return unreadable(&e.to_string())
+        };
+        profiles.version = CURRENT_VERSION;
+        if profiles.profiles.is_empty() {
+            profiles.profiles.insert(DEFAULT_PROFILE.to_string(), HeadlinesConfig::localized(""));
+        }
+        if !profiles.profiles.contains_key(&profiles.active) {
+            profiles.active = profiles.profiles.keys().next().cloned().unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+        }
+        if outdated {
+            profiles.store();
+        }
+        profiles
+    }
+
+    pub fn store(&self) {
+        storage::store(APP_NAME, self)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    pub fn active(&self) -> &str {
+        &self.active
+    }
+
+    pub fn contains(&self,name : &str) -> bool {
+        self.profiles.contains_key(name)
+    }
+
+    pub fn get(&self,name : &str) -> Option<&HeadlinesConfig> {
+        self.profiles.get(name)
+    }
+
+    /// makes `name` the profile used on start, adding it with `config` when missing
+    pub fn activate(&mut self,name : &str,config : HeadlinesConfig) {
+        self.profiles.entry(name.to_string()).or_insert(config);
+        self.active = name.to_string();
+    }
+
+    /// replaces the config of the `name` profile, adding it when missing
+    pub fn set(&mut self,name : &str,config : HeadlinesConfig) {
+        self.profiles.insert(name.to_string(), config);
+    }
+
+    /// the last profile and the active one are kept
+    pub fn remove(&mut self,name : &str) -> bool {
+        name != self.active && self.profiles.remove(name).is_some()
+    }
+}
+
+/// `config` of `version` upgraded to the current one
+fn migrate(config : Value,version : u64) -> Value {
+    MIGRATIONS.iter()
+        .skip(version as usize)
+        .fold(config, |config, migration| migration(config))
+}
+
+/// the default profiles, after backing up the file they are replacing
+fn unreadable(error : &str) -> Profiles {
+    tracing::error!("failed loading the config, starting from the defaults : {}",error);
+    if let Err(e) = storage::backup(APP_NAME, "unreadable") {
+        tracing::error!("failed backing up the config : {}",e);
+    }
+    Profiles::default()
+}
+
+/// valid profile names are short and can be typed on the command line
+pub fn validate_name(name : &str) -> Result<(),Message> {
+    if name.is_empty() || name.len() > 32 {
+        return Err(message!("invalid-profile-name-length"));
+    }
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return Err(message!("invalid-profile-name"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::storage::TestDir;
+    use crate::config::TextSizes;
+
+    /// the profiles in a toml file of `version`
+    fn migrated(toml : &str,version : u64) -> Profiles {
+        let value : toml::Value = toml::from_str(toml).unwrap();
+        serde_json::from_value(migrate(serde_json::to_value(value).unwrap(), version)).unwrap()
+    }
+
+    const V0 : &str = r#"
+api_key = "key"
+dark_mode = true
+text_size = 21.0
+"#;
+
+    #[test]
+    fn version_0_becomes_the_default_profile() {
+        let profiles = migrated(V0, 0);
+        assert_eq!(profiles.version, 3);
+        assert_eq!(profiles.active(), DEFAULT_PROFILE);
+        let config = profiles.get(DEFAULT_PROFILE).unwrap();
+        assert_eq!(config.api_key, "key");
+        assert_eq!(config.theme, "Dark");
+        assert_eq!(config.text_sizes, TextSizes { small : 15., body : 21., button : 21., heading : 30., monospace : 21. });
+    }
+
+    #[test]
+    fn version_1_dark_mode_becomes_a_theme() {
+        let profiles = migrated(r#"
+version = 1
+active = "work"
+
+[profiles.work]
+api_key = "work key"
+dark_mode = false
+
+[profiles.home]
+dark_mode = true
+"#, 1);
+        assert_eq!(profiles.active(), "work");
+        assert_eq!(profiles.get("work").unwrap().theme, "Light");
+        assert_eq!(profiles.get("work").unwrap().api_key, "work key");
+        assert_eq!(profiles.get("home").unwrap().theme, "Dark");
+        assert_eq!(profiles.get("home").unwrap().text_sizes, TextSizes::default());
+    }
+
+    #[test]
+    fn version_2_text_size_scales_the_text_styles() {
+        let profiles = migrated(r#"
+version = 2
+active = "default"
+
+[profiles.default]
+theme = "Dark"
+text_size = 14.0
+"#, 2);
+        let config = profiles.get(DEFAULT_PROFILE).unwrap();
+        assert_eq!(config.theme, "Dark");
+        assert_eq!(config.text_sizes, TextSizes::default());
+    }
+
+    #[test]
+    fn old_files_are_backed_up_before_they_are_migrated() {
+        let dir = TestDir::new("backup");
+        fs::write(dir.file("headlines.toml"), V0).unwrap();
+
+        let profiles = Profiles::load();
+        assert_eq!(profiles.get(DEFAULT_PROFILE).unwrap().theme, "Dark");
+        assert_eq!(fs::read_to_string(dir.file("headlines.v0.bak.toml")).unwrap(), V0);
+        let stored = fs::read_to_string(dir.file("headlines.toml")).unwrap();
+        assert!(stored.contains("version = 3"), "{}",stored);
+    }
+
+    #[test]
+    fn unreadable_files_are_backed_up_and_replaced_by_the_defaults() {
+        let dir = TestDir::new("unreadable");
+        fs::write(dir.file("headlines.toml"), "version = [").unwrap();
+
+        let profiles = Profiles::load();
+        assert_eq!(profiles.names().collect::<Vec<_>>(), [DEFAULT_PROFILE]);
+        assert_eq!(fs::read_to_string(dir.file("headlines.unreadable.bak.toml")).unwrap(), "version = [");
+    }
+
+    #[test]
+    fn current_files_are_not_backed_up() {
+        let dir = TestDir::new("current");
+        Profiles::default().store();
+        Profiles::load();
+        assert!(!dir.file("headlines.v3.bak.toml").exists());
+    }
+}
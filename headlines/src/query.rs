@@ -0,0 +1,189 @@
+use chrono::NaiveDate;
+use newsApi::{Category, Country, Endpoint, Language, NewsApi, SortBy};
+use serde::{Serialize,Deserialize};
+
+use crate::{l10n::Message, message};
+
+/// What to ask newsapi.org for, owned by the fetch worker.
+///
+/// Without keywords, sources or domains it asks for the top headlines of `country`
+/// in `category`, otherwise it searches `everything` and the remaining filters apply.
+#[derive(Clone,Debug,PartialEq,Eq,Hash,Serialize,Deserialize)]
+#[serde(default)]
+pub struct NewsQuery {
+    pub country : Country,
+    /// `None` for all the top headlines
+    pub category : Option<Category>,
+    pub keywords : String,
+    pub language : Option<Language>,
+    pub sort_by : Option<SortBy>,
+    /// `YYYY-MM-DD`, empty for no bound
+    pub from : String,
+    pub to : String,
+    /// comma separated source ids
+    pub sources : String,
+    /// comma separated domains
+    pub domains : String
+}
+
+impl Default for NewsQuery {
+    fn default() -> Self {
+        NewsQuery {
+            country : Country::Us,
+            category : None,
+            keywords : String::new(),
+            language : None,
+            sort_by : None,
+            from : String::new(),
+            to : String::new(),
+            sources : String::new(),
+            domains : String::new()
+        }
+    }
+}
+
+impl NewsQuery {
+    pub fn endpoint(&self) -> Endpoint {
+        if self.keywords.trim().is_empty() && self.sources.trim().is_empty() && self.domains.trim().is_empty() {
+            Endpoint::TopHeadlines
+        } else {
+            Endpoint::Everything
+        }
+    }
+
+    pub fn is_search(&self) -> bool {
+        self.endpoint() == Endpoint::Everything
+    }
+
+    /// same filters, other keywords
+    pub fn with_keywords(&self,keywords : &str) -> NewsQuery {
+        NewsQuery { keywords : keywords.trim().to_string(), ..self.clone() }
+    }
+
+    /// the top headlines of `category`, dropping the search filters
+    pub fn with_category(&self,category : Option<Category>) -> NewsQuery {
+        NewsQuery {
+            category,
+            keywords : String::new(),
+            sources : String::new(),
+            domains : String::new(),
+            ..self.clone()
+        }
+    }
+
+    /// the first invalid field, if any
+    pub fn validate(&self) -> Result<(),Message> {
+        for date in [&self.from, &self.to] {
+            if !date.is_empty() && !is_valid_date(date) {
+                return Err(message!("invalid-date", date = date.as_str()));
+            }
+        }
+        if !self.from.is_empty() && !self.to.is_empty() && self.from > self.to {
+            return Err(message!("invalid-date-order"));
+        }
+        Ok(())
+    }
+
+    pub fn request(&self,api_key : &str) -> NewsApi {
+        let mut api = NewsApi::new(api_key);
+        api.endpoint(self.endpoint()).country(self.country);
+        if !self.keywords.trim().is_empty() {
+            api.query(self.keywords.trim());
+        }
+        if !self.from.is_empty() {
+            api.from(&self.from);
+        }
+        if !self.to.is_empty() {
+            api.to(&self.to);
+        }
+        if !self.sources.trim().is_empty() {
+            api.sources(self.sources.trim());
+        }
+        if !self.domains.trim().is_empty() {
+            api.domains(self.domains.trim());
+        }
+        if let Some(category) = self.category {
+            api.category(category);
+        }
+        if let Some(language) = self.language {
+            api.language(language);
+        }
+        if let Some(sort_by) = self.sort_by {
+            api.sort_by(sort_by);
+        }
+        api
+    }
+}
+
+fn is_valid_date(date : &str) -> bool {
+    let parts : Vec<&str> = date.split('-').collect();
+    let lengths_ok = parts.len() == 3 && parts[0].len() == 4 && parts[1].len() == 2 && parts[2].len() == 2;
+    if !lengths_ok || !parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit())) {
+        return false;
+    }
+    let year : i32 = parts[0].parse().unwrap_or(0);
+    let month : u32 = parts[1].parse().unwrap_or(0);
+    let day : u32 = parts[2].parse().unwrap_or(0);
+    NaiveDate::from_ymd_opt(year, month, day).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_are_checked() {
+        for date in ["2022-03-01", "2020-02-29", "0001-12-31"] {
+            assert!(is_valid_date(date), "{}", date);
+        }
+        for date in ["", "2022-3-1", "22-03-01", "2022/03/01", "2022-03-01T00:00", "2022-13-01", "2022-00-10", "2022-04-31", "2021-02-29", "+022-03-01", "2022-0a-01"] {
+            assert!(!is_valid_date(date), "{}", date);
+        }
+    }
+
+    #[test]
+    fn validate_reports_the_first_invalid_date() {
+        assert_eq!(NewsQuery::default().validate(), Ok(()));
+        let query = NewsQuery { from : "2022-03-01".into(), to : "2022-03-01".into(), ..NewsQuery::default() };
+        assert_eq!(query.validate(), Ok(()));
+        let query = NewsQuery { from : "yesterday".into(), to : "2022-02-30".into(), ..NewsQuery::default() };
+        assert_eq!(query.validate(), Err(message!("invalid-date", date = "yesterday")));
+        let query = NewsQuery { to : "2022-02-30".into(), ..NewsQuery::default() };
+        assert_eq!(query.validate(), Err(message!("invalid-date", date = "2022-02-30")));
+        let query = NewsQuery { from : "2022-03-02".into(), to : "2022-03-01".into(), ..NewsQuery::default() };
+        assert_eq!(query.validate(), Err(message!("invalid-date-order")));
+    }
+
+    #[test]
+    fn searches_only_with_keywords_sources_or_domains() {
+        let query = NewsQuery { category : Some(Category::Science), language : Some(Language::Fr), from : "2022-03-01".into(), ..NewsQuery::default() };
+        assert_eq!(query.endpoint(), Endpoint::TopHeadlines);
+        assert!(!NewsQuery { keywords : "  ".into(), ..query.clone() }.is_search());
+        assert!(NewsQuery { keywords : "rust".into(), ..query.clone() }.is_search());
+        assert!(NewsQuery { sources : "bbc-news".into(), ..query.clone() }.is_search());
+        assert!(NewsQuery { domains : "bbc.co.uk".into(), ..query }.is_search());
+    }
+
+    #[test]
+    fn other_keywords_keep_the_filters() {
+        let query = NewsQuery { language : Some(Language::Fr), domains : "lemonde.fr".into(), ..NewsQuery::default() };
+        let search = query.with_keywords("  rust ");
+        assert_eq!(search, NewsQuery { keywords : "rust".into(), ..query.clone() });
+        assert_eq!(search.with_keywords(""), query);
+    }
+
+    #[test]
+    fn a_category_drops_the_search() {
+        let query = NewsQuery {
+            country : Country::Fr,
+            keywords : "rust".into(),
+            sources : "bbc-news".into(),
+            domains : "bbc.co.uk".into(),
+            sort_by : Some(SortBy::Popularity),
+            ..NewsQuery::default()
+        };
+        let headlines = query.with_category(Some(Category::Science));
+        assert_eq!(headlines, NewsQuery { country : Country::Fr, category : Some(Category::Science), sort_by : Some(SortBy::Popularity), ..NewsQuery::default() });
+        assert_eq!(headlines.endpoint(), Endpoint::TopHeadlines);
+    }
+}
@@ -0,0 +1,124 @@
+use eframe::egui::{Hyperlink, Layout, RichText, ScrollArea, Separator, Ui};
+
+use crate::{
+    extract::{Block, Span},
+    headlines::{archive_button, bookmark_button, PADDING},
+    state::{Action, AppState, Page, Reader},
+    thumbnails::Thumbnails,
+    tr
+};
+
+/// The article open in the reader, with a way back to the list and out to the browser.
+pub(crate) fn render_reader(ui : &mut Ui,state : &AppState,reader : &Reader,thumbnails : Option<&mut Thumbnails>) -> Vec<Action> {
+    let mut actions = Vec::new();
+    let saved = state.bookmarks.contains(&reader.article.url);
+    let article = &reader.article;
+    ui.horizontal(|ui|{
+        if ui.button(tr!("reader-back")).clicked() {
+            actions.push(Action::CloseReader);
+        }
+        ui.with_layout(Layout::right_to_left(), |ui|{
+            ui.add(Hyperlink::from_label_and_url(format!("{} 🌐",tr!("reader-browser")), &article.url));
+            if bookmark_button(ui, saved).clicked() {
+                actions.push(Action::ToggleBookmark(article.clone()));
+            }
+            actions.extend(archive_button(ui, state, article));
+        });
+    });
+    ui.add(Separator::default().spacing(20.));
+
+    let document = match &reader.page {
+        Page::Loading => {
+            ui.vertical_centered(|ui|{
+                ui.heading(tr!("loading"));
+            });
+            return actions;
+        }
+        Page::Failed(error) => {
+            ui.heading(&article.title);
+            ui.add_space(PADDING);
+            ui.label(&article.desc);
+            ui.add_space(PADDING);
+            ui.label(tr!("reader-failed", error = error.to_string()));
+            if !state.config.offline {
+                ui.add(Hyperlink::from_label_and_url(format!("{} 🌐",tr!("reader-failed-browser")), &article.url));
+            }
+            return actions;
+        }
+        Page::Ready(document) => document
+    };
+
+    ScrollArea::vertical().id_source(("reader", &article.url)).show(ui, |ui|{
+        let title = if document.title.is_empty() { &article.title } else { &document.title };
+        ui.heading(title);
+        if let Some(byline) = &document.byline {
+            ui.label(RichText::new(tr!("reader-byline", author = byline.as_str())).weak());
+        }
+        ui.add_space(PADDING);
+        let mut thumbnails = thumbnails;
+        if let (Some(image), Some(thumbnails)) = (&document.lead_image, thumbnails.as_deref_mut()) {
+            thumbnails.show(ui, image);
+            ui.add_space(PADDING);
+        }
+        for block in &document.blocks {
+            match block {
+                Block::Heading { level, spans } => {
+                    ui.add_space(PADDING * 2.);
+                    let text = crate::extract::plain_text(spans);
+                    if *level <= 2 {
+                        ui.heading(text);
+                    } else {
+                        ui.label(RichText::new(text).strong());
+                    }
+                }
+                Block::Paragraph(spans) => render_spans(ui, spans),
+                Block::Quote(spans) => {
+                    ui.indent("quote", |ui|{
+                        ui.horizontal(|ui|{
+                            ui.label(RichText::new("┃").weak());
+                            ui.vertical(|ui| render_spans(ui, spans));
+                        });
+                    });
+                }
+                Block::ListItem(spans) => {
+                    ui.horizontal(|ui|{
+                        ui.label("•");
+                        ui.vertical(|ui| render_spans(ui, spans));
+                    });
+                }
+                Block::Code(code) => {
+                    ui.code(code);
+                }
+                Block::Image { src, alt } => match thumbnails.as_deref_mut() {
+                    Some(thumbnails) => { thumbnails.show(ui, src).on_hover_text(alt); }
+                    None => { ui.add(Hyperlink::from_label_and_url(format!("🖼 {}",alt), src)); }
+                }
+            }
+            ui.add_space(PADDING);
+        }
+    });
+    actions
+}
+
+/// formatted runs of text flowing on as many lines as needed
+fn render_spans(ui : &mut Ui,spans : &[Span]) {
+    ui.horizontal_wrapped(|ui|{
+        ui.spacing_mut().item_spacing.x = 0.;
+        for span in spans {
+            let mut text = RichText::new(&span.text);
+            if span.emphasis {
+                text = text.italics();
+            }
+            if span.strong {
+                text = text.strong();
+            }
+            if span.code {
+                text = text.code();
+            }
+            match &span.link {
+                Some(link) => { ui.hyperlink_to(text, link); }
+                None => { ui.label(text); }
+            }
+        }
+    });
+}
@@ -0,0 +1,771 @@
+use eframe::egui::{style::Margin, Button, CollapsingHeader, ComboBox, Context, DragValue, Frame, Grid, RichText, TextEdit, Ui, Window};
+use newsApi::{Category, Country, Language};
+
+use crate::{
+    accessibility::Describe,
+    cards::{group_by_name, layout_name, order_name},
+    config::{validate_base_url, HeadlinesConfig, TextSizes, FONTS, MAX_ZOOM, MIN_ZOOM},
+    filters::MuteFilters,
+    fonts::Fonts,
+    headlines::{PADDING, RED, REFRESH_INTERVALS},
+    l10n::{binding_name, category_name, country_name, language_name, Locale, Message},
+    layout::{GroupBy, Layout},
+    sort::SortOrder,
+    profiles::validate_name,
+    shortcuts::{Binding, Shortcuts},
+    state::{Action, AppState, View},
+    theme::{self, Theme, Themes},
+    message, tr
+};
+
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub(crate) enum SettingsTab {
+    Account,
+    Feed,
+    Appearance,
+    Text,
+    Accessibility,
+    Filters,
+    Keys,
+    Storage,
+    Network
+}
+
+impl SettingsTab {
+    pub(crate) const ALL : [SettingsTab; 9] = [
+        SettingsTab::Account,
+        SettingsTab::Feed,
+        SettingsTab::Appearance,
+        SettingsTab::Text,
+        SettingsTab::Accessibility,
+        SettingsTab::Filters,
+        SettingsTab::Keys,
+        SettingsTab::Storage,
+        SettingsTab::Network
+    ];
+
+    pub(crate) fn name(&self) -> String {
+        match self {
+            SettingsTab::Account => tr!("tab-account"),
+            SettingsTab::Feed => tr!("tab-feed"),
+            SettingsTab::Appearance => tr!("tab-appearance"),
+            SettingsTab::Text => tr!("tab-text"),
+            SettingsTab::Accessibility => tr!("tab-accessibility"),
+            SettingsTab::Filters => tr!("tab-filters"),
+            SettingsTab::Keys => tr!("tab-keys"),
+            SettingsTab::Storage => tr!("tab-storage"),
+            SettingsTab::Network => tr!("tab-network")
+        }
+    }
+}
+
+/// The settings window, `Some` while it is open.
+///
+/// Most settings apply as soon as they are changed, text fields once they are
+/// confirmed since a half typed api key or proxy is of no use.
+pub(crate) struct Settings {
+    pub(crate) tab : SettingsTab,
+    /// the profile the drafts were taken from
+    pub(crate) profile : String,
+    new_profile : String,
+    api_key : String,
+    show_api_key : bool,
+    proxy : String,
+    base_url : String,
+    mute : MuteDraft,
+    /// the theme being customized, shown in the preview until it is saved
+    theme : Option<Theme>,
+    theme_error : Option<String>,
+    font_file : String,
+    /// one file per line
+    fallback_fonts : String,
+    shortcuts : Shortcuts,
+    /// the last edit, kept in the form while it fails validation
+    invalid : Option<InvalidEdit>
+}
+
+/// Settings that were edited into an invalid configuration.
+struct InvalidEdit {
+    /// the configuration it was edited from, the edit is dropped once that one changes
+    from : HeadlinesConfig,
+    config : HeadlinesConfig,
+    tab : SettingsTab,
+    error : Message
+}
+
+impl Settings {
+    pub(crate) fn new(state : &AppState,tab : SettingsTab) -> Settings {
+        let config = &state.config;
+        Settings {
+            tab,
+            profile : state.profiles.active().to_string(),
+            new_profile : String::new(),
+            api_key : config.api_key.clone(),
+            show_api_key : false,
+            proxy : config.proxy.clone(),
+            base_url : config.base_url.clone(),
+            mute : MuteDraft::new(&config.filters),
+            theme : None,
+            theme_error : None,
+            font_file : config.font_file.clone(),
+            fallback_fonts : config.fallback_fonts.join("\n"),
+            shortcuts : config.shortcuts.clone(),
+            invalid : None
+        }
+    }
+}
+
+/// The mute filters as typed, applied all at once.
+struct MuteDraft {
+    /// one regular expression per line
+    keywords : String,
+    /// comma separated
+    sources : String,
+    domains : String,
+    min_description : usize
+}
+
+impl MuteDraft {
+    fn new(filters : &MuteFilters) -> MuteDraft {
+        MuteDraft {
+            keywords : filters.keywords.join("\n"),
+            sources : filters.sources.join(", "),
+            domains : filters.domains.join(", "),
+            min_description : filters.min_description
+        }
+    }
+
+    fn filters(&self) -> MuteFilters {
+        let list = |text : &str,separator : char| text.split(separator)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+        MuteFilters {
+            min_description : self.min_description,
+            keywords : list(&self.keywords, '\n'),
+            sources : list(&self.sources, ','),
+            domains : list(&self.domains, ',')
+        }
+    }
+}
+
+/// The tabbed settings window. On first run it only closes once an api key is set.
+pub(crate) fn settings_window(ctx : &Context,state : &AppState,settings : &mut Option<Settings>,themes : &mut Themes,fonts : &Fonts) -> Vec<Action> {
+    let mut actions = Vec::new();
+    let current = match settings.as_mut() {
+        Some(current) => current,
+        None => return actions
+    };
+    let first_run = !state.api_key_initialized;
+    if current.invalid.as_ref().is_some_and(|invalid| invalid.from != state.config) {
+        current.invalid = None;
+    }
+    let mut config = current.invalid.as_ref().map_or_else(|| state.config.clone(), |invalid| invalid.config.clone());
+    let mut open = true;
+    let mut window = Window::new(tr!("settings")).id(eframe::egui::Id::new("settings")).collapsible(false).default_width(420.);
+    if !first_run {
+        window = window.open(&mut open);
+    }
+    window.show(ctx, |ui|{
+        ui.horizontal_wrapped(|ui|{
+            for tab in SettingsTab::ALL {
+                ui.selectable_value(&mut current.tab, tab, tab.name());
+            }
+        });
+        ui.separator();
+        match current.tab {
+            SettingsTab::Account => account_tab(ui, state, current, &mut actions),
+            SettingsTab::Feed => feed_tab(ui, &mut config),
+            SettingsTab::Appearance => appearance_tab(ui, current, themes, &mut config),
+            SettingsTab::Text => text_tab(ui, current, fonts, &mut config),
+            SettingsTab::Accessibility => accessibility_tab(ui, &mut config),
+            SettingsTab::Filters => filters_tab(ui, current, &mut config),
+            SettingsTab::Keys => keys_tab(ui, current, &mut config),
+            SettingsTab::Storage => storage_tab(ui, state, &mut config),
+            SettingsTab::Network => network_tab(ui, current, &mut config)
+        }
+        if let Some(invalid) = current.invalid.as_ref().filter(|invalid| invalid.tab == current.tab) {
+            ui.colored_label(RED, format!("⚠ {}",invalid.error));
+        }
+    });
+    if config == state.config {
+        current.invalid = None;
+    } else {
+        match config.validate() {
+            Ok(()) => {
+                current.invalid = None;
+                actions.push(Action::SetConfig(Box::new(config)));
+            }
+            Err(error) => {
+                // the tab of the field in error, and not the one of a later edit
+                let tab = match &current.invalid {
+                    Some(invalid) if invalid.error == error => invalid.tab,
+                    _ => {
+                        // shown from the next frame on
+                        ctx.request_repaint();
+                        current.tab
+                    }
+                };
+                current.invalid = Some(InvalidEdit { from : state.config.clone(), config, tab, error });
+            }
+        }
+    }
+    if !open {
+        *settings = None;
+    }
+    actions
+}
+
+fn account_tab(ui : &mut Ui,state : &AppState,settings : &mut Settings,actions : &mut Vec<Action>) {
+    profiles(ui, state, settings, actions);
+    ui.separator();
+    if let Some(error) = &state.key_error {
+        ui.colored_label(RED, format!("⚠ {}",error));
+        ui.label(tr!("api-key-check"));
+        ui.add_space(PADDING);
+    }
+    ui.label(tr!("api-key"));
+    ui.horizontal(|ui|{
+        let input = ui.add(TextEdit::singleline(&mut settings.api_key)
+            .password(!settings.show_api_key)
+            .hint_text(tr!("api-key-hint")));
+        let entered = input.lost_focus() && ui.input().key_pressed(eframe::egui::Key::Enter);
+        let (icon, hint) = if settings.show_api_key { ("🙈", tr!("api-key-hide")) } else { ("👁", tr!("api-key-show")) };
+        if ui.button(icon).describe(hint).clicked() {
+            settings.show_api_key = !settings.show_api_key;
+        }
+        let changed = settings.api_key.trim() != state.config.api_key;
+        let save = ui.add_enabled(changed && !settings.api_key.trim().is_empty(), Button::new(tr!("save-key")));
+        if (entered || save.clicked()) && changed && !settings.api_key.trim().is_empty() {
+            actions.push(Action::ApiKeySet(settings.api_key.clone()));
+            tracing::info!("api key set");
+        }
+    });
+    ui.add_space(PADDING);
+    ui.horizontal(|ui|{
+        ui.label(tr!("api-key-register"));
+        ui.hyperlink("https://newsapi.org");
+    });
+}
+
+/// every setting but the bookmarks, history, archive and alerts belongs to a profile
+fn profiles(ui : &mut Ui,state : &AppState,settings : &mut Settings,actions : &mut Vec<Action>) {
+    let active = state.profiles.active();
+    ui.horizontal(|ui|{
+        ui.label(tr!("profile"));
+        let mut selected = active.to_string();
+        ComboBox::from_id_source("settings_profile")
+            .selected_text(active)
+            .show_ui(ui, |ui|{
+                for name in state.profiles.names() {
+                    ui.selectable_value(&mut selected, name.to_string(), name);
+                }
+            });
+        if selected != active {
+            actions.push(Action::SwitchProfile(selected));
+        }
+        let others = state.profiles.names().count() > 1;
+        if ui.add_enabled(others, Button::new("🗑")).describe(tr!("profile-delete")).clicked() {
+            if let Some(next) = state.profiles.names().find(|name| *name != active) {
+                actions.push(Action::SwitchProfile(next.to_string()));
+                actions.push(Action::DeleteProfile(active.to_string()));
+            }
+        }
+    });
+    ui.horizontal(|ui|{
+        let input = ui.add(TextEdit::singleline(&mut settings.new_profile).hint_text(tr!("profile-new-hint")));
+        let name = settings.new_profile.trim();
+        let valid = validate_name(name).and_then(|()| match state.profiles.contains(name) {
+            true => Err(message!("profile-exists", profile = name)),
+            false => Ok(())
+        });
+        let entered = input.lost_focus() && ui.input().key_pressed(eframe::egui::Key::Enter);
+        let create = ui.add_enabled(valid.is_ok(), Button::new(tr!("profile-create")))
+            .on_hover_text(tr!("profile-create-hint"));
+        if (entered || create.clicked()) && valid.is_ok() {
+            actions.push(Action::CreateProfile(name.to_string()));
+            settings.new_profile.clear();
+        } else if let Err(e) = valid {
+            if !name.is_empty() {
+                ui.colored_label(RED, format!("⚠ {}",e));
+            }
+        }
+    });
+}
+
+fn feed_tab(ui : &mut Ui,config : &mut HeadlinesConfig) {
+    Grid::new("settings_feed").num_columns(2).spacing([10., 8.]).show(ui, |ui|{
+        let query = &mut config.query;
+        ui.label(tr!("feed-country"));
+        ComboBox::from_id_source("settings_country")
+            .selected_text(country_name(query.country))
+            .show_ui(ui, |ui|{
+                for country in Country::ALL {
+                    ui.selectable_value(&mut query.country, country, country_name(country));
+                }
+            });
+        ui.end_row();
+
+        ui.label(tr!("feed-category"));
+        let mut category = if query.is_search() { None } else { Some(query.category) };
+        ComboBox::from_id_source("settings_category")
+            .selected_text(match category {
+                None => tr!("feed-search"),
+                Some(None) => tr!("tab-top"),
+                Some(Some(c)) => category_name(c)
+            })
+            .show_ui(ui, |ui|{
+                ui.selectable_value(&mut category, Some(None), tr!("tab-top"));
+                for c in Category::ALL {
+                    ui.selectable_value(&mut category, Some(Some(c)), category_name(c));
+                }
+            });
+        if let Some(category) = category.filter(|c| query.is_search() || *c != query.category) {
+            *query = query.with_category(category);
+        }
+        ui.end_row();
+
+        ui.label(tr!("query-language"));
+        ComboBox::from_id_source("settings_language")
+            .selected_text(query.language.map_or_else(|| tr!("query-any"), language_name))
+            .show_ui(ui, |ui|{
+                ui.selectable_value(&mut query.language, None, tr!("query-any"));
+                for language in Language::ALL {
+                    ui.selectable_value(&mut query.language, Some(language), language_name(language));
+                }
+            });
+        ui.end_row();
+
+        ui.label(tr!("refresh"));
+        let minutes_label = |minutes : u32| if minutes == 0 { tr!("auto-refresh-never") } else { tr!("auto-refresh-every", minutes = minutes) };
+        ComboBox::from_id_source("settings_refresh")
+            .selected_text(minutes_label(config.refresh_minutes))
+            .show_ui(ui, |ui|{
+                for minutes in REFRESH_INTERVALS {
+                    ui.selectable_value(&mut config.refresh_minutes, minutes, minutes_label(minutes));
+                }
+            });
+        ui.end_row();
+
+        ui.label("");
+        ui.checkbox(&mut config.auto_archive, tr!("saved-auto-archive"));
+        ui.end_row();
+
+        ui.label("");
+        ui.checkbox(&mut config.offline, tr!("feed-offline"));
+        ui.end_row();
+    });
+    ui.add_space(PADDING);
+    ui.small(tr!("feed-note"));
+}
+
+fn appearance_tab(ui : &mut Ui,settings : &mut Settings,themes : &mut Themes,config : &mut HeadlinesConfig) {
+    Grid::new("settings_appearance").num_columns(2).spacing([10., 8.]).show(ui, |ui|{
+        ui.label(tr!("language"));
+        let mut locale = config.locale.clone();
+        let system = tr!("language-system", language = Locale::system().name());
+        ComboBox::from_id_source("settings_locale")
+            .selected_text(Locale::from_tag(&locale).map_or(system.clone(), |l| l.name().to_string()))
+            .show_ui(ui, |ui|{
+                ui.selectable_value(&mut locale, String::new(), system);
+                for l in Locale::ALL {
+                    ui.selectable_value(&mut locale, l.code().to_string(), l.name());
+                }
+            });
+        if locale != config.locale {
+            // a feed left to the defaults of the previous language follows the new one
+            let previous = Locale::from_setting(&config.locale).news_defaults();
+            let (language, country) = Locale::from_setting(&locale).news_defaults();
+            if config.query.language.is_none_or(|l| l == previous.0) && config.query.country == previous.1 {
+                config.query.language = Some(language);
+                config.query.country = country;
+            }
+            config.locale = locale;
+        }
+        ui.end_row();
+
+        ui.label(tr!("theme"));
+        let mut selected = themes.get(&config.theme).name.clone();
+        ComboBox::from_id_source("settings_theme")
+            .selected_text(selected.as_str())
+            .show_ui(ui, |ui|{
+                for name in themes.names() {
+                    ui.selectable_value(&mut selected, name.to_string(), name);
+                }
+            });
+        if selected != themes.get(&config.theme).name {
+            config.theme = selected;
+            config.follow_system_theme = false;
+        }
+        ui.end_row();
+
+        ui.label("");
+        ui.checkbox(&mut config.follow_system_theme, tr!("theme-follow-system"))
+            .on_hover_text(tr!("theme-follow-system-hint"));
+        ui.end_row();
+
+        ui.label("");
+        let mut show_images = !config.hide_images;
+        if ui.checkbox(&mut show_images, tr!("show-thumbnails")).changed() {
+            config.hide_images = !show_images;
+        }
+        ui.end_row();
+
+        for (view, label) in [(View::Feed, tr!("layout-feed")), (View::Saved, tr!("layout-saved")), (View::Alerts, tr!("layout-alerts"))] {
+            ui.label(label);
+            let current = config.layouts.get_mut(view);
+            ui.horizontal(|ui|{
+                ComboBox::from_id_source(("settings_layout", view))
+                    .selected_text(layout_name(current.layout))
+                    .show_ui(ui, |ui|{
+                        for layout in Layout::ALL {
+                            ui.selectable_value(&mut current.layout, layout, layout_name(layout));
+                        }
+                    });
+                ComboBox::from_id_source(("settings_group_by", view))
+                    .selected_text(tr!("group-by-value", group = group_by_name(current.group_by)))
+                    .show_ui(ui, |ui|{
+                        for group_by in GroupBy::ALL {
+                            ui.selectable_value(&mut current.group_by, group_by, group_by_name(group_by));
+                        }
+                    });
+                ComboBox::from_id_source(("settings_sort", view))
+                    .selected_text(tr!("order-value", order = order_name(current.sort)))
+                    .show_ui(ui, |ui|{
+                        for order in SortOrder::ALL {
+                            ui.selectable_value(&mut current.sort, order, order_name(order));
+                        }
+                    });
+            });
+            ui.end_row();
+        }
+    });
+    ui.add_space(PADDING);
+    let preview = settings.theme.clone().unwrap_or_else(|| themes.get(&config.theme).clone());
+    theme_preview(ui, &preview);
+    ui.add_space(PADDING);
+
+    CollapsingHeader::new(tr!("theme-customize")).id_source("settings_customize_theme").show(ui, |ui|{
+        let draft = settings.theme.get_or_insert_with(|| Theme {
+            name : format!("{}-custom",preview.name.replace(' ', "-")),
+            ..preview.clone()
+        });
+        Grid::new("settings_theme_colours").num_columns(2).spacing([10., 6.]).show(ui, |ui|{
+            for (label, colour) in [
+                (tr!("theme-background"), &mut draft.background),
+                (tr!("theme-card"), &mut draft.card),
+                (tr!("theme-title"), &mut draft.title),
+                (tr!("theme-description"), &mut draft.description),
+                (tr!("theme-links"), &mut draft.hyperlink)
+            ] {
+                ui.label(label);
+                ui.color_edit_button_srgb(colour);
+                ui.end_row();
+            }
+            ui.label(tr!("theme-spacing"));
+            ui.add(DragValue::new(&mut draft.spacing).clamp_range(0.0..=24.0).speed(0.5));
+            ui.end_row();
+            ui.label(tr!("theme-rounding"));
+            ui.add(DragValue::new(&mut draft.rounding).clamp_range(0.0..=24.0).speed(0.5));
+            ui.end_row();
+            ui.label(tr!("theme-outline"));
+            ui.add(DragValue::new(&mut draft.outline).clamp_range(0.0..=4.0).speed(0.1));
+            ui.end_row();
+            ui.label("");
+            ui.checkbox(&mut draft.dark, tr!("theme-dark-widgets"));
+            ui.end_row();
+            ui.label(tr!("theme-name"));
+            ui.text_edit_singleline(&mut draft.name);
+            ui.end_row();
+        });
+        let valid = theme::validate_name(&draft.name).and_then(|()| match themes.is_builtin(&draft.name) {
+            true => Err(message!("theme-builtin", theme = draft.name.as_str())),
+            false => Ok(())
+        });
+        let (save, reset) = ui.horizontal(|ui|{
+            let save = ui.add_enabled(valid.is_ok(), Button::new(tr!("theme-save"))).clicked();
+            let reset = ui.button(tr!("reset")).on_hover_text(tr!("theme-reset-hint")).clicked();
+            (save, reset)
+        }).inner;
+        let saved = save.then(|| draft.clone());
+        if let Some(e) = valid.err().map(|e| e.to_string()).or_else(|| settings.theme_error.clone()) {
+            ui.colored_label(RED, format!("⚠ {}",e));
+        }
+        if let Some(theme) = saved {
+            let name = theme.name.clone();
+            match themes.save(theme) {
+                Ok(()) => {
+                    config.theme = name;
+                    config.follow_system_theme = false;
+                    settings.theme = None;
+                    settings.theme_error = None;
+                }
+                Err(e) => settings.theme_error = Some(e)
+            }
+        } else if reset {
+            settings.theme = None;
+        }
+    });
+    ui.add_space(PADDING);
+    ui.horizontal(|ui|{
+        if ui.button(tr!("theme-reload")).clicked() {
+            *themes = Themes::load();
+        }
+        ui.small(if cfg!(target_arch = "wasm32") {
+            tr!("theme-storage-web")
+        } else {
+            tr!("theme-storage")
+        });
+    });
+    for error in &themes.errors {
+        ui.colored_label(RED, format!("⚠ {}",error));
+    }
+}
+
+fn text_tab(ui : &mut Ui,settings : &mut Settings,fonts : &Fonts,config : &mut HeadlinesConfig) {
+    Grid::new("settings_text").num_columns(2).spacing([10., 8.]).show(ui, |ui|{
+        ui.label(tr!("font"));
+        ComboBox::from_id_source("settings_font")
+            .selected_text(config.font.as_str())
+            .show_ui(ui, |ui|{
+                for font in FONTS {
+                    ui.selectable_value(&mut config.font, font.to_string(), font);
+                }
+            });
+        ui.end_row();
+
+        ui.label(tr!("font-file"));
+        ui.horizontal(|ui|{
+            let input = ui.add_enabled(!cfg!(target_arch = "wasm32"), TextEdit::singleline(&mut settings.font_file).hint_text("/path/to/font.ttf"))
+                .on_hover_text(tr!("font-file-hint"))
+                .on_disabled_hover_text(tr!("font-file-web"));
+            let file = settings.font_file.trim();
+            let changed = file != config.font_file;
+            let entered = input.lost_focus() && ui.input().key_pressed(eframe::egui::Key::Enter);
+            if (ui.add_enabled(changed, Button::new(tr!("apply"))).clicked() || entered) && changed {
+                config.font_file = file.to_string();
+            }
+        });
+        ui.end_row();
+
+        let sizes = &mut config.text_sizes;
+        for (label, size) in [
+            (tr!("size-heading"), &mut sizes.heading),
+            (tr!("size-body"), &mut sizes.body),
+            (tr!("size-button"), &mut sizes.button),
+            (tr!("size-small"), &mut sizes.small),
+            (tr!("size-monospace"), &mut sizes.monospace)
+        ] {
+            ui.label(label);
+            ui.add(DragValue::new(size).clamp_range(6.0..=48.0).speed(0.5).suffix(" pt"));
+            ui.end_row();
+        }
+
+        ui.label(tr!("zoom"));
+        let mut percent = config.zoom * 100.;
+        let zoom = ui.add(DragValue::new(&mut percent).clamp_range(MIN_ZOOM * 100. ..=MAX_ZOOM * 100.).speed(1.).suffix(" %"))
+            .on_hover_text(tr!("zoom-hint"));
+        if zoom.changed() {
+            config.zoom = (percent / 100.).clamp(MIN_ZOOM, MAX_ZOOM);
+        }
+        ui.end_row();
+    });
+    if ui.add_enabled(config.text_sizes != TextSizes::default(), Button::new(tr!("size-defaults"))).clicked() {
+        config.text_sizes = TextSizes::default();
+    }
+    ui.separator();
+    ui.label(tr!("fallback-fonts"));
+    ui.add(TextEdit::multiline(&mut settings.fallback_fonts)
+        .hint_text(tr!("fallback-fonts-hint"))
+        .desired_rows(2)
+        .interactive(!cfg!(target_arch = "wasm32")));
+    let fallbacks : Vec<String> = settings.fallback_fonts.lines().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string).collect();
+    if ui.add_enabled(fallbacks != config.fallback_fonts, Button::new(tr!("apply"))).clicked() {
+        config.fallback_fonts = fallbacks;
+    }
+    ui.add_space(PADDING);
+    if fonts.fallbacks.is_empty() {
+        ui.small(tr!("fallback-fonts-none"));
+    } else {
+        ui.small(tr!("fallback-fonts-found", fonts = fonts.fallbacks.join(", ")));
+    }
+    for error in &fonts.errors {
+        ui.colored_label(RED, format!("⚠ {}",error));
+    }
+}
+
+fn accessibility_tab(ui : &mut Ui,config : &mut HeadlinesConfig) {
+    ui.checkbox(&mut config.high_contrast, tr!("high-contrast"))
+        .on_hover_text(tr!("high-contrast-hint"));
+    ui.checkbox(&mut config.reduced_motion, tr!("reduce-motion"))
+        .on_hover_text(tr!("reduce-motion-hint"));
+    ui.checkbox(&mut config.screen_reader, tr!("screen-reader"))
+        .on_hover_text(tr!("screen-reader-hint"));
+    ui.add_space(PADDING);
+    ui.small(tr!("accessibility-keys"));
+    ui.small(tr!("accessibility-shortcuts", help = config.shortcuts.help.as_str(), palette = config.shortcuts.palette.as_str()));
+    ui.small(tr!("accessibility-text"));
+}
+
+/// a made up article, drawn with `theme` whatever the one in use
+fn theme_preview(ui : &mut Ui,theme : &Theme) {
+    ui.scope(|ui|{
+        theme.apply(ui.style_mut());
+        Frame::none().fill(theme.background()).rounding(theme.rounding).margin(Margin::same(theme.spacing)).show(ui, |ui|{
+            theme.card(ui, false, |ui|{
+                ui.colored_label(theme.title(), format!("🔹 {}",tr!("theme-preview-title")));
+                ui.add_space(PADDING);
+                ui.label(tr!("theme-preview-text"));
+                ui.add_space(PADDING);
+                ui.horizontal(|ui|{
+                    let _ = ui.button(tr!("theme-preview-button"));
+                    ui.label(RichText::new(tr!("read-more")).color(ui.visuals().hyperlink_color));
+                });
+            });
+        });
+    });
+}
+
+fn filters_tab(ui : &mut Ui,settings : &mut Settings,config : &mut HeadlinesConfig) {
+    let draft = &mut settings.mute;
+    Grid::new("settings_filters").num_columns(2).spacing([10., 8.]).show(ui, |ui|{
+        ui.label(tr!("query-keywords"));
+        ui.add(TextEdit::multiline(&mut draft.keywords)
+            .hint_text(format!("{}\ncelebrit(y|ies)\n\\bhoroscope\\b",tr!("mute-keywords-hint")))
+            .desired_rows(4));
+        ui.end_row();
+
+        ui.label(tr!("query-sources"));
+        ui.add(TextEdit::singleline(&mut draft.sources).hint_text("Daily Mail, TMZ"));
+        ui.end_row();
+
+        ui.label(tr!("query-domains"));
+        ui.add(TextEdit::singleline(&mut draft.domains).hint_text("example.com, ads.example.org"));
+        ui.end_row();
+
+        ui.label(tr!("mute-shortest"));
+        ui.add(DragValue::new(&mut draft.min_description).clamp_range(0..=500).suffix(format!(" {}",tr!("unit-characters"))))
+            .on_hover_text(tr!("mute-shortest-hint"));
+        ui.end_row();
+    });
+    ui.add_space(PADDING);
+    ui.small(tr!("mute-note"));
+    ui.add_space(PADDING);
+    let filters = draft.filters();
+    let validation = filters.validate();
+    if let Err(e) = &validation {
+        ui.colored_label(RED, format!("⚠ {}",e));
+    }
+    ui.horizontal(|ui|{
+        if ui.add_enabled(validation.is_ok() && filters != config.filters, Button::new(tr!("apply"))).clicked() {
+            config.filters = filters;
+        }
+        if ui.button(tr!("clear")).clicked() {
+            *draft = MuteDraft::new(&MuteFilters::default());
+        }
+    });
+}
+
+fn keys_tab(ui : &mut Ui,settings : &mut Settings,config : &mut HeadlinesConfig) {
+    let draft = &mut settings.shortcuts;
+    Grid::new("settings_keys").num_columns(2).spacing([10., 8.]).show(ui, |ui|{
+        for binding in Binding::ALL {
+            ui.label(binding_name(binding));
+            ui.add(TextEdit::singleline(draft.get_mut(binding)).desired_width(120.));
+            ui.end_row();
+        }
+    });
+    ui.add_space(PADDING);
+    ui.small(tr!("keys-note"));
+    ui.add_space(PADDING);
+    let validation = draft.validate();
+    if let Err(e) = &validation {
+        ui.colored_label(RED, format!("⚠ {}",e));
+    }
+    ui.horizontal(|ui|{
+        if ui.add_enabled(validation.is_ok() && *draft != config.shortcuts, Button::new(tr!("apply"))).clicked() {
+            config.shortcuts = draft.clone();
+        }
+        if ui.button(tr!("defaults")).clicked() {
+            *draft = Shortcuts::default();
+        }
+    });
+}
+
+fn storage_tab(ui : &mut Ui,state : &AppState,config : &mut HeadlinesConfig) {
+    Grid::new("settings_storage").num_columns(2).spacing([10., 8.]).show(ui, |ui|{
+        ui.label(tr!("thumbnail-cache"));
+        ui.horizontal(|ui|{
+            ui.add_enabled(!cfg!(target_arch = "wasm32"), DragValue::new(&mut config.thumbnail_cache_mb).clamp_range(1..=1024).suffix(" MB"))
+                .on_disabled_hover_text(tr!("thumbnail-cache-web"));
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui.button(tr!("clear")).clicked() {
+                crate::thumbnails::clear_cache();
+            }
+        });
+        ui.end_row();
+
+        ui.label(tr!("history"));
+        ui.add(DragValue::new(&mut config.history_days).clamp_range(1..=365).suffix(format!(" {}",tr!("unit-days"))))
+            .on_hover_text(tr!("history-hint"));
+        ui.end_row();
+
+        ui.label(tr!("archive-size"));
+        ui.label(tr!("articles", count = state.archive.len()));
+        ui.end_row();
+
+        ui.label(tr!("saved"));
+        ui.label(tr!("articles", count = state.bookmarks.len()));
+        ui.end_row();
+    });
+}
+
+fn network_tab(ui : &mut Ui,settings : &mut Settings,config : &mut HeadlinesConfig) {
+    Grid::new("settings_server").num_columns(2).spacing([10., 8.]).show(ui, |ui|{
+        ui.label(tr!("server"));
+        ui.horizontal(|ui|{
+            let input = ui.add(TextEdit::singleline(&mut settings.base_url).hint_text("https://newsapi.org/v2"));
+            let valid = validate_base_url(&settings.base_url);
+            let changed = settings.base_url.trim() != config.base_url;
+            let apply = ui.add_enabled(changed && valid.is_ok(), Button::new(tr!("apply")));
+            let entered = input.lost_focus() && ui.input().key_pressed(eframe::egui::Key::Enter);
+            if (apply.clicked() || entered) && changed && valid.is_ok() {
+                config.base_url = settings.base_url.trim().to_string();
+            }
+        });
+        ui.end_row();
+    });
+    if let Err(e) = validate_base_url(&settings.base_url) {
+        ui.colored_label(RED, format!("⚠ {}",e));
+    }
+    ui.small(tr!("server-note"));
+    ui.separator();
+    if cfg!(target_arch = "wasm32") {
+        ui.label(tr!("network-web"));
+        return;
+    }
+    Grid::new("settings_network").num_columns(2).spacing([10., 8.]).show(ui, |ui|{
+        ui.label(tr!("timeout"));
+        ui.add(DragValue::new(&mut config.timeout_secs).clamp_range(1..=300).suffix(" s"));
+        ui.end_row();
+
+        ui.label(tr!("proxy"));
+        ui.horizontal(|ui|{
+            let input = ui.add(TextEdit::singleline(&mut settings.proxy).hint_text("http://host:8080"));
+            let valid = crate::net::validate_proxy(&settings.proxy);
+            let changed = settings.proxy.trim() != config.proxy;
+            let apply = ui.add_enabled(changed && valid.is_ok(), Button::new(tr!("apply")));
+            let entered = input.lost_focus() && ui.input().key_pressed(eframe::egui::Key::Enter);
+            if (apply.clicked() || entered) && changed && valid.is_ok() {
+                config.proxy = settings.proxy.trim().to_string();
+            }
+        });
+        ui.end_row();
+    });
+    if let Err(e) = crate::net::validate_proxy(&settings.proxy) {
+        ui.colored_label(RED, format!("⚠ {}",e));
+    }
+    ui.add_space(PADDING);
+    ui.small(tr!("proxy-note"));
+}
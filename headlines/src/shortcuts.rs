@@ -0,0 +1,233 @@
+use serde::{Serialize,Deserialize};
+
+use crate::{l10n::{binding_name, Message}, message};
+
+/// Keys without a character of their own, named as in the config.
+pub const NAMED_KEYS : [&str; 15] = ["Enter", "Escape", "Tab", "Space", "Backspace", "Delete", "Insert", "Home", "End", "PageUp", "PageDown", "Up", "Down", "Left", "Right"];
+
+/// What a shortcut does.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum Binding {
+    Next,
+    Previous,
+    Open,
+    OpenExternal,
+    Save,
+    Refresh,
+    Search,
+    Palette,
+    Help
+}
+
+impl Binding {
+    pub const ALL : [Binding; 9] = [
+        Binding::Next,
+        Binding::Previous,
+        Binding::Open,
+        Binding::OpenExternal,
+        Binding::Save,
+        Binding::Refresh,
+        Binding::Search,
+        Binding::Palette,
+        Binding::Help
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Binding::Next => "Next article",
+            Binding::Previous => "Previous article",
+            Binding::Open => "Open in the reader",
+            Binding::OpenExternal => "Open in the browser",
+            Binding::Save => "Save for later",
+            Binding::Refresh => "Refresh",
+            Binding::Search => "Search",
+            Binding::Palette => "Command palette",
+            Binding::Help => "Keyboard shortcuts"
+        }
+    }
+}
+
+/// The keyboard shortcuts of the window, like `j`, `/`, `Enter` or `Ctrl+P`.
+///
+/// Single characters are matched as typed, `J` being shift and j. With `Ctrl` or
+/// `Alt` they must be a letter or a digit. `Ctrl` stands for ⌘ on macOS.
+#[derive(Clone,Debug,PartialEq,Serialize,Deserialize)]
+#[serde(default)]
+pub struct Shortcuts {
+    pub next : String,
+    pub previous : String,
+    /// the selected article in the reader
+    pub open : String,
+    /// the selected article in the browser
+    pub open_external : String,
+    pub save : String,
+    pub refresh : String,
+    pub search : String,
+    pub palette : String,
+    pub help : String
+}
+
+impl Default for Shortcuts {
+    fn default() -> Self {
+        Shortcuts {
+            next : "j".to_string(),
+            previous : "k".to_string(),
+            open : "Enter".to_string(),
+            open_external : "o".to_string(),
+            save : "s".to_string(),
+            refresh : "r".to_string(),
+            search : "/".to_string(),
+            palette : "Ctrl+P".to_string(),
+            help : "?".to_string()
+        }
+    }
+}
+
+impl Shortcuts {
+    pub fn get(&self,binding : Binding) -> &String {
+        match binding {
+            Binding::Next => &self.next,
+            Binding::Previous => &self.previous,
+            Binding::Open => &self.open,
+            Binding::OpenExternal => &self.open_external,
+            Binding::Save => &self.save,
+            Binding::Refresh => &self.refresh,
+            Binding::Search => &self.search,
+            Binding::Palette => &self.palette,
+            Binding::Help => &self.help
+        }
+    }
+
+    pub fn get_mut(&mut self,binding : Binding) -> &mut String {
+        match binding {
+            Binding::Next => &mut self.next,
+            Binding::Previous => &mut self.previous,
+            Binding::Open => &mut self.open,
+            Binding::OpenExternal => &mut self.open_external,
+            Binding::Save => &mut self.save,
+            Binding::Refresh => &mut self.refresh,
+            Binding::Search => &mut self.search,
+            Binding::Palette => &mut self.palette,
+            Binding::Help => &mut self.help
+        }
+    }
+
+    pub fn validate(&self) -> Result<(),Message> {
+        let mut combos : Vec<KeyCombo> = Vec::new();
+        for binding in Binding::ALL {
+            let shortcut = self.get(binding);
+            let combo = KeyCombo::parse(shortcut).map_err(|e| message!("invalid-binding", binding = binding_name(binding), error = e.to_string()))?;
+            if combos.contains(&combo) {
+                return Err(message!("invalid-bound-twice", shortcut = shortcut));
+            }
+            combos.push(combo);
+        }
+        Ok(())
+    }
+}
+
+/// A key and the modifiers held with it.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct KeyCombo {
+    pub ctrl : bool,
+    pub alt : bool,
+    /// only for named keys, characters tell it themselves
+    pub shift : bool,
+    /// one character or one of [`NAMED_KEYS`]
+    pub key : String
+}
+
+impl KeyCombo {
+    pub fn parse(text : &str) -> Result<KeyCombo,Message> {
+        let text = text.trim();
+        let (modifiers, key) = match text {
+            "+" => ("", text),
+            _ => text.rsplit_once('+').unwrap_or(("", text))
+        };
+        let mut combo = KeyCombo { ctrl : false, alt : false, shift : false, key : String::new() };
+        for modifier in modifiers.split('+').map(str::trim).filter(|m| !m.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "cmd" => combo.ctrl = true,
+                "alt" => combo.alt = true,
+                "shift" => combo.shift = true,
+                _ => return Err(message!("invalid-modifier", modifier = modifier))
+            }
+        }
+        let key = key.trim();
+        let named = NAMED_KEYS.iter().find(|name| name.eq_ignore_ascii_case(key));
+        let mut chars = key.chars();
+        combo.key = match (named, chars.next(), chars.next()) {
+            (Some(name), _, _) => name.to_string(),
+            (None, Some(c), None) if !c.is_whitespace() => {
+                if combo.shift {
+                    return Err(message!("invalid-shift"));
+                }
+                if (combo.ctrl || combo.alt) && !c.is_ascii_alphanumeric() {
+                    return Err(message!("invalid-modified-key"));
+                }
+                // the modifiers hide the case of letters
+                if combo.ctrl || combo.alt { c.to_ascii_uppercase().to_string() } else { c.to_string() }
+            }
+            _ => return Err(message!("invalid-key", keys = NAMED_KEYS.join(", "), key = text))
+        };
+        Ok(combo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combo(ctrl : bool,alt : bool,shift : bool,key : &str) -> KeyCombo {
+        KeyCombo { ctrl, alt, shift, key : key.to_string() }
+    }
+
+    fn error(text : &str) -> &'static str {
+        KeyCombo::parse(text).unwrap_err().id
+    }
+
+    #[test]
+    fn parses_keys_and_modifiers() {
+        assert_eq!(KeyCombo::parse(" j ").unwrap(), combo(false, false, false, "j"));
+        assert_eq!(KeyCombo::parse("J").unwrap(), combo(false, false, false, "J"));
+        assert_eq!(KeyCombo::parse("+").unwrap(), combo(false, false, false, "+"));
+        assert_eq!(KeyCombo::parse("ctrl + p").unwrap(), combo(true, false, false, "P"));
+        assert_eq!(KeyCombo::parse("Cmd+Alt+1").unwrap(), combo(true, true, false, "1"));
+        assert_eq!(KeyCombo::parse("pagedown").unwrap(), combo(false, false, false, "PageDown"));
+        assert_eq!(KeyCombo::parse("Shift+Tab").unwrap(), combo(false, false, true, "Tab"));
+    }
+
+    #[test]
+    fn rejects_invalid_bindings() {
+        assert_eq!(error(""), "invalid-key");
+        assert_eq!(error("jk"), "invalid-key");
+        assert_eq!(error("Ctrl+"), "invalid-key");
+        assert_eq!(error("Super+j"), "invalid-modifier");
+        assert_eq!(error("Shift+j"), "invalid-shift");
+        assert_eq!(error("Ctrl+/"), "invalid-modified-key");
+    }
+
+    #[test]
+    fn the_defaults_are_valid() {
+        assert_eq!(Shortcuts::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn a_combo_bound_twice_is_a_conflict() {
+        let shortcuts = Shortcuts { search : "R".to_string(), refresh : "r".to_string(), ..Shortcuts::default() };
+        assert_eq!(shortcuts.validate(), Ok(()));
+
+        let shortcuts = Shortcuts { palette : "ctrl+p".to_string(), help : "Cmd+P".to_string(), ..Shortcuts::default() };
+        assert_eq!(shortcuts.validate(), Err(message!("invalid-bound-twice", shortcut = "Cmd+P")));
+
+        let shortcuts = Shortcuts { open : "enter".to_string(), save : "Enter".to_string(), ..Shortcuts::default() };
+        assert_eq!(shortcuts.validate().unwrap_err().id, "invalid-bound-twice");
+    }
+
+    #[test]
+    fn the_invalid_binding_is_named() {
+        let mut shortcuts = Shortcuts::default();
+        *shortcuts.get_mut(Binding::Save) = "Shift+s".to_string();
+        assert_eq!(shortcuts.validate(), Err(message!("invalid-binding", binding = binding_name(Binding::Save), error = message!("invalid-shift").to_string())));
+    }
+}
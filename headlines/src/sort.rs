@@ -0,0 +1,229 @@
+use std::cmp::Reverse;
+
+use regex::{Regex, RegexBuilder};
+use serde::{Serialize,Deserialize};
+
+use crate::news::NewsCardData;
+
+/// The order the loaded articles are listed in.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Serialize,Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// as newsapi.org returned them, or as they were saved and matched
+    #[default]
+    Default,
+    Newest,
+    Oldest,
+    /// by publication name
+    Source,
+    Title,
+    /// the articles with the most words of the query and the quick filter first
+    Relevance
+}
+
+impl SortOrder {
+    pub const ALL : [SortOrder; 6] = [SortOrder::Default, SortOrder::Newest, SortOrder::Oldest, SortOrder::Source, SortOrder::Title, SortOrder::Relevance];
+}
+
+/// Narrows the loaded articles to those with a text in their title, description or source,
+/// without asking newsapi.org again.
+#[derive(Clone,Debug,Default)]
+pub struct QuickFilter {
+    text : String,
+    regex : bool,
+    /// `None` for an empty text or an invalid expression
+    compiled : Option<Regex>,
+    error : Option<String>
+}
+
+impl QuickFilter {
+    /// `text` as a substring, or as a regular expression when `regex`, ignoring case either way
+    pub fn new(text : &str,regex : bool) -> QuickFilter {
+        let pattern = if regex { text.to_string() } else { regex::escape(text.trim()) };
+        let (compiled, error) = if text.trim().is_empty() {
+            (None, None)
+        } else {
+            match RegexBuilder::new(&pattern).case_insensitive(true).build() {
+                Ok(compiled) => (Some(compiled), None),
+                Err(e) => (None, Some(e.to_string()))
+            }
+        };
+        QuickFilter { text : text.to_string(), regex, compiled, error }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_regex(&self) -> bool {
+        self.regex
+    }
+
+    /// why the regular expression does not compile
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// whether it narrows anything, an invalid expression lets every article through
+    pub fn is_active(&self) -> bool {
+        self.compiled.is_some()
+    }
+
+    pub fn matches(&self,article : &NewsCardData) -> bool {
+        self.compiled.as_ref().is_none_or(|re| re.is_match(&article.title) || re.is_match(&article.desc) || re.is_match(&article.source))
+    }
+
+    /// the words of the text for [`SortOrder::Relevance`], none for a regular expression
+    pub fn words(&self) -> Vec<String> {
+        if self.regex { Vec::new() } else { words(&self.text) }
+    }
+}
+
+/// the words of `keywords` in lower case, without the operators of the newsapi.org search
+pub fn words(keywords : &str) -> Vec<String> {
+    keywords.split_whitespace()
+        .filter(|word| !matches!(*word, "AND" | "OR" | "NOT"))
+        .map(|word| word.trim_matches(|c : char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// how often `words` appear in the article, twice as much in its title
+fn relevance(article : &NewsCardData,words : &[String]) -> usize {
+    let (title, desc, source) = (article.title.to_lowercase(), article.desc.to_lowercase(), article.source.to_lowercase());
+    words.iter()
+        .map(|word| 2 * title.matches(word.as_str()).count() + desc.matches(word.as_str()).count() + source.matches(word.as_str()).count())
+        .sum()
+}
+
+/// the articles `filter` lets through in `order`, `words` telling their relevance, each with
+/// the index it came with. Ties keep the order the articles came in.
+pub fn arrange<'a>(articles : impl IntoIterator<Item = (usize,&'a NewsCardData)>,order : SortOrder,filter : &QuickFilter,words : &[String]) -> Vec<(usize,&'a NewsCardData)> {
+    let mut articles : Vec<(usize,&NewsCardData)> = articles.into_iter().filter(|(_, a)| filter.matches(a)).collect();
+    // the unknown dates and sources last
+    match order {
+        SortOrder::Default => {}
+        SortOrder::Newest => articles.sort_by_key(|(_, a)| (a.published_at.is_none(), Reverse(a.published_at))),
+        SortOrder::Oldest => articles.sort_by_key(|(_, a)| (a.published_at.is_none(), a.published_at)),
+        SortOrder::Source => articles.sort_by_cached_key(|(_, a)| (a.source.is_empty(), a.source.to_lowercase())),
+        SortOrder::Title => articles.sort_by_cached_key(|(_, a)| a.title.to_lowercase()),
+        SortOrder::Relevance => articles.sort_by_cached_key(|(_, a)| Reverse(relevance(a, words)))
+    }
+    articles
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn article(title : &str,desc : &str,source : &str,day : Option<u32>) -> NewsCardData {
+        NewsCardData {
+            title : title.to_string(),
+            desc : desc.to_string(),
+            url : format!("https://example.com/{}",title),
+            image : None,
+            source : source.to_string(),
+            published_at : day.map(|day| Utc.with_ymd_and_hms(2022, 3, day, 12, 0, 0).unwrap()),
+            category : None
+        }
+    }
+
+    fn titles(articles : &[NewsCardData],order : SortOrder,filter : &QuickFilter,words : &[String]) -> Vec<String> {
+        arrange(articles.iter().enumerate(), order, filter, words).into_iter().map(|(_, a)| a.title.clone()).collect()
+    }
+
+    fn articles() -> Vec<NewsCardData> {
+        vec![
+            article("b", "", "Wire", Some(2)),
+            article("C", "", "", None),
+            article("a", "", "daily", Some(3)),
+            article("d", "", "Daily", Some(1)),
+            article("e", "", "", Some(3))
+        ]
+    }
+
+    #[test]
+    fn sort_orders() {
+        let articles = articles();
+        let all = QuickFilter::default();
+        assert_eq!(titles(&articles, SortOrder::Default, &all, &[]), ["b", "C", "a", "d", "e"]);
+        assert_eq!(titles(&articles, SortOrder::Newest, &all, &[]), ["a", "e", "b", "d", "C"]);
+        assert_eq!(titles(&articles, SortOrder::Oldest, &all, &[]), ["d", "b", "a", "e", "C"]);
+        assert_eq!(titles(&articles, SortOrder::Source, &all, &[]), ["a", "d", "b", "C", "e"]);
+        assert_eq!(titles(&articles, SortOrder::Title, &all, &[]), ["a", "b", "C", "d", "e"]);
+    }
+
+    #[test]
+    fn articles_keep_their_index() {
+        let articles = articles();
+        let indices : Vec<usize> = arrange(articles.iter().enumerate(), SortOrder::Title, &QuickFilter::new("^(a|b|c)$", true), &[])
+            .into_iter()
+            .map(|(i, a)| {
+                assert_eq!(articles[i], *a);
+                i
+            })
+            .collect();
+        assert_eq!(indices, [2, 0, 1]);
+    }
+
+    #[test]
+    fn ties_keep_the_order_the_articles_came_in() {
+        let articles = vec![article("x", "", "", Some(1)), article("y", "", "", Some(1)), article("z", "", "", Some(1))];
+        let all = QuickFilter::default();
+        for order in SortOrder::ALL {
+            assert_eq!(titles(&articles, order, &all, &[]), ["x", "y", "z"], "{:?}", order);
+        }
+    }
+
+    #[test]
+    fn relevance_counts_the_title_twice() {
+        let articles = vec![
+            article("nothing", "", "", None),
+            article("rust in the description", "rust rust rust", "", None),
+            article("rust rust", "", "", None),
+            article("rust", "rust", "", None)
+        ];
+        let words = words("Rust AND \"rust\"");
+        assert_eq!(words, ["rust", "rust"]);
+        assert_eq!(titles(&articles, SortOrder::Relevance, &QuickFilter::default(), &words), ["rust in the description", "rust rust", "rust", "nothing"]);
+    }
+
+    #[test]
+    fn substrings_are_matched_literally() {
+        let articles = vec![article("1+1", "", "", None), article("11", "", "", None), article("x", "", "The Wire", None)];
+        let filter = QuickFilter::new(" 1+1 ", false);
+        assert!(filter.is_active());
+        assert_eq!(titles(&articles, SortOrder::Default, &filter, &[]), ["1+1"]);
+        assert_eq!(filter.words(), ["1+1"]);
+        assert_eq!(titles(&articles, SortOrder::Default, &QuickFilter::new("wIRE", false), &[]), ["x"]);
+    }
+
+    #[test]
+    fn regexes_ignore_case() {
+        let articles = vec![article("1+1", "", "", None), article("11", "", "", None), article("x", "ONE", "", None)];
+        let filter = QuickFilter::new("^1+1$|one", true);
+        assert!(filter.is_active() && filter.is_regex());
+        assert_eq!(titles(&articles, SortOrder::Default, &filter, &[]), ["11", "x"]);
+        assert!(filter.words().is_empty());
+    }
+
+    #[test]
+    fn invalid_regexes_let_every_article_through() {
+        let articles = articles();
+        let filter = QuickFilter::new("(unclosed", true);
+        assert!(filter.error().is_some());
+        assert!(!filter.is_active());
+        assert_eq!(titles(&articles, SortOrder::Default, &filter, &[]).len(), articles.len());
+        assert!(QuickFilter::new("(unclosed", false).error().is_none());
+    }
+
+    #[test]
+    fn blank_filters_are_inactive() {
+        for filter in [QuickFilter::new("", false), QuickFilter::new("  ", true)] {
+            assert!(!filter.is_active());
+            assert!(filter.error().is_none());
+        }
+    }
+}
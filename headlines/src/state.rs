@@ -0,0 +1,1131 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{alerts::{Alert, Alerts, WatchRule}, archive::Archive, bookmarks::Bookmarks, config::{HeadlinesConfig, MAX_ZOOM, MIN_ZOOM}, error::FetchError, extract::Document, filters::{MuteFilters, MutePipeline}, cli::Overrides, history::History, l10n::Message, layout::{GroupBy, Layout}, news::NewsCardData, sort::SortOrder, profiles::{self, Profiles}, query::NewsQuery, tr, worker::{Command, Event}};
+
+/// Everything a frontend can ask the app to do.
+pub enum Action {
+    ApiKeySet(String),
+    Refresh,
+    /// fetch the next page of the feed, once the list is scrolled near its end
+    LoadMore,
+    Cancel,
+    /// the theme by that name, no longer following the system one
+    SetTheme(String),
+    /// scale of the whole window, kept within [`crate::MIN_ZOOM`] and [`crate::MAX_ZOOM`]
+    SetZoom(f32),
+    /// show or hide the article thumbnails
+    ToggleImages,
+    /// lay the current view out in another way
+    SetLayout(Layout),
+    /// group the articles of the current view by something else
+    SetGroupBy(GroupBy),
+    /// list the articles of the current view in another order
+    SetSort(SortOrder),
+    DismissError,
+    /// search with another query, it becomes the persisted default
+    ChangeQuery(NewsQuery),
+    ShowView(View),
+    /// save the article for later, or forget it if it is saved already
+    ToggleBookmark(NewsCardData),
+    /// comma separated tags of the bookmark with this url
+    SetBookmarkTags { url : String, tags : String },
+    SetBookmarkNote { url : String, note : String },
+    /// show the article in the reader, marking it read
+    OpenReader(NewsCardData),
+    CloseReader,
+    /// save the article for offline reading
+    Archive(NewsCardData),
+    Unarchive(String),
+    /// read the archive only, or go back online
+    ToggleOffline,
+    /// minutes between automatic refreshes, 0 to turn them off
+    SetRefreshInterval(u32),
+    /// automatic refreshes wait while the app is in the background
+    SetFocused(bool),
+    /// the new articles notice was acted upon
+    DismissNewArticles,
+    /// archive bookmarks as soon as they are saved
+    ToggleAutoArchive,
+    MarkRead(String),
+    MarkUnread(String),
+    /// every article of the feed on screen
+    MarkAllRead,
+    /// list the muted articles as well, or hide them again
+    ToggleShowHidden,
+    /// replaces the mute filters, ignored unless they are valid
+    SetMuteFilters(MuteFilters),
+    /// replaces every setting at once, ignored unless they are valid
+    SetConfig(Box<HeadlinesConfig>),
+    /// adds the watch rule, or updates the one with the same id
+    SaveWatchRule(WatchRule),
+    RemoveWatchRule(u64),
+    /// the alerts of a rule, of every rule for `None`
+    MarkAlertsRead(Option<u64>),
+    /// saves the settings in use and loads the ones of this profile
+    SwitchProfile(String),
+    /// a profile starting with a copy of the settings in use, switched to
+    CreateProfile(String),
+    /// any profile but the one in use
+    DeleteProfile(String),
+    /// an event reported by the fetch worker
+    Worker(Event)
+}
+
+/// Side effects requested by [`AppState::update`], executed by the frontend.
+#[derive(Debug,PartialEq)]
+pub enum Effect {
+    Worker(Command),
+    StoreConfig,
+    StoreBookmarks,
+    StoreHistory,
+    StoreArchive,
+    StoreAlerts,
+    /// a desktop notification, see [`crate::notify`]
+    Notify { summary : String, body : String }
+}
+
+/// What the main panel lists.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub enum View {
+    /// the articles of the current query
+    #[default]
+    Feed,
+    /// the bookmarks, whatever feed they came from
+    Saved,
+    /// the articles matched by the watch rules
+    Alerts
+}
+
+/// The article shown in the reader and its extracted content.
+pub struct Reader {
+    pub article : NewsCardData,
+    pub page : Page
+}
+
+pub enum Page {
+    Loading,
+    Ready(Document),
+    Failed(FetchError)
+}
+
+/// How long a cached feed is shown before switching to it fetches it again.
+const FEED_FRESH_MINUTES : i64 = 10;
+/// Cached feeds kept around, the oldest ones are dropped first.
+const MAX_FEEDS : usize = 20;
+/// Articles kept in a feed as refreshes merge into it, the oldest ones are dropped first.
+const MAX_FEED_ARTICLES : usize = 5000;
+
+/// The articles of one query, cached so that switching tabs does not refetch them.
+#[derive(Default)]
+pub struct Feed {
+    /// the articles on screen, what [`MutePipeline`] let through unless muted ones are shown
+    pub articles : Vec<NewsCardData>,
+    /// articles muted by the filters
+    pub hidden : usize,
+    pub loading : bool,
+    /// fetching the page after `pages`
+    pub loading_more : bool,
+    pub error : Option<FetchError>,
+    pub fetched_at : Option<DateTime<Utc>>,
+    /// pages of results fetched
+    pub pages : u32,
+    /// newsapi.org has results past the last page fetched
+    pub more : bool,
+    /// urls of the articles a refresh added on top of the list, until they are looked at
+    pub new_articles : Vec<String>,
+    /// every article merged in, the muted ones included
+    fetched : Vec<NewsCardData>
+}
+
+impl Feed {
+    pub fn is_fresh(&self,now : DateTime<Utc>) -> bool {
+        self.error.is_none() && self.fetched_at
+            .is_some_and(|at| now - at < Duration::minutes(FEED_FRESH_MINUTES))
+    }
+
+    /// whether the feed holds as many articles as it keeps, no page is fetched past them
+    pub fn is_full(&self) -> bool {
+        self.fetched.len() >= MAX_FEED_ARTICLES
+    }
+
+    /// the articles on screen stay until the results are merged in
+    fn start_loading(&mut self) {
+        self.loading = true;
+        self.error = None;
+    }
+
+    /// puts the articles not in the feed yet on top of it
+    fn merge(&mut self,articles : Vec<NewsCardData>,mute : &MutePipeline,show_hidden : bool) {
+        let refreshing = !self.fetched.is_empty();
+        let known : HashSet<&str> = self.fetched.iter().map(|a| a.url.as_str()).collect();
+        let fresh : Vec<NewsCardData> = articles.into_iter()
+            .filter(|a| !known.contains(a.url.as_str()))
+            .collect();
+        if refreshing {
+            self.new_articles.extend(fresh.iter().map(|a| a.url.clone()));
+        }
+        self.fetched.splice(0..0, fresh);
+        self.fetched.truncate(MAX_FEED_ARTICLES);
+        self.apply(mute, show_hidden);
+    }
+
+    /// puts the articles of the next page not in the feed yet below it
+    fn append(&mut self,articles : Vec<NewsCardData>,mute : &MutePipeline,show_hidden : bool) {
+        let known : HashSet<&str> = self.fetched.iter().map(|a| a.url.as_str()).collect();
+        let fresh : Vec<NewsCardData> = articles.into_iter()
+            .filter(|a| !known.contains(a.url.as_str()))
+            .collect();
+        self.fetched.extend(fresh);
+        if self.fetched.len() >= MAX_FEED_ARTICLES {
+            self.fetched.truncate(MAX_FEED_ARTICLES);
+            self.more = false;
+        }
+        self.apply(mute, show_hidden);
+    }
+
+    /// runs the fetched articles through the mute filters
+    fn apply(&mut self,mute : &MutePipeline,show_hidden : bool) {
+        let (shown, hidden) : (Vec<NewsCardData>, Vec<NewsCardData>) = self.fetched.iter()
+            .cloned()
+            .partition(|a| mute.hides(a).is_none());
+        self.hidden = hidden.len();
+        self.articles = if show_hidden { self.fetched.clone() } else { shown };
+        let articles = &self.articles;
+        self.new_articles.retain(|url| articles.iter().any(|a| &a.url == url));
+    }
+}
+
+static EMPTY_FEED : Feed = Feed {
+    articles : Vec::new(),
+    hidden : 0,
+    loading : false,
+    loading_more : false,
+    error : None,
+    fetched_at : None,
+    pages : 0,
+    more : false,
+    new_articles : Vec::new(),
+    fetched : Vec::new()
+};
+
+/// UI agnostic application state, shared by the egui and terminal frontends.
+pub struct AppState {
+    /// the settings of the active profile, copied back into `profiles` when stored
+    pub config : HeadlinesConfig,
+    pub profiles : Profiles,
+    pub api_key_initialized : bool,
+    /// the query of the feed on screen
+    pub query : NewsQuery,
+    /// set when newsapi.org rejected the api key
+    pub key_error : Option<FetchError>,
+    pub view : View,
+    pub bookmarks : Bookmarks,
+    /// read articles and the ones new since the last session
+    pub history : History,
+    /// `Some` while an article is open in the reader
+    pub reader : Option<Reader>,
+    pub archive : Archive,
+    /// watch rules and the articles they matched
+    pub alerts : Alerts,
+    /// urls being archived
+    pub archiving : HashSet<String>,
+    /// why the last attempt to archive these urls failed
+    pub archive_errors : HashMap<String,FetchError>,
+    /// false while the app is in the background
+    pub focused : bool,
+    /// list the articles muted by the filters, marked as such
+    pub show_hidden : bool,
+    /// the compiled `config.filters`
+    mute : MutePipeline,
+    /// articles were first seen since the history was stored, it is stored once their fetch ends
+    history_seen : bool,
+    /// applied over the launch profile, kept out of what is stored
+    overrides : Overrides,
+    feeds : HashMap<NewsQuery,Feed>,
+    /// the archived articles, shown instead of the feeds while offline
+    offline_feed : Feed
+}
+
+impl AppState {
+    /// the state persisted by the previous session, with `overrides` over its settings.
+    /// They name the profile to use instead of the last one, a new one if there is none by that name.
+    pub fn load(overrides : Overrides) -> AppState {
+        let mut profiles = Profiles::load();
+        if let Some(name) = &overrides.profile {
+            match profiles::validate_name(name) {
+                Ok(()) => profiles.activate(name, HeadlinesConfig::localized("")),
+                Err(e) => tracing::error!("ignoring profile {} : {}",name,e)
+            }
+        }
+        let persisted = profiles.get(profiles.active()).cloned().unwrap_or_default();
+        let config = overrides.apply(persisted.clone());
+        let config = match config.validate() {
+            Ok(()) => config,
+            Err(e) => {
+                tracing::error!("ignoring the overrides : {}",e);
+                persisted
+            }
+        };
+        let mut state = AppState::new(config);
+        state.profiles = profiles;
+        state.overrides = overrides;
+        state.bookmarks = Bookmarks::load();
+        state.history = History::load(state.config.history_days);
+        state.archive = Archive::load();
+        state.alerts = Alerts::load();
+        state
+    }
+
+    pub fn new(config : HeadlinesConfig) -> AppState {
+        AppState {
+            api_key_initialized : !config.api_key.is_empty(),
+            query : config.query.clone(),
+            mute : MutePipeline::new(&config.filters),
+            history_seen : false,
+            overrides : Overrides::default(),
+            config,
+            profiles : Profiles::default(),
+            key_error : None,
+            view : View::Feed,
+            bookmarks : Bookmarks::default(),
+            history : History::default(),
+            reader : None,
+            archive : Archive::default(),
+            alerts : Alerts::default(),
+            archiving : HashSet::new(),
+            archive_errors : HashMap::new(),
+            focused : true,
+            show_hidden : false,
+            feeds : HashMap::new(),
+            offline_feed : Feed::default()
+        }
+    }
+
+    /// commands configuring a freshly spawned worker
+    pub fn init(&mut self) -> Vec<Effect> {
+        let mut effects = vec![
+            // records the start of this visit
+            Effect::StoreHistory,
+            Effect::Worker(Command::SetApiKey(self.config.api_key.to_string())),
+            self.base_url(),
+            Effect::Worker(Command::ChangeQuery(self.query.clone())),
+            self.network(),
+            self.auto_refresh()
+        ];
+        if self.config.offline {
+            self.update_offline_feed();
+        } else if self.api_key_initialized {
+            self.feed_mut().start_loading();
+            effects.push(Effect::Worker(Command::Refresh));
+        }
+        effects.extend(self.auto_archive());
+        effects
+    }
+
+    /// writes the settings in use to the active profile, and the profiles to disk
+    pub fn store_config(&mut self) {
+        self.save_profile();
+        self.profiles.store();
+    }
+
+    /// copies the settings in use to the active profile, but for the overridden ones
+    fn save_profile(&mut self) {
+        let active = self.profiles.active().to_string();
+        let config = match self.profiles.get(&active) {
+            Some(persisted) => self.overrides.restore(self.config.clone(), persisted),
+            None => self.config.clone()
+        };
+        self.profiles.set(&active, config);
+    }
+
+    /// the feed on screen, the archive while offline
+    pub fn feed(&self) -> &Feed {
+        if self.config.offline {
+            return &self.offline_feed;
+        }
+        self.feeds.get(&self.query).unwrap_or(&EMPTY_FEED)
+    }
+
+    /// configures the worker refreshes, there are none while offline
+    fn auto_refresh(&self) -> Effect {
+        let minutes = Some(self.config.refresh_minutes).filter(|_| !self.config.offline);
+        Effect::Worker(Command::SetAutoRefresh(minutes))
+    }
+
+    fn base_url(&self) -> Effect {
+        Effect::Worker(Command::SetBaseUrl(self.config.base_url.trim().to_string()))
+    }
+
+    fn network(&self) -> Effect {
+        Effect::Worker(Command::SetNetwork {
+            timeout_secs : self.config.timeout_secs,
+            proxy : self.config.proxy.trim().to_string()
+        })
+    }
+
+    /// the new articles of the feed on screen
+    pub fn new_articles(&self) -> &[String] {
+        &self.feed().new_articles
+    }
+
+    fn update_offline_feed(&mut self) {
+        // archived articles were picked one by one, filters do not apply
+        self.offline_feed = Feed {
+            articles : self.archive.iter().map(|e| e.article.clone()).collect(),
+            fetched_at : Some(Utc::now()),
+            ..Feed::default()
+        };
+    }
+
+    fn archive_article(&mut self,article : NewsCardData) -> Vec<Effect> {
+        if self.config.offline || self.archiving.contains(&article.url) {
+            return vec![];
+        }
+        self.archiving.insert(article.url.clone());
+        self.archive_errors.remove(&article.url);
+        // no need to download the page again when it is open in the reader
+        let document = self.reader.as_ref()
+            .filter(|r| r.article.url == article.url)
+            .and_then(|r| match &r.page {
+                Page::Ready(document) => Some(document.clone()),
+                _ => None
+            });
+        vec![Effect::Worker(Command::Archive { article, document })]
+    }
+
+    /// archives the bookmarks missing from the archive, when enabled
+    fn auto_archive(&mut self) -> Vec<Effect> {
+        if !self.config.auto_archive || self.config.offline {
+            return vec![];
+        }
+        let missing : Vec<NewsCardData> = self.bookmarks.iter()
+            .filter(|b| !self.archive.contains(&b.article.url))
+            .map(|b| b.article.clone())
+            .collect();
+        missing.into_iter().flat_map(|article| self.archive_article(article)).collect()
+    }
+
+    fn feed_mut(&mut self) -> &mut Feed {
+        self.feeds.entry(self.query.clone()).or_default()
+    }
+
+    pub fn articles(&self) -> &[NewsCardData] {
+        &self.feed().articles
+    }
+
+    pub fn loading(&self) -> bool {
+        self.feed().loading
+    }
+
+    /// whether scrolling to the end of the feed on screen can fetch its next page
+    pub fn can_load_more(&self) -> bool {
+        let feed = self.feed();
+        !self.config.offline && self.api_key_initialized && feed.more
+            && !feed.loading && !feed.loading_more && feed.error.is_none()
+    }
+
+    pub fn error(&self) -> Option<&FetchError> {
+        self.feed().error.as_ref()
+    }
+
+    /// articles of the feed on screen muted by the filters
+    pub fn hidden_count(&self) -> usize {
+        self.feed().hidden
+    }
+
+    /// why the filters mute `article`, `None` when they do not
+    pub fn hidden_reason(&self,article : &NewsCardData) -> Option<Message> {
+        self.mute.hides(article)
+    }
+
+    /// articles of the feed on screen not read yet
+    pub fn unread_count(&self) -> usize {
+        self.history.unread_count(self.articles())
+    }
+
+    /// nothing to show yet and nothing on the way
+    pub fn is_empty_result(&self) -> bool {
+        let feed = self.feed();
+        feed.articles.is_empty() && !feed.loading && feed.error.is_none()
+    }
+
+    pub fn update(&mut self,action : Action) -> Vec<Effect> {
+        match action {
+            Action::ApiKeySet(api_key) => {
+                self.config.api_key = api_key.trim().to_string();
+                if self.config.api_key.is_empty() {
+                    return vec![];
+                }
+                self.api_key_initialized = true;
+                self.key_error = None;
+                // cached feeds were fetched, or failed, with the previous key
+                self.feeds.clear();
+                let mut effects = vec![
+                    Effect::StoreConfig,
+                    Effect::Worker(Command::SetApiKey(self.config.api_key.to_string()))
+                ];
+                if !self.config.offline {
+                    self.feed_mut().start_loading();
+                    effects.push(Effect::Worker(Command::Refresh));
+                }
+                effects
+            }
+            Action::Refresh => {
+                if self.config.offline {
+                    self.update_offline_feed();
+                    return vec![];
+                }
+                if !self.api_key_initialized {
+                    return vec![];
+                }
+                self.feed_mut().start_loading();
+                vec![Effect::Worker(Command::Refresh)]
+            }
+            Action::LoadMore => {
+                if !self.can_load_more() {
+                    return vec![];
+                }
+                let feed = self.feed_mut();
+                feed.loading_more = true;
+                vec![Effect::Worker(Command::LoadPage(feed.pages + 1))]
+            }
+            Action::Cancel => vec![Effect::Worker(Command::Cancel)],
+            Action::ChangeQuery(query) => {
+                if query == self.query {
+                    return vec![];
+                }
+                self.query = query.clone();
+                self.config.query = query.clone();
+                let mut effects = vec![Effect::StoreConfig, Effect::Worker(Command::ChangeQuery(query))];
+                let feed = self.feed();
+                if self.api_key_initialized && !self.config.offline && !feed.loading && !feed.is_fresh(Utc::now()) {
+                    self.feed_mut().start_loading();
+                    effects.push(Effect::Worker(Command::Refresh));
+                }
+                self.evict_feeds();
+                effects
+            }
+            Action::ShowView(view) => {
+                self.view = view;
+                vec![]
+            }
+            Action::ToggleBookmark(article) => {
+                let mut effects = vec![Effect::StoreBookmarks];
+                if self.bookmarks.toggle(&article) {
+                    effects.extend(self.auto_archive());
+                }
+                effects
+            }
+            Action::SetBookmarkTags { url, tags } => {
+                if self.bookmarks.set_tags(&url, &tags) { vec![Effect::StoreBookmarks] } else { vec![] }
+            }
+            Action::SetBookmarkNote { url, note } => {
+                if self.bookmarks.set_note(&url, &note) { vec![Effect::StoreBookmarks] } else { vec![] }
+            }
+            Action::OpenReader(article) => {
+                let url = article.url.clone();
+                self.reader = Some(Reader { article, page : Page::Loading });
+                let mut effects = Vec::new();
+                if self.archive.contains(&url) {
+                    effects.push(Effect::Worker(Command::LoadArchived(url.clone())));
+                } else if self.config.offline {
+                    let error = FetchError::Network("this article is not archived for offline reading".to_string());
+                    self.reader = self.reader.take().map(|r| Reader { page : Page::Failed(error), ..r });
+                } else {
+                    effects.push(Effect::Worker(Command::FetchPage(url.clone())));
+                }
+                if self.history.mark_read(&url) {
+                    effects.push(Effect::StoreHistory);
+                }
+                if self.alerts.mark_url_read(&url) {
+                    effects.push(Effect::StoreAlerts);
+                }
+                effects
+            }
+            Action::CloseReader => {
+                self.reader = None;
+                vec![]
+            }
+            Action::Archive(article) => self.archive_article(article),
+            Action::Unarchive(url) => {
+                if !self.archive.remove(&url) {
+                    return vec![];
+                }
+                if self.config.offline {
+                    self.update_offline_feed();
+                }
+                vec![Effect::StoreArchive, Effect::Worker(Command::Unarchive(url))]
+            }
+            Action::ToggleOffline => {
+                self.config.offline = !self.config.offline;
+                let mut effects = vec![Effect::StoreConfig, self.auto_refresh()];
+                if self.config.offline {
+                    self.update_offline_feed();
+                    effects.push(Effect::Worker(Command::Cancel));
+                } else {
+                    let feed = self.feed();
+                    if self.api_key_initialized && !feed.loading && !feed.is_fresh(Utc::now()) {
+                        self.feed_mut().start_loading();
+                        effects.push(Effect::Worker(Command::Refresh));
+                    }
+                    effects.extend(self.auto_archive());
+                }
+                effects
+            }
+            Action::SetRefreshInterval(minutes) => {
+                if minutes == self.config.refresh_minutes {
+                    return vec![];
+                }
+                self.config.refresh_minutes = minutes;
+                vec![Effect::StoreConfig, self.auto_refresh()]
+            }
+            Action::SetFocused(focused) => {
+                if focused == self.focused {
+                    return vec![];
+                }
+                self.focused = focused;
+                vec![Effect::Worker(Command::Pause(!focused))]
+            }
+            Action::DismissNewArticles => {
+                if !self.config.offline {
+                    self.feed_mut().new_articles.clear();
+                }
+                vec![]
+            }
+            Action::ToggleAutoArchive => {
+                self.config.auto_archive = !self.config.auto_archive;
+                let mut effects = vec![Effect::StoreConfig];
+                effects.extend(self.auto_archive());
+                effects
+            }
+            Action::MarkRead(url) => {
+                let mut effects = Vec::new();
+                if self.history.mark_read(&url) {
+                    effects.push(Effect::StoreHistory);
+                }
+                if self.alerts.mark_url_read(&url) {
+                    effects.push(Effect::StoreAlerts);
+                }
+                effects
+            }
+            Action::MarkUnread(url) => {
+                if self.history.mark_unread(&url) { vec![Effect::StoreHistory] } else { vec![] }
+            }
+            Action::MarkAllRead => {
+                let urls : Vec<String> = self.articles().iter().map(|a| a.url.clone()).collect();
+                let mut changed = false;
+                for url in urls {
+                    changed |= self.history.mark_read(&url);
+                }
+                if changed { vec![Effect::StoreHistory] } else { vec![] }
+            }
+            Action::ToggleShowHidden => {
+                self.show_hidden = !self.show_hidden;
+                self.apply_filters();
+                vec![]
+            }
+            Action::SetMuteFilters(filters) => {
+                if filters == self.config.filters || filters.validate().is_err() {
+                    return vec![];
+                }
+                self.mute = MutePipeline::new(&filters);
+                self.config.filters = filters;
+                self.apply_filters();
+                vec![Effect::StoreConfig]
+            }
+            Action::SetConfig(config) => self.set_config(*config),
+            Action::SaveWatchRule(rule) => {
+                if rule.validate().is_err() {
+                    return vec![];
+                }
+                self.alerts.save_rule(rule);
+                // the rule applies to the feeds fetched already as well, without
+                // notifications for articles that are not news anymore
+                let articles : Vec<NewsCardData> = self.feeds.values()
+                    .flat_map(|feed| feed.articles.iter().cloned())
+                    .collect();
+                self.alerts.evaluate(&articles);
+                vec![Effect::StoreAlerts]
+            }
+            Action::RemoveWatchRule(id) => {
+                if self.alerts.remove_rule(id) { vec![Effect::StoreAlerts] } else { vec![] }
+            }
+            Action::MarkAlertsRead(rule) => {
+                if self.alerts.mark_read(rule) { vec![Effect::StoreAlerts] } else { vec![] }
+            }
+            Action::SwitchProfile(name) => self.switch_profile(&name),
+            Action::CreateProfile(name) => {
+                if profiles::validate_name(&name).is_err() || self.profiles.contains(&name) {
+                    return vec![];
+                }
+                self.profiles.set(&name, self.config.clone());
+                self.switch_profile(&name)
+            }
+            Action::DeleteProfile(name) => {
+                if self.profiles.remove(&name) { vec![Effect::StoreConfig] } else { vec![] }
+            }
+            Action::DismissError => {
+                if let Some(feed) = self.feeds.get_mut(&self.query) {
+                    feed.error = None;
+                }
+                vec![]
+            }
+            Action::SetTheme(theme) => {
+                if theme.trim().is_empty() {
+                    return vec![];
+                }
+                self.config.theme = theme;
+                self.config.follow_system_theme = false;
+                vec![Effect::StoreConfig]
+            }
+            Action::SetZoom(zoom) => {
+                // steps of 10% do not add up exactly
+                let zoom = ((zoom * 100.).round() / 100.).clamp(MIN_ZOOM, MAX_ZOOM);
+                if zoom == self.config.zoom {
+                    return vec![];
+                }
+                self.config.zoom = zoom;
+                vec![Effect::StoreConfig]
+            }
+            Action::ToggleImages => {
+                self.config.hide_images = !self.config.hide_images;
+                vec![Effect::StoreConfig]
+            }
+            Action::SetLayout(layout) => {
+                let current = self.config.layouts.get_mut(self.view);
+                if layout == current.layout {
+                    return vec![];
+                }
+                current.layout = layout;
+                vec![Effect::StoreConfig]
+            }
+            Action::SetGroupBy(group_by) => {
+                let current = self.config.layouts.get_mut(self.view);
+                if group_by == current.group_by {
+                    return vec![];
+                }
+                current.group_by = group_by;
+                vec![Effect::StoreConfig]
+            }
+            Action::SetSort(sort) => {
+                let current = self.config.layouts.get_mut(self.view);
+                if sort == current.sort {
+                    return vec![];
+                }
+                current.sort = sort;
+                vec![Effect::StoreConfig]
+            }
+            Action::Worker(event) => self.on_worker_event(event)
+        }
+    }
+
+    /// stores the history once a fetch ends rather than for each of its pages
+    fn store_seen(&mut self) -> Vec<Effect> {
+        if std::mem::take(&mut self.history_seen) { vec![Effect::StoreHistory] } else { vec![] }
+    }
+
+    fn on_worker_event(&mut self,event : Event) -> Vec<Effect> {
+        match event {
+            Event::Started { query, page } => {
+                let feed = self.feeds.entry(query).or_default();
+                if page <= 1 {
+                    feed.loading = true;
+                } else {
+                    feed.loading_more = true;
+                }
+                feed.error = None;
+            }
+            Event::Progress { query, page, articles, more } => {
+                let mut effects = Vec::new();
+                self.history_seen |= self.history.see(&articles);
+                // muted articles do not raise alerts either
+                let shown : Vec<NewsCardData> = articles.iter()
+                    .filter(|a| self.mute.hides(a).is_none())
+                    .cloned()
+                    .collect();
+                let alerts = self.alerts.evaluate(&shown);
+                if !alerts.is_empty() {
+                    effects.push(Effect::StoreAlerts);
+                    effects.extend(self.notify(&alerts));
+                }
+                if let Some(feed) = self.feeds.get_mut(&query) {
+                    if page <= 1 {
+                        feed.merge(articles, &self.mute, self.show_hidden);
+                        // a refresh keeps the pages loaded after the first one
+                        if feed.pages <= 1 {
+                            feed.more = more;
+                        }
+                        feed.pages = feed.pages.max(1);
+                    } else if page == feed.pages + 1 {
+                        feed.more = more;
+                        feed.pages = page;
+                        feed.append(articles, &self.mute, self.show_hidden);
+                    }
+                }
+                return effects;
+            }
+            Event::Finished { query } => {
+                if let Some(feed) = self.feeds.get_mut(&query) {
+                    if !feed.loading_more {
+                        feed.fetched_at = Some(Utc::now());
+                    }
+                    feed.loading = false;
+                    feed.loading_more = false;
+                }
+                return self.store_seen();
+            }
+            Event::Cancelled { query } => {
+                if let Some(feed) = self.feeds.get_mut(&query) {
+                    feed.loading = false;
+                    feed.loading_more = false;
+                }
+                return self.store_seen();
+            }
+            Event::PageLoaded { url, result } => {
+                // ignore pages of articles closed since
+                if let Some(reader) = self.reader.as_mut().filter(|r| r.article.url == url) {
+                    reader.page = match result {
+                        Ok(document) => Page::Ready(document),
+                        Err(error) => Page::Failed(error)
+                    };
+                }
+            }
+            Event::Archived { url, result } => {
+                self.archiving.remove(&url);
+                match result {
+                    Ok(entry) => {
+                        self.archive.insert(entry);
+                        if self.config.offline {
+                            self.update_offline_feed();
+                        }
+                        return vec![Effect::StoreArchive];
+                    }
+                    Err(error) => {
+                        tracing::error!("failed archiving {} : {}",url,error);
+                        self.archive_errors.insert(url, error);
+                    }
+                }
+            }
+            Event::Failed { query, error } => {
+                tracing::error!("failed fetching news : {}",error);
+                // a rejected key sends the user back to the configuration window
+                if error.is_invalid_api_key() {
+                    self.api_key_initialized = false;
+                    self.key_error = Some(error.clone());
+                }
+                if let Some(feed) = self.feeds.get_mut(&query) {
+                    feed.loading = false;
+                    feed.loading_more = false;
+                    feed.error = Some(error);
+                }
+                return self.store_seen();
+            }
+        }
+        vec![]
+    }
+
+    /// starts over with the settings of the profile `name`
+    fn switch_profile(&mut self,name : &str) -> Vec<Effect> {
+        if name == self.profiles.active() || !self.profiles.contains(name) {
+            return vec![];
+        }
+        self.save_profile();
+        // the overrides were meant for the launch profile
+        self.overrides = Overrides::default();
+        self.profiles.activate(name, HeadlinesConfig::localized(&self.config.locale));
+        self.config = self.profiles.get(name).cloned().unwrap_or_default();
+        self.api_key_initialized = !self.config.api_key.is_empty();
+        self.key_error = None;
+        self.query = self.config.query.clone();
+        self.mute = MutePipeline::new(&self.config.filters);
+        // the cached feeds were fetched with the previous key and filters
+        self.feeds.clear();
+        self.offline_feed = Feed::default();
+        self.reader = None;
+        let mut effects = vec![Effect::Worker(Command::Cancel)];
+        effects.extend(self.init());
+        effects.push(Effect::StoreConfig);
+        effects
+    }
+
+    /// applies what changed, through the actions changing each setting on its own
+    fn set_config(&mut self,config : HeadlinesConfig) -> Vec<Effect> {
+        if config == self.config {
+            return vec![];
+        }
+        if let Err(e) = config.validate() {
+            tracing::warn!("ignoring settings : {}",e);
+            return vec![];
+        }
+        let mut effects = Vec::new();
+        if config.api_key != self.config.api_key {
+            effects.extend(self.update(Action::ApiKeySet(config.api_key.clone())));
+        }
+        if config.offline != self.config.offline {
+            effects.extend(self.update(Action::ToggleOffline));
+        }
+        if config.query != self.config.query {
+            effects.extend(self.update(Action::ChangeQuery(config.query.clone())));
+        }
+        if config.refresh_minutes != self.config.refresh_minutes {
+            effects.extend(self.update(Action::SetRefreshInterval(config.refresh_minutes)));
+        }
+        if config.filters != self.config.filters {
+            effects.extend(self.update(Action::SetMuteFilters(config.filters.clone())));
+        }
+        if config.auto_archive != self.config.auto_archive {
+            effects.extend(self.update(Action::ToggleAutoArchive));
+        }
+        if config.history_days != self.config.history_days && self.history.forget_older_than(config.history_days) {
+            effects.push(Effect::StoreHistory);
+        }
+        let network_changed = config.timeout_secs != self.config.timeout_secs || config.proxy != self.config.proxy;
+        let server_changed = config.base_url != self.config.base_url;
+        // the remaining settings are only read by the frontends
+        self.config = config;
+        if network_changed {
+            effects.push(self.network());
+        }
+        if server_changed {
+            // cached feeds came from the previous server
+            self.feeds.clear();
+            effects.push(self.base_url());
+            if self.api_key_initialized && !self.config.offline {
+                self.feed_mut().start_loading();
+                effects.push(Effect::Worker(Command::Refresh));
+            }
+        }
+        effects.retain(|effect| *effect != Effect::StoreConfig);
+        effects.push(Effect::StoreConfig);
+        effects
+    }
+
+    /// filters the cached feeds again, after the filters or `show_hidden` changed
+    fn apply_filters(&mut self) {
+        for feed in self.feeds.values_mut() {
+            feed.apply(&self.mute, self.show_hidden);
+        }
+    }
+
+    /// one notification per rule asking for them
+    fn notify(&self,alerts : &[Alert]) -> Vec<Effect> {
+        self.alerts.rules().iter()
+            .filter(|rule| rule.notify)
+            .filter_map(|rule| {
+                let matched : Vec<&str> = alerts.iter()
+                    .filter(|a| a.rule == rule.id)
+                    .map(|a| a.article.title.as_str())
+                    .collect();
+                let first = matched.first()?;
+                let summary = tr!("notify-new-articles", rule = rule.name.as_str(), count = matched.len());
+                Some(Effect::Notify { summary, body : first.to_string() })
+            })
+            .collect()
+    }
+
+    /// forget the least recently fetched feeds once there are too many
+    fn evict_feeds(&mut self) {
+        while self.feeds.len() > MAX_FEEDS {
+            let oldest = self.feeds.iter()
+                .filter(|(query,feed)| **query != self.query && !feed.loading)
+                .min_by_key(|(_,feed)| feed.fetched_at)
+                .map(|(query,_)| query.clone());
+            match oldest {
+                Some(query) => { self.feeds.remove(&query); }
+                None => break
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(i : usize) -> NewsCardData {
+        NewsCardData {
+            title : format!("article {}",i),
+            desc : String::new(),
+            url : format!("https://example.com/{}",i),
+            image : None,
+            source : String::new(),
+            published_at : None,
+            category : None
+        }
+    }
+
+    fn articles(range : std::ops::Range<usize>) -> Vec<NewsCardData> {
+        range.map(article).collect()
+    }
+
+    fn urls(feed : &Feed) -> Vec<String> {
+        feed.articles.iter().map(|a| a.url.clone()).collect()
+    }
+
+    fn with_key() -> AppState {
+        AppState::new(HeadlinesConfig { api_key : "key".to_string(), ..HeadlinesConfig::default() })
+    }
+
+    /// the worker events of a fetch of `page` of the current query
+    fn fetched(state : &mut AppState,page : u32,articles : Vec<NewsCardData>,more : bool) -> Vec<Effect> {
+        let query = state.query.clone();
+        let mut effects = state.update(Action::Worker(Event::Started { query : query.clone(), page }));
+        effects.extend(state.update(Action::Worker(Event::Progress { query : query.clone(), page, articles, more })));
+        effects.extend(state.update(Action::Worker(Event::Finished { query })));
+        effects
+    }
+
+    #[test]
+    fn setting_the_api_key_stores_it_and_refreshes() {
+        let mut state = AppState::new(HeadlinesConfig::default());
+        assert!(!state.api_key_initialized);
+        assert_eq!(state.update(Action::ApiKeySet("  ".to_string())), vec![]);
+        assert!(!state.api_key_initialized);
+
+        let effects = state.update(Action::ApiKeySet(" key ".to_string()));
+        assert_eq!(effects, vec![
+            Effect::StoreConfig,
+            Effect::Worker(Command::SetApiKey("key".to_string())),
+            Effect::Worker(Command::Refresh)
+        ]);
+        assert!(state.api_key_initialized);
+        assert_eq!(state.config.api_key, "key");
+        assert!(state.loading());
+    }
+
+    #[test]
+    fn refresh_needs_a_key() {
+        let mut state = AppState::new(HeadlinesConfig::default());
+        assert_eq!(state.update(Action::Refresh), vec![]);
+        assert!(!state.loading());
+
+        let mut state = with_key();
+        assert_eq!(state.update(Action::Refresh), vec![Effect::Worker(Command::Refresh)]);
+        assert!(state.loading());
+        fetched(&mut state, 1, articles(0..3), false);
+        assert!(!state.loading());
+        assert_eq!(state.articles().len(), 3);
+        assert!(state.feed().is_fresh(Utc::now()));
+    }
+
+    #[test]
+    fn refresh_while_offline_shows_the_archive() {
+        let mut state = AppState::new(HeadlinesConfig { api_key : "key".to_string(), offline : true, ..HeadlinesConfig::default() });
+        assert_eq!(state.update(Action::Refresh), vec![]);
+        assert!(!state.loading());
+    }
+
+    #[test]
+    fn the_history_is_stored_once_a_fetch_ends() {
+        let mut state = with_key();
+        let query = state.query.clone();
+        state.update(Action::Refresh);
+        state.update(Action::Worker(Event::Started { query : query.clone(), page : 1 }));
+        for page in 1..=3 {
+            let articles = articles(10 * page as usize..10 * page as usize + 10);
+            assert_eq!(state.update(Action::Worker(Event::Progress { query : query.clone(), page, articles, more : true })), vec![]);
+        }
+        assert_eq!(state.update(Action::Worker(Event::Finished { query : query.clone() })), vec![Effect::StoreHistory]);
+        // nothing new was seen
+        assert!(!fetched(&mut state, 1, articles(10..20), false).contains(&Effect::StoreHistory));
+        state.update(Action::Worker(Event::Progress { query : query.clone(), page : 1, articles : articles(0..1), more : false }));
+        assert_eq!(state.update(Action::Worker(Event::Cancelled { query })), vec![Effect::StoreHistory]);
+    }
+
+    #[test]
+    fn failed_fetches_keep_their_error() {
+        let mut state = with_key();
+        let query = state.query.clone();
+        state.update(Action::Refresh);
+        state.update(Action::Worker(Event::Failed { query, error : FetchError::Network("timed out".to_string()) }));
+        assert!(!state.loading());
+        assert!(state.error().is_some());
+        assert!(state.api_key_initialized);
+
+        assert_eq!(state.update(Action::DismissError), vec![]);
+        assert!(state.error().is_none());
+    }
+
+    #[test]
+    fn dismissing_no_error_leaves_the_feeds_alone() {
+        let mut state = with_key();
+        assert_eq!(state.update(Action::DismissError), vec![]);
+        assert!(state.feeds.is_empty());
+    }
+
+    #[test]
+    fn setting_the_theme_stops_following_the_system() {
+        let mut state = with_key();
+        state.config.follow_system_theme = true;
+        assert_eq!(state.update(Action::SetTheme(" ".to_string())), vec![]);
+        assert!(state.config.follow_system_theme);
+
+        assert_eq!(state.update(Action::SetTheme("Dark".to_string())), vec![Effect::StoreConfig]);
+        assert_eq!(state.config.theme, "Dark");
+        assert!(!state.config.follow_system_theme);
+    }
+
+    #[test]
+    fn refreshes_put_the_new_articles_on_top_once() {
+        let mut state = with_key();
+        fetched(&mut state, 1, articles(0..3), false);
+        assert!(state.new_articles().is_empty());
+
+        fetched(&mut state, 1, articles(2..5), false);
+        assert_eq!(urls(state.feed()), ["https://example.com/3", "https://example.com/4", "https://example.com/0", "https://example.com/1", "https://example.com/2"]);
+        assert_eq!(state.new_articles(), ["https://example.com/3", "https://example.com/4"]);
+    }
+
+    #[test]
+    fn pages_are_appended_without_duplicates() {
+        let mut state = with_key();
+        fetched(&mut state, 1, articles(0..20), true);
+        assert!(state.can_load_more());
+
+        assert_eq!(state.update(Action::LoadMore), vec![Effect::Worker(Command::LoadPage(2))]);
+        assert!(state.feed().loading_more);
+        assert_eq!(state.update(Action::LoadMore), vec![]);
+
+        fetched(&mut state, 2, articles(15..35), false);
+        let feed = state.feed();
+        assert_eq!(feed.articles.len(), 35);
+        assert_eq!(feed.articles.last().map(|a| a.url.as_str()), Some("https://example.com/34"));
+        assert_eq!(feed.pages, 2);
+        assert!(!feed.loading_more);
+        assert!(!state.can_load_more());
+
+        // a stale page is ignored
+        fetched(&mut state, 2, articles(100..120), true);
+        assert_eq!(state.articles().len(), 35);
+    }
+
+    #[test]
+    fn feeds_keep_at_most_their_maximum_of_articles() {
+        let mut feed = Feed::default();
+        let mute = MutePipeline::default();
+        feed.more = true;
+        feed.merge(articles(0..MAX_FEED_ARTICLES - 10), &mute, false);
+        feed.append(articles(MAX_FEED_ARTICLES - 20..MAX_FEED_ARTICLES + 20), &mute, false);
+        assert_eq!(feed.articles.len(), MAX_FEED_ARTICLES);
+        assert!(!feed.more);
+
+        feed.merge(articles(MAX_FEED_ARTICLES + 100..MAX_FEED_ARTICLES + 105), &mute, false);
+        assert_eq!(feed.articles.len(), MAX_FEED_ARTICLES);
+        assert_eq!(feed.articles[0], article(MAX_FEED_ARTICLES + 100));
+    }
+
+    #[test]
+    fn the_oldest_cached_feeds_are_evicted() {
+        let mut state = with_key();
+        let queries : Vec<NewsQuery> = (0..=MAX_FEEDS).map(|i| state.query.with_keywords(&format!("topic{}",i))).collect();
+        for query in &queries {
+            state.update(Action::ChangeQuery(query.clone()));
+            state.update(Action::Worker(Event::Finished { query : query.clone() }));
+        }
+        assert_eq!(state.feeds.len(), MAX_FEEDS);
+        assert!(!state.feeds.contains_key(&queries[0]));
+        assert!(state.feeds.contains_key(&queries[MAX_FEEDS]));
+    }
+}
@@ -0,0 +1,224 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Directory, and local storage prefix, shared by everything the app persists.
+pub const APP_NAME : &str = "headlines";
+
+/// Small documents persisted between sessions, one per `name`.
+///
+/// On native they are toml files in the config directory, next to the confy config,
+/// on wasm32 json strings in the browser local storage.
+/// A missing or unreadable document loads as its default.
+pub fn load<T : Serialize + DeserializeOwned + Default>(name : &str) -> T {
+    match read(name) {
+        Ok(value) => value,
+        Err(e) => {
+            tracing::error!("failed loading {} : {}",name,e);
+            T::default()
+        }
+    }
+}
+
+pub fn store<T : Serialize>(name : &str,value : &T) {
+    if let Err(e) = write(name, value) {
+        tracing::error!("failed saving {} : {}",name,e);
+    }
+}
+
+/// Like [`load`], reporting what is wrong with the document rather than falling back to its default.
+#[cfg(feature = "gui")]
+pub fn try_load<T : Serialize + DeserializeOwned + Default>(name : &str) -> Result<T,String> {
+    read(name)
+}
+
+#[cfg(feature = "gui")]
+pub fn try_store<T : Serialize>(name : &str,value : &T) -> Result<(),String> {
+    write(name, value)
+}
+
+/// The document as it is stored, `None` when there is none yet.
+///
+/// For documents whose format changed between versions, to be migrated before they are deserialized.
+pub fn load_value(name : &str) -> Result<Option<serde_json::Value>,String> {
+    read_value(name)
+}
+
+/// Copies the stored document next to it, under the name `<name>.<suffix>.bak`.
+pub fn backup(name : &str,suffix : &str) -> Result<(),String> {
+    copy(name, &format!("{}.{}.bak",name,suffix))
+}
+
+/// file name friendly key of an url, its 64 bit FNV-1a hash. Archived files are found
+/// by it, it has to stay the same across builds, which the hashers of std do not promise.
+pub fn url_key(url : &str) -> String {
+    const OFFSET_BASIS : u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME : u64 = 0x0100_0000_01b3;
+
+    let hash = url.bytes().fold(OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME));
+    format!("{:016x}",hash)
+}
+
+#[cfg(test)]
+thread_local! {
+    /// where the tests of this thread store their documents, they store none without it
+    pub static TEST_DIR : std::cell::RefCell<Option<std::path::PathBuf>> = const { std::cell::RefCell::new(None) };
+}
+
+/// A config directory of its own for a test, removed once it is dropped.
+#[cfg(test)]
+pub(crate) struct TestDir(std::path::PathBuf);
+
+#[cfg(test)]
+impl TestDir {
+    pub(crate) fn new(name : &str) -> TestDir {
+        let dir = std::env::temp_dir().join(format!("headlines-{}-{}",name,std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        TEST_DIR.with(|test_dir| *test_dir.borrow_mut() = Some(dir.clone()));
+        TestDir(dir)
+    }
+
+    pub(crate) fn file(&self,name : &str) -> std::path::PathBuf {
+        self.0.join(name)
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        TEST_DIR.with(|test_dir| *test_dir.borrow_mut() = None);
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(all(not(test), not(target_arch = "wasm32")))]
+pub fn path(name : &str) -> Option<std::path::PathBuf> {
+    let project = directories::ProjectDirs::from("rs", "", APP_NAME)?;
+    Some(project.config_dir().join(format!("{}.toml",name)))
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub fn path(name : &str) -> Option<std::path::PathBuf> {
+    TEST_DIR.with(|dir| dir.borrow().as_ref().map(|dir| dir.join(format!("{}.toml",name))))
+}
+
+/// Names of the documents stored under `dir`, `<dir>/<name>` loading them.
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+pub fn list(dir : &str) -> Vec<String> {
+    let entries = match path(dir).map(|path| path.with_extension("")).map(std::fs::read_dir) {
+        Some(Ok(entries)) => entries,
+        _ => return Vec::new()
+    };
+    let mut names : Vec<String> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "toml"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read<T : Serialize + DeserializeOwned + Default>(name : &str) -> Result<T,String> {
+    let path = path(name).ok_or("no config directory")?;
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    confy::load_path(path).map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_value(name : &str) -> Result<Option<serde_json::Value>,String> {
+    let path = path(name).ok_or("no config directory")?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let value : toml::Value = toml::from_str(&text).map_err(|e| e.to_string())?;
+    serde_json::to_value(value).map(Some).map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn copy(name : &str,to : &str) -> Result<(),String> {
+    let from = path(name).ok_or("no config directory")?;
+    let to = path(to).ok_or("no config directory")?;
+    std::fs::copy(from, to).map(|_| ()).map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write<T : Serialize>(name : &str,value : &T) -> Result<(),String> {
+    let path = path(name).ok_or("no config directory")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    confy::store_path(path, value).map_err(|e| e.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Result<web_sys::Storage,String> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or_else(|| "local storage is not available".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn key(name : &str) -> String {
+    format!("{}.{}",APP_NAME,name)
+}
+
+#[cfg(all(feature = "gui", target_arch = "wasm32"))]
+pub fn list(dir : &str) -> Vec<String> {
+    let storage = match local_storage() {
+        Ok(storage) => storage,
+        Err(_) => return Vec::new()
+    };
+    let prefix = format!("{}/",key(dir));
+    let mut names : Vec<String> = (0..storage.length().unwrap_or(0))
+        .filter_map(|index| storage.key(index).ok().flatten())
+        .filter_map(|key| Some(key.strip_prefix(&prefix)?.to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read<T : Serialize + DeserializeOwned + Default>(name : &str) -> Result<T,String> {
+    match local_storage()?.get_item(&key(name)).map_err(|_| "failed reading local storage")? {
+        Some(json) => serde_json::from_str(&json).map_err(|e| e.to_string()),
+        None => Ok(T::default())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_value(name : &str) -> Result<Option<serde_json::Value>,String> {
+    match local_storage()?.get_item(&key(name)).map_err(|_| "failed reading local storage")? {
+        Some(json) => serde_json::from_str(&json).map(Some).map_err(|e| e.to_string()),
+        None => Ok(None)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn copy(name : &str,to : &str) -> Result<(),String> {
+    let storage = local_storage()?;
+    let json = storage.get_item(&key(name))
+        .map_err(|_| "failed reading local storage")?
+        .ok_or("nothing to copy")?;
+    storage.set_item(&key(to), &json).map_err(|_| "failed writing local storage".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write<T : Serialize>(name : &str,value : &T) -> Result<(),String> {
+    let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    local_storage()?.set_item(&key(name), &json).map_err(|_| "failed writing local storage".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_keys_never_change() {
+        assert_eq!(url_key(""), "cbf29ce484222325");
+        assert_eq!(url_key("a"), "af63dc4c8601ec8c");
+        assert_eq!(url_key("https://example.com/news/1"), "d9a3508a5dc09bbf");
+    }
+}
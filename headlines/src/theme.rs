@@ -0,0 +1,310 @@
+use eframe::egui::{style::Margin, Color32, Frame, Rounding, Stroke, Style, Ui, Visuals};
+use serde::{Serialize,Deserialize};
+
+use crate::{config::{DARK_THEME, DEFAULT_THEME, HIGH_CONTRAST_THEME}, l10n::Message, message, storage};
+
+/// Where the user themes are stored, one `<name>.toml` each.
+const THEMES_DIR : &str = "themes";
+
+/// Colours and shapes of the window, built in or loaded from a user toml file.
+///
+/// ```toml
+/// dark = false
+/// background = "#f4ecd8"
+/// card = "#fbf5e6"
+/// title = "#3c2814"
+/// description = "#5b4632"
+/// hyperlink = "#965014"
+/// spacing = 6.0
+/// rounding = 6.0
+/// ```
+#[derive(Clone,Debug,PartialEq,Serialize,Deserialize)]
+#[serde(default)]
+pub(crate) struct Theme {
+    /// the file name of user themes
+    #[serde(skip)]
+    pub(crate) name : String,
+    /// starts from the dark egui style rather than the light one
+    pub(crate) dark : bool,
+    /// panels and windows
+    #[serde(with = "hex")]
+    pub(crate) background : [u8; 3],
+    #[serde(with = "hex")]
+    pub(crate) card : [u8; 3],
+    #[serde(with = "hex")]
+    pub(crate) title : [u8; 3],
+    /// descriptions and the rest of the text
+    #[serde(with = "hex")]
+    pub(crate) description : [u8; 3],
+    #[serde(with = "hex")]
+    pub(crate) hyperlink : [u8; 3],
+    /// room around and between the cards, in points
+    pub(crate) spacing : f32,
+    /// radius of the corners of cards, buttons and windows
+    pub(crate) rounding : f32,
+    /// width of the card borders, drawn in the title colour, 0 for none
+    pub(crate) outline : f32
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            name : DEFAULT_THEME.to_string(),
+            dark : false,
+            background : [245, 245, 245],
+            card : [255, 255, 255],
+            title : [0, 0, 0],
+            description : [60, 60, 60],
+            hyperlink : [0, 130, 150],
+            spacing : 5.,
+            rounding : 4.,
+            outline : 0.
+        }
+    }
+}
+
+impl Theme {
+    fn builtin() -> [Theme; 4] {
+        [
+            Theme::default(),
+            Theme {
+                name : DARK_THEME.to_string(),
+                dark : true,
+                background : [27, 27, 27],
+                card : [38, 38, 38],
+                title : [255, 255, 255],
+                description : [180, 180, 180],
+                hyperlink : [0, 250, 250],
+                ..Theme::default()
+            },
+            Theme {
+                name : HIGH_CONTRAST_THEME.to_string(),
+                dark : true,
+                background : [0, 0, 0],
+                card : [0, 0, 0],
+                title : [255, 255, 255],
+                description : [255, 255, 255],
+                hyperlink : [255, 255, 0],
+                spacing : 6.,
+                rounding : 0.,
+                outline : 1.5
+            },
+            Theme {
+                name : "Sepia".to_string(),
+                background : [244, 236, 216],
+                card : [251, 245, 230],
+                title : [60, 40, 20],
+                description : [91, 70, 50],
+                hyperlink : [150, 80, 20],
+                spacing : 6.,
+                rounding : 6.,
+                ..Theme::default()
+            }
+        ]
+    }
+
+    pub(crate) fn title(&self) -> Color32 {
+        rgb(self.title)
+    }
+
+    pub(crate) fn background(&self) -> Color32 {
+        rgb(self.background)
+    }
+
+    /// replaces the colours and shapes of `style`, leaving its text styles alone
+    pub(crate) fn apply(&self,style : &mut Style) {
+        let mut visuals = if self.dark { Visuals::dark() } else { Visuals::light() };
+        visuals.widgets.noninteractive.bg_fill = rgb(self.background);
+        visuals.widgets.noninteractive.fg_stroke.color = rgb(self.description);
+        visuals.faint_bg_color = rgb(self.card);
+        visuals.hyperlink_color = rgb(self.hyperlink);
+        let rounding = Rounding::same(self.rounding);
+        let widgets = &mut visuals.widgets;
+        for widget in [&mut widgets.noninteractive, &mut widgets.inactive, &mut widgets.hovered, &mut widgets.active, &mut widgets.open] {
+            widget.rounding = rounding;
+        }
+        visuals.window_rounding = rounding;
+        style.visuals = visuals;
+    }
+
+    /// an article card, followed by the space to the next one, outlined in the
+    /// link colour when `selected` by the keyboard
+    pub(crate) fn card<R>(&self,ui : &mut Ui,selected : bool,add_contents : impl FnOnce(&mut Ui) -> R) -> R {
+        let stroke = if selected {
+            Stroke::new(self.outline.max(1.) + 1., rgb(self.hyperlink))
+        } else {
+            Stroke::new(self.outline, rgb(self.title))
+        };
+        let inner = Frame::none()
+            .fill(rgb(self.card))
+            .rounding(self.rounding)
+            .stroke(stroke)
+            .margin(Margin::same(self.spacing))
+            .show(ui, |ui|{
+                ui.set_min_width(ui.available_width());
+                add_contents(ui)
+            })
+            .inner;
+        ui.add_space(self.spacing);
+        inner
+    }
+}
+
+fn rgb([r, g, b] : [u8; 3]) -> Color32 {
+    Color32::from_rgb(r, g, b)
+}
+
+/// The built-in themes followed by the user ones.
+pub(crate) struct Themes {
+    themes : Vec<Theme>,
+    /// user themes that failed to load
+    pub(crate) errors : Vec<String>
+}
+
+impl Themes {
+    pub(crate) fn load() -> Themes {
+        let mut themes = Themes { themes : Theme::builtin().to_vec(), errors : Vec::new() };
+        for name in storage::list(THEMES_DIR) {
+            if themes.is_builtin(&name) {
+                themes.errors.push(format!("{} : a built-in theme has this name",name));
+                continue;
+            }
+            match storage::try_load::<Theme>(&format!("{}/{}",THEMES_DIR,name)) {
+                Ok(theme) => themes.themes.push(Theme { name, ..theme }),
+                Err(e) => themes.errors.push(format!("{} : {}",name,e))
+            }
+        }
+        for error in &themes.errors {
+            tracing::warn!("ignoring theme {}",error);
+        }
+        themes
+    }
+
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        self.themes.iter().map(|theme| theme.name.as_str())
+    }
+
+    /// the theme by that name, whatever its case, the default one when there is none
+    pub(crate) fn get(&self,name : &str) -> &Theme {
+        self.themes.iter()
+            .find(|theme| theme.name.eq_ignore_ascii_case(name))
+            .unwrap_or(&self.themes[0])
+    }
+
+    pub(crate) fn is_builtin(&self,name : &str) -> bool {
+        Theme::builtin().iter().any(|theme| theme.name.eq_ignore_ascii_case(name))
+    }
+
+    /// saves `theme` as a user theme, replacing the one with the same name
+    pub(crate) fn save(&mut self,theme : Theme) -> Result<(),String> {
+        validate_name(&theme.name)?;
+        if self.is_builtin(&theme.name) {
+            return Err(message!("theme-builtin", theme = theme.name.as_str()).into());
+        }
+        storage::try_store(&format!("{}/{}",THEMES_DIR,theme.name), &theme)?;
+        self.themes.retain(|t| !t.name.eq_ignore_ascii_case(&theme.name));
+        self.themes.push(theme);
+        Ok(())
+    }
+}
+
+/// user themes are files, named after them
+pub(crate) fn validate_name(name : &str) -> Result<(),Message> {
+    if name.is_empty() || name.len() > 32 {
+        return Err(message!("invalid-theme-name-length"));
+    }
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return Err(message!("invalid-theme-name"));
+    }
+    Ok(())
+}
+
+/// Whether the system is set to a dark theme, light where it does not tell.
+pub(crate) fn system_is_dark() -> bool {
+    dark_light::detect() == dark_light::Mode::Dark
+}
+
+/// `#rrggbb` colours, friendlier to edit by hand than arrays
+mod hex {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S : Serializer>([r, g, b] : &[u8; 3],serializer : S) -> Result<S::Ok,S::Error> {
+        serializer.serialize_str(&format!("#{:02x}{:02x}{:02x}",r,g,b))
+    }
+
+    pub fn deserialize<'de,D : Deserializer<'de>>(deserializer : D) -> Result<[u8; 3],D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let hex = text.strip_prefix('#').filter(|hex| hex.len() == 6 && hex.is_ascii())
+            .ok_or_else(|| D::Error::custom(format!("expected a colour like #1e90ff, not {}",text)))?;
+        let channel = |i : usize| u8::from_str_radix(&hex[i..i + 2], 16)
+            .map_err(|_| D::Error::custom(format!("expected a colour like #1e90ff, not {}",text)));
+        Ok([channel(0)?, channel(2)?, channel(4)?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::storage::TestDir;
+
+    /// the themes of a config directory holding these user theme files
+    fn load(name : &str,files : &[(&str,&str)]) -> Themes {
+        let dir = TestDir::new(name);
+        fs::create_dir_all(dir.file(THEMES_DIR)).unwrap();
+        for (file, toml) in files {
+            fs::write(dir.file(THEMES_DIR).join(file), toml).unwrap();
+        }
+        Themes::load()
+    }
+
+    #[test]
+    fn user_themes_follow_the_builtin_ones() {
+        let themes = load("themes", &[
+            ("ocean.toml", "dark = true\nbackground = \"#001F3f\"\nrounding = 8.0"),
+            ("notes.txt", "not a theme")
+        ]);
+        assert!(themes.errors.is_empty(), "{:?}", themes.errors);
+        let names : Vec<&str> = themes.names().collect();
+        assert_eq!(names, [DEFAULT_THEME, DARK_THEME, HIGH_CONTRAST_THEME, "Sepia", "ocean"]);
+        let ocean = themes.get("OCEAN");
+        assert!(ocean.dark);
+        assert_eq!(ocean.background, [0x00, 0x1f, 0x3f]);
+        assert_eq!(ocean.rounding, 8.);
+    }
+
+    #[test]
+    fn missing_fields_are_those_of_the_default_theme() {
+        let themes = load("themes-missing", &[("empty.toml", ""), ("card.toml", "card = \"#101010\"")]);
+        assert!(themes.errors.is_empty(), "{:?}", themes.errors);
+        assert_eq!(*themes.get("empty"), Theme { name : "empty".to_string(), ..Theme::default() });
+        assert_eq!(*themes.get("card"), Theme { name : "card".to_string(), card : [16, 16, 16], ..Theme::default() });
+    }
+
+    #[test]
+    fn invalid_colours_are_reported() {
+        let themes = load("themes-colours", &[
+            ("named.toml", "card = \"red\""),
+            ("short.toml", "card = \"#fff\""),
+            ("hex.toml", "card = \"#gg0000\""),
+            ("number.toml", "card = 255"),
+            ("ok.toml", "card = \"#FFFFFF\"")
+        ]);
+        let mut failed : Vec<&str> = themes.errors.iter().map(|error| error.split(" : ").next().unwrap()).collect();
+        failed.sort_unstable();
+        assert_eq!(failed, ["hex", "named", "number", "short"]);
+        assert!(themes.errors.iter().any(|error| error.contains("expected a colour like #1e90ff, not red")), "{:?}", themes.errors);
+        assert_eq!(themes.names().filter(|name| !themes.is_builtin(name)).collect::<Vec<_>>(), ["ok"]);
+        // the failed ones fall back to the default theme
+        assert_eq!(themes.get("named").name, DEFAULT_THEME);
+    }
+
+    #[test]
+    fn builtin_names_are_taken() {
+        let themes = load("themes-builtin", &[("dark.toml", "background = \"#ff0000\""), ("sepia.toml", "")]);
+        assert_eq!(themes.errors, ["dark : a built-in theme has this name", "sepia : a built-in theme has this name"]);
+        assert_eq!(themes.get("dark").background, [27, 27, 27]);
+        assert_eq!(themes.names().count(), Theme::builtin().len());
+    }
+}
@@ -0,0 +1,322 @@
+use std::{collections::HashMap, sync::mpsc::{self, Receiver, Sender}};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs, path::{Path, PathBuf}, sync::{atomic::{AtomicU64, Ordering}, Arc, Mutex}, thread, time::SystemTime};
+
+use eframe::egui::{Align2, ColorImage, Context, Image, Rect, Response, Sense, TextStyle, TextureHandle, Ui, Vec2};
+
+use crate::{tr, worker::Waker};
+
+/// Size of the thumbnail next to an article, images are downscaled to fit in it.
+pub const THUMBNAIL_SIZE : Vec2 = Vec2::new(96., 72.);
+/// Images are decoded at most this big, the tiles of the magazine layout show them
+/// at full size.
+const DECODED_SIZE : Vec2 = Vec2::new(320., 200.);
+/// Bigger images are not downloaded.
+const MAX_IMAGE_BYTES : u64 = 5 * 1024 * 1024;
+/// Textures kept in memory, the least recently shown ones are dropped first.
+const MAX_TEXTURES : usize = 200;
+#[cfg(not(target_arch = "wasm32"))]
+const LOADER_THREADS : usize = 4;
+/// Size of the thumbnail disk cache, the least recently used files are deleted first.
+#[cfg(not(target_arch = "wasm32"))]
+static CACHE_BYTES : AtomicU64 = AtomicU64::new(50 * 1024 * 1024);
+
+type Loaded = (String,Result<ColorImage,String>);
+
+enum Thumbnail {
+    Loading,
+    Ready(TextureHandle),
+    Failed
+}
+
+/// Article thumbnails as egui textures, downloaded and decoded off the UI thread.
+///
+/// On native a few loader threads share the requests and keep the downscaled images
+/// in a disk cache. On wasm32 the browser cache is relied on instead, downloads run
+/// on the event loop and decoding on the main thread.
+pub struct Thumbnails {
+    thumbnails : HashMap<String,(Thumbnail,u64)>,
+    /// incremented on every poll, marks when a thumbnail was last shown
+    frame : u64,
+    loaded : Receiver<Loaded>,
+    /// asks for the frame turning the loaded images into textures
+    waker : Waker,
+    #[cfg(not(target_arch = "wasm32"))]
+    requests : Sender<String>,
+    #[cfg(target_arch = "wasm32")]
+    loaded_tx : Sender<Loaded>
+}
+
+impl Thumbnails {
+    pub fn new() -> Thumbnails {
+        let (loaded_tx,loaded) = mpsc::channel();
+        let waker = Waker::default();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let (requests,request_rx) = mpsc::channel::<String>();
+            let request_rx = Arc::new(Mutex::new(request_rx));
+            for i in 0..LOADER_THREADS {
+                let request_rx = request_rx.clone();
+                let loaded_tx = loaded_tx.clone();
+                let waker = waker.clone();
+                let spawned = thread::Builder::new()
+                    .name(format!("headlines-thumbnails-{}",i))
+                    .spawn(move || {
+                        // stops once `requests` is dropped
+                        loop {
+                            let url = match request_rx.lock() {
+                                Ok(rx) => match rx.recv() {
+                                    Ok(url) => url,
+                                    Err(_) => break
+                                },
+                                Err(_) => break
+                            };
+                            let image = load(&url);
+                            if loaded_tx.send((url,image)).is_err() {
+                                break;
+                            }
+                            waker.wake();
+                        }
+                    });
+                if let Err(e) = spawned {
+                    tracing::error!("failed spawning thumbnail loader : {}",e);
+                }
+            }
+            Thumbnails { thumbnails : HashMap::new(), frame : 0, loaded, waker, requests }
+        }
+        #[cfg(target_arch = "wasm32")]
+        Thumbnails { thumbnails : HashMap::new(), frame : 0, loaded, waker, loaded_tx }
+    }
+
+    /// called from the loader threads once an image is ready
+    pub fn set_waker(&self,wake : impl Fn() + Send + Sync + 'static) {
+        self.waker.set(wake);
+    }
+
+    /// turns the images loaded since the last frame into textures
+    pub fn poll(&mut self,ctx : &Context) {
+        self.frame += 1;
+        for (url,image) in self.loaded.try_iter() {
+            let thumbnail = match image {
+                Ok(image) => Thumbnail::Ready(ctx.load_texture(url.as_str(), image)),
+                Err(e) => {
+                    tracing::warn!("failed loading thumbnail {} : {}",url,e);
+                    Thumbnail::Failed
+                }
+            };
+            self.thumbnails.insert(url, (thumbnail,self.frame));
+        }
+        while self.thumbnails.len() > MAX_TEXTURES {
+            let oldest = self.thumbnails.iter()
+                .filter(|(_,(thumbnail,_))| !matches!(thumbnail, Thumbnail::Loading))
+                .min_by_key(|(_,(_,last_used))| *last_used)
+                .map(|(url,_)| url.clone());
+            match oldest {
+                Some(url) => { self.thumbnails.remove(&url); }
+                None => break
+            }
+        }
+    }
+
+    /// the thumbnail of `url`, or a placeholder while it loads or when it failed
+    pub fn show(&mut self,ui : &mut Ui,url : &str) -> Response {
+        self.show_sized(ui, url, THUMBNAIL_SIZE)
+    }
+
+    /// the image of `url` fit in `size`, never upscaled
+    pub fn show_sized(&mut self,ui : &mut Ui,url : &str,size : Vec2) -> Response {
+        if !self.thumbnails.contains_key(url) {
+            self.request(url);
+        }
+        let frame = self.frame;
+        let (thumbnail,last_used) = self.thumbnails.entry(url.to_string()).or_insert((Thumbnail::Loading,frame));
+        *last_used = frame;
+        let (rect,response) = ui.allocate_exact_size(size, Sense::hover());
+        let painter = ui.painter_at(rect);
+        let (icon,hint) = match thumbnail {
+            Thumbnail::Ready(texture) => {
+                // centered, keeping the aspect ratio of the downscaled image
+                let image_size = texture.size_vec2();
+                let scale = (size.x / image_size.x).min(size.y / image_size.y).min(1.);
+                let image_rect = Rect::from_center_size(rect.center(), image_size * scale);
+                ui.put(image_rect, Image::new(texture.id(), image_rect.size()));
+                return response;
+            }
+            Thumbnail::Loading => ("⏳", tr!("image-loading")),
+            Thumbnail::Failed => ("🖼", tr!("image-unavailable"))
+        };
+        painter.rect_filled(rect, 4., ui.visuals().faint_bg_color);
+        painter.text(rect.center(), Align2::CENTER_CENTER, icon, TextStyle::Body.resolve(ui.style()), ui.visuals().weak_text_color());
+        response.on_hover_text(hint)
+    }
+
+    fn request(&mut self,url : &str) {
+        self.thumbnails.insert(url.to_string(), (Thumbnail::Loading,self.frame));
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(e) = self.requests.send(url.to_string()) {
+            tracing::error!("failed requesting thumbnail : {}",e);
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let url = url.to_string();
+            let loaded_tx = self.loaded_tx.clone();
+            let waker = self.waker.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let image = download_web(&url).await
+                    .and_then(|bytes| decode(&bytes))
+                    .map(to_color_image);
+                if loaded_tx.send((url,image)).is_ok() {
+                    waker.wake();
+                }
+            });
+        }
+    }
+}
+
+/// Size of the disk cache, applied as thumbnails are cached. The browser cache is used on wasm32.
+pub(crate) fn set_cache_limit(megabytes : u32) {
+    #[cfg(not(target_arch = "wasm32"))]
+    CACHE_BYTES.store(u64::from(megabytes) * 1024 * 1024, Ordering::Relaxed);
+    #[cfg(target_arch = "wasm32")]
+    let _ = megabytes;
+}
+
+/// Deletes the thumbnails cached on disk, the ones on screen stay loaded.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn clear_cache() {
+    if let Some(dir) = cache_dir() {
+        if let Err(e) = fs::remove_dir_all(dir) {
+            tracing::warn!("failed clearing the thumbnail cache : {}",e);
+        }
+    }
+}
+
+impl Default for Thumbnails {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// decodes and downscales to fit in [`DECODED_SIZE`]
+fn decode(bytes : &[u8]) -> Result<image::DynamicImage,String> {
+    let image = image::load_from_memory(bytes).map_err(|e| e.to_string())?;
+    Ok(image.thumbnail(DECODED_SIZE.x as u32, DECODED_SIZE.y as u32))
+}
+
+fn to_color_image(image : image::DynamicImage) -> ColorImage {
+    let rgba = image.to_rgba8();
+    ColorImage::from_rgba_unmultiplied([rgba.width() as usize, rgba.height() as usize], rgba.as_raw())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load(url : &str) -> Result<ColorImage,String> {
+    let cached = cache_path(url);
+    if let Some(image) = cached.as_deref().and_then(read_cached) {
+        return Ok(to_color_image(image));
+    }
+    // archived images keep working offline
+    let bytes = match crate::archive::image_path(url).and_then(|path| fs::read(path).ok()) {
+        Some(bytes) => bytes,
+        None => download(url)?
+    };
+    let image = decode(&bytes)?;
+    if let Some(path) = cached {
+        write_cached(&path, &image);
+    }
+    Ok(to_color_image(image))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn download(url : &str) -> Result<Vec<u8>,String> {
+    use std::io::Read;
+
+    let response = crate::net::agent().get(url).call().map_err(|e| e.to_string())?;
+    let mut bytes = Vec::new();
+    response.into_reader()
+        .take(MAX_IMAGE_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| e.to_string())?;
+    if bytes.len() as u64 > MAX_IMAGE_BYTES {
+        return Err("image is too big".to_string());
+    }
+    Ok(bytes)
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn download_web(url : &str) -> Result<Vec<u8>,String> {
+    let response = reqwasm::http::Request::get(url).send().await.map_err(|e| e.to_string())?;
+    if !response.ok() {
+        return Err(format!("status {}",response.status()));
+    }
+    let bytes = response.binary().await.map_err(|e| e.to_string())?;
+    if bytes.len() as u64 > MAX_IMAGE_BYTES {
+        return Err("image is too big".to_string());
+    }
+    Ok(bytes)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn cache_dir() -> Option<PathBuf> {
+    let project = directories::ProjectDirs::from("rs", "", crate::storage::APP_NAME)?;
+    Some(project.cache_dir().join("thumbnails"))
+}
+
+/// one png per url, named after its hash and the size it was decoded at
+#[cfg(not(target_arch = "wasm32"))]
+fn cache_path(url : &str) -> Option<PathBuf> {
+    Some(cache_dir()?.join(format!("{}-{}.png",crate::storage::url_key(url),DECODED_SIZE.x)))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_cached(path : &Path) -> Option<image::DynamicImage> {
+    let image = image::open(path).ok()?;
+    // the modification time orders the files for eviction
+    if let Ok(file) = fs::File::options().write(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+    Some(image)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_cached(path : &Path,image : &image::DynamicImage) {
+    let dir = match path.parent() {
+        Some(dir) => dir,
+        None => return
+    };
+    if let Err(e) = fs::create_dir_all(dir) {
+        tracing::warn!("failed creating the thumbnail cache : {}",e);
+        return;
+    }
+    if let Err(e) = image.save_with_format(path, image::ImageFormat::Png) {
+        tracing::warn!("failed caching thumbnail : {}",e);
+        return;
+    }
+    evict_cached(dir, CACHE_BYTES.load(Ordering::Relaxed));
+}
+
+/// deletes the least recently used files until the cache fits in `max_bytes`
+#[cfg(not(target_arch = "wasm32"))]
+fn evict_cached(dir : &Path,max_bytes : u64) {
+    let mut files : Vec<(SystemTime,u64,PathBuf)> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+            })
+            .collect(),
+        Err(_) => return
+    };
+    let mut total : u64 = files.iter().map(|(_,len,_)| len).sum();
+    files.sort();
+    for (_,len,path) in files {
+        if total <= max_bytes {
+            break;
+        }
+        // another loader thread may have deleted it already
+        if fs::remove_file(&path).is_ok() {
+            total -= len;
+        }
+    }
+}
@@ -0,0 +1,492 @@
+use std::sync::{atomic::{AtomicBool, Ordering}, mpsc::{self, Receiver, Sender}, Arc, Mutex};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{sync::mpsc::RecvTimeoutError, thread::{self, JoinHandle}};
+#[cfg(target_arch = "wasm32")]
+use std::cell::RefCell;
+
+use chrono::{DateTime, Duration, Utc};
+use newsApi::{NewsApi, NewsApiResponse};
+
+use crate::{archive::{self, ArchiveEntry}, error::FetchError, extract::{self, Document}, news::NewsCardData, query::NewsQuery};
+
+/// Articles asked for per page.
+pub const PAGE_SIZE : usize = 20;
+
+type Wake = Arc<dyn Fn() + Send + Sync>;
+
+/// Wakes the frontend up once the worker has events for it, see [`Worker::set_waker`].
+#[derive(Clone,Default)]
+pub struct Waker(Arc<Mutex<Option<Wake>>>);
+
+impl Waker {
+    pub fn set(&self,wake : impl Fn() + Send + Sync + 'static) {
+        if let Ok(mut waker) = self.0.lock() {
+            *waker = Some(Arc::new(wake));
+        }
+    }
+
+    pub fn wake(&self) {
+        let wake = self.0.lock().ok().and_then(|waker| waker.clone());
+        if let Some(wake) = wake {
+            wake();
+        }
+    }
+}
+
+/// The sending end of the worker events, waking the frontend up.
+#[derive(Clone)]
+struct Events {
+    sender : Sender<Event>,
+    waker : Waker
+}
+
+impl Events {
+    /// fails once the frontend dropped the worker
+    fn send(&self,event : Event) -> Result<(),String> {
+        self.sender.send(event).map_err(|e| e.to_string())?;
+        self.waker.wake();
+        Ok(())
+    }
+}
+
+/// Requests sent from the UI to the fetch worker.
+#[derive(Clone,Debug,PartialEq)]
+pub enum Command {
+    /// fetch the current query again
+    Refresh,
+    /// fetch that page of the current query, to add it after the ones already fetched
+    LoadPage(u32),
+    SetApiKey(String),
+    /// the server asked for the news, empty for newsapi.org
+    SetBaseUrl(String),
+    /// replace the current query, the next `Refresh` will use it
+    ChangeQuery(NewsQuery),
+    /// drop the results of the fetch in flight
+    Cancel,
+    /// refresh the current query every that many minutes, `None` to stop
+    SetAutoRefresh(Option<u32>),
+    /// hold the automatic refreshes, while the window is in the background
+    Pause(bool),
+    /// download an article page and extract its readable content
+    FetchPage(String),
+    /// the archived content of an article, as a `PageLoaded` event
+    LoadArchived(String),
+    /// save the article for offline reading, fetching its page unless it is given
+    Archive { article : NewsCardData, document : Option<Document> },
+    Unarchive(String),
+    /// seconds before requests are given up and the proxy they go through, native only
+    SetNetwork { timeout_secs : u32, proxy : String },
+    Shutdown
+}
+
+/// Status reported by the fetch worker, each event carries the query it belongs to.
+#[derive(Clone,Debug,PartialEq)]
+pub enum Event {
+    /// `page` is 1 for a refresh
+    Started { query : NewsQuery, page : u32 },
+    /// `more` when newsapi.org has results past this page
+    Progress { query : NewsQuery, page : u32, articles : Vec<NewsCardData>, more : bool },
+    Finished { query : NewsQuery },
+    Failed { query : NewsQuery, error : FetchError },
+    Cancelled { query : NewsQuery },
+    PageLoaded { url : String, result : Result<Document,FetchError> },
+    Archived { url : String, result : Result<ArchiveEntry,FetchError> }
+}
+
+/// Long lived background worker owning the api key and the current query.
+///
+/// On native it runs on its own thread and every fetch gets a helper thread so that
+/// `Cancel` and `Shutdown` are handled while a request is in flight. On wasm32 commands
+/// are handled right away and fetches run as futures on the browser event loop.
+pub struct Worker {
+    events : Receiver<Event>,
+    waker : Waker,
+    #[cfg(not(target_arch = "wasm32"))]
+    commands : Sender<Command>,
+    #[cfg(not(target_arch = "wasm32"))]
+    handle : Option<JoinHandle<()>>,
+    #[cfg(target_arch = "wasm32")]
+    inner : RefCell<WorkerLoop>
+}
+
+impl Worker {
+    pub fn spawn() -> Worker {
+        let (sender,events) = mpsc::channel();
+        let waker = Waker::default();
+        let event_tx = Events { sender, waker : waker.clone() };
+        let worker_loop = WorkerLoop {
+            api_key : String::new(),
+            base_url : String::new(),
+            query : NewsQuery::default(),
+            events : event_tx.clone(),
+            in_flight : None,
+            auto_refresh : None,
+            paused : false,
+            last_fetch : None,
+            #[cfg(target_arch = "wasm32")]
+            wake_at : None,
+            #[cfg(not(target_arch = "wasm32"))]
+            archiver : spawn_archiver(event_tx)
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let (commands,command_rx) = mpsc::channel();
+            let handle = thread::Builder::new()
+                .name("headlines-worker".to_string())
+                .spawn(move || worker_loop.run(command_rx))
+                .expect("failed spawning the fetch worker");
+            Worker { events, waker, commands, handle : Some(handle) }
+        }
+        #[cfg(target_arch = "wasm32")]
+        Worker { events, waker, inner : RefCell::new(worker_loop) }
+    }
+
+    /// called from any thread as events come in, for frontends waiting for them
+    pub fn set_waker(&self,wake : impl Fn() + Send + Sync + 'static) {
+        self.waker.set(wake);
+    }
+
+    pub fn send(&self,command : Command) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(e) = self.commands.send(command) {
+            tracing::error!("failed sending command to the worker : {}",e);
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.inner.borrow_mut().handle(command);
+    }
+
+    /// events received since the last call, never blocks
+    pub fn poll(&self) -> Vec<Event> {
+        // no thread to wake the worker up on wasm32, the UI frames drive the refreshes
+        #[cfg(target_arch = "wasm32")]
+        self.inner.borrow_mut().tick();
+        self.events.try_iter().collect()
+    }
+
+    /// stop the worker and wait for it to exit, fetches in flight are dropped
+    pub fn shutdown(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(handle) = self.handle.take() {
+            self.send(Command::Shutdown);
+            if handle.join().is_err() {
+                tracing::error!("the fetch worker panicked");
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.send(Command::Shutdown);
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+struct WorkerLoop {
+    api_key : String,
+    base_url : String,
+    query : NewsQuery,
+    events : Events,
+    in_flight : Option<(NewsQuery,Arc<AtomicBool>)>,
+    auto_refresh : Option<Duration>,
+    paused : bool,
+    last_fetch : Option<DateTime<Utc>>,
+    /// when the frontend was asked to wake up for the next automatic refresh
+    #[cfg(target_arch = "wasm32")]
+    wake_at : Option<DateTime<Utc>>,
+    /// archives one article at a time, bookmarks can queue many
+    #[cfg(not(target_arch = "wasm32"))]
+    archiver : Sender<(NewsCardData,Option<Document>)>
+}
+
+impl WorkerLoop {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn run(mut self,commands : Receiver<Command>) {
+        // a closed channel means the UI is gone, same as an explicit shutdown
+        loop {
+            let command = match self.until_refresh() {
+                Some(wait) => match commands.recv_timeout(wait.to_std().unwrap_or_default()) {
+                    Ok(command) => command,
+                    Err(RecvTimeoutError::Timeout) => {
+                        self.tick();
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break
+                },
+                None => match commands.recv() {
+                    Ok(command) => command,
+                    Err(_) => break
+                }
+            };
+            if !self.handle(command) {
+                break;
+            }
+        }
+        self.cancel();
+        tracing::info!("fetch worker stopped");
+    }
+
+    /// returns false once the worker should stop
+    fn handle(&mut self,command : Command) -> bool {
+        match command {
+            Command::Refresh => self.fetch(1),
+            Command::LoadPage(page) => self.fetch(page),
+            Command::SetApiKey(api_key) => self.api_key = api_key,
+            Command::SetBaseUrl(base_url) => self.base_url = base_url,
+            Command::ChangeQuery(query) => self.query = query,
+            Command::Cancel => self.cancel(),
+            Command::SetAutoRefresh(minutes) => {
+                self.auto_refresh = minutes.filter(|m| *m > 0).map(|m| Duration::minutes(m.into()));
+                self.last_fetch.get_or_insert_with(Utc::now);
+            }
+            Command::Pause(paused) => self.paused = paused,
+            Command::FetchPage(url) => self.fetch_page(url),
+            Command::LoadArchived(url) => {
+                let result = archive::load_document(&url);
+                self.emit(Event::PageLoaded { url, result });
+            }
+            Command::Archive { article, document } => self.archive(article, document),
+            Command::Unarchive(url) => archive::delete(&url),
+            #[cfg(not(target_arch = "wasm32"))]
+            Command::SetNetwork { timeout_secs, proxy } => crate::net::configure(timeout_secs, &proxy),
+            // the browser owns the network settings
+            #[cfg(target_arch = "wasm32")]
+            Command::SetNetwork { .. } => {}
+            Command::Shutdown => {
+                self.cancel();
+                return false;
+            }
+        }
+        true
+    }
+
+    /// time left before the next automatic refresh, `None` when there is none to wait for
+    fn until_refresh(&self) -> Option<Duration> {
+        let interval = self.auto_refresh?;
+        if self.paused || self.api_key.is_empty() {
+            return None;
+        }
+        let due = self.last_fetch.unwrap_or_else(Utc::now) + interval;
+        Some((due - Utc::now()).max(Duration::zero()))
+    }
+
+    /// refreshes the current query once the interval elapsed
+    fn tick(&mut self) {
+        let fetching = self.in_flight.as_ref().is_some_and(|(_,done)| !done.load(Ordering::SeqCst));
+        if !fetching && self.until_refresh().is_some_and(|wait| wait <= Duration::zero()) {
+            tracing::info!("automatic refresh");
+            self.fetch(1);
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.schedule_wake();
+    }
+
+    /// wakes the frontend up when the next automatic refresh is due, its frames drive them
+    #[cfg(target_arch = "wasm32")]
+    fn schedule_wake(&mut self) {
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let due = match self.until_refresh() {
+            Some(wait) => Utc::now() + wait,
+            None => return
+        };
+        if self.wake_at.is_some_and(|at| at <= due && at > Utc::now()) {
+            return;
+        }
+        self.wake_at = Some(due);
+        let waker = self.events.waker.clone();
+        let wake = Closure::once_into_js(move || waker.wake());
+        let millis = (due - Utc::now()).num_milliseconds().clamp(0, i32::MAX.into()) as i32;
+        if let Some(window) = web_sys::window() {
+            if let Err(e) = window.set_timeout_with_callback_and_timeout_and_arguments_0(wake.unchecked_ref(), millis) {
+                tracing::warn!("failed scheduling the next refresh : {:?}",e);
+            }
+        }
+    }
+
+    fn cancel(&mut self) {
+        if let Some((query,cancelled)) = self.in_flight.take() {
+            if !cancelled.swap(true, Ordering::SeqCst) {
+                self.emit(Event::Cancelled { query });
+            }
+        }
+    }
+
+    /// fetches a page of the current query, the first one for a refresh
+    fn fetch(&mut self,page : u32) {
+        self.cancel();
+        if self.api_key.is_empty() {
+            self.emit(Event::Failed { query : self.query.clone(), error : FetchError::MissingApiKey });
+            return;
+        }
+        if page <= 1 {
+            self.last_fetch = Some(Utc::now());
+        }
+        let query = self.query.clone();
+        // set by the worker on cancel, and by the fetch once it has reported its result
+        let done = Arc::new(AtomicBool::new(false));
+        self.in_flight = Some((query.clone(),done.clone()));
+        self.emit(Event::Started { query : query.clone(), page });
+
+        let mut request = query.request(&self.api_key);
+        request.page(page).page_size(PAGE_SIZE as u32);
+        if !self.base_url.is_empty() {
+            request.base_url(&self.base_url);
+        }
+        let events = self.events.clone();
+        #[cfg(not(target_arch = "wasm32"))]
+        thread::spawn(move || {
+            let result = fetch_news(request, page);
+            report(result, query, page, &done, &events);
+        });
+        #[cfg(target_arch = "wasm32")]
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = fetch_web(request, page).await;
+            report(result, query, page, &done, &events);
+        });
+    }
+
+    fn fetch_page(&self,url : String) {
+        let events = self.events.clone();
+        #[cfg(not(target_arch = "wasm32"))]
+        thread::spawn(move || {
+            let result = fetch_page(&url);
+            let _ = events.send(Event::PageLoaded { url, result });
+        });
+        #[cfg(target_arch = "wasm32")]
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = fetch_page_web(&url).await;
+            let _ = events.send(Event::PageLoaded { url, result });
+        });
+    }
+
+    fn archive(&self,article : NewsCardData,document : Option<Document>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(e) = self.archiver.send((article,document)) {
+            tracing::error!("failed queueing article for the archive : {}",e);
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let events = self.events.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let url = article.url.clone();
+                let document = match document {
+                    Some(document) => Ok(document),
+                    None => fetch_page_web(&url).await
+                };
+                let result = document.and_then(|document| archive::save(&article, &document));
+                let _ = events.send(Event::Archived { url, result });
+            });
+        }
+    }
+
+    fn emit(&self,event : Event) {
+        if let Err(e) = self.events.send(event) {
+            tracing::warn!("failed sending worker event : {}",e);
+        }
+    }
+}
+
+/// the thread archiving queued articles, it stops with the worker
+#[cfg(not(target_arch = "wasm32"))]
+fn spawn_archiver(events : Events) -> Sender<(NewsCardData,Option<Document>)> {
+    let (jobs,job_rx) = mpsc::channel::<(NewsCardData,Option<Document>)>();
+    let spawned = thread::Builder::new()
+        .name("headlines-archiver".to_string())
+        .spawn(move || {
+            for (article,document) in job_rx {
+                let document = match document {
+                    Some(document) => Ok(document),
+                    None => fetch_page(&article.url)
+                };
+                let result = document.and_then(|document| archive::save(&article, &document));
+                if events.send(Event::Archived { url : article.url, result }).is_err() {
+                    break;
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        tracing::error!("failed spawning the archiver : {}",e);
+    }
+    jobs
+}
+
+/// the articles of a page, and whether newsapi.org has more past it
+type Fetched = (Vec<NewsCardData>,bool);
+
+fn report(result : Result<Fetched,FetchError>,query : NewsQuery,page : u32,done : &AtomicBool,events : &Events) {
+    if done.swap(true, Ordering::SeqCst) {
+        // cancelled while the request was in flight
+        return;
+    }
+    let outcome = match result {
+        Ok((mut articles, more)) => {
+            if !query.is_search() {
+                for article in &mut articles {
+                    article.category = query.category;
+                }
+            }
+            vec![Event::Progress { query : query.clone(), page, articles, more }, Event::Finished { query }]
+        }
+        Err(error) => vec![Event::Failed { query, error }]
+    };
+    for event in outcome {
+        if let Err(e) = events.send(event) {
+            tracing::warn!("failed sending worker event : {}",e);
+            break;
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn fetch_news(mut request : NewsApi,page : u32) -> Result<Fetched,FetchError> {
+    let response = request.agent(crate::net::agent()).fetch()?;
+    Ok(fetched(&response, page))
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn fetch_web(request : NewsApi,page : u32) -> Result<Fetched,FetchError> {
+    let response = request.fetch_web().await?;
+    Ok(fetched(&response, page))
+}
+
+/// a full page with results left past it, as newsapi.org counts them, means there are more
+fn fetched(response : &NewsApiResponse,page : u32) -> Fetched {
+    let articles : Vec<NewsCardData> = response.articles().iter().map(NewsCardData::from).collect();
+    let more = articles.len() >= PAGE_SIZE
+        && response.total_results().is_none_or(|total| total as usize > page as usize * PAGE_SIZE);
+    (articles, more)
+}
+
+fn readable(html : &str,url : &str) -> Result<Document,FetchError> {
+    let document = extract::extract(html, Some(url));
+    if document.has_content() {
+        Ok(document)
+    } else {
+        Err(FetchError::InvalidResponse("no readable content on this page".to_string()))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn fetch_page(url : &str) -> Result<Document,FetchError> {
+    let html = crate::net::agent().get(url)
+        .call()
+        .map_err(|e| FetchError::Network(e.to_string()))?
+        .into_string()
+        .map_err(|e| FetchError::InvalidResponse(e.to_string()))?;
+    readable(&html, url)
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn fetch_page_web(url : &str) -> Result<Document,FetchError> {
+    // most sites do not allow cross origin requests, the reader then falls back to the link
+    let response = reqwasm::http::Request::get(url).send().await
+        .map_err(|e| FetchError::Network(e.to_string()))?;
+    if !response.ok() {
+        return Err(FetchError::Network(format!("status {}",response.status())));
+    }
+    let html = response.text().await.map_err(|e| FetchError::InvalidResponse(e.to_string()))?;
+    readable(&html, url)
+}
@@ -0,0 +1,53 @@
+//! The readable content of saved article pages, from `tests/pages`.
+
+use headlines::{extract, plain_text, Block, Document};
+
+fn page(name : &str,url : &str) -> Document {
+    let path = format!("{}/tests/pages/{}",env!("CARGO_MANIFEST_DIR"),name);
+    let html = std::fs::read_to_string(&path).expect("missing test page");
+    extract(&html, Some(url))
+}
+
+/// the text of the blocks, one line each
+fn text(document : &Document) -> String {
+    document.blocks.iter()
+        .filter_map(|block| match block {
+            Block::Heading { spans, .. } | Block::Paragraph(spans) | Block::Quote(spans) | Block::ListItem(spans) => Some(plain_text(spans)),
+            Block::Code(code) => Some(code.clone()),
+            Block::Image { .. } => None
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn keeps_a_lead_story_and_drops_the_chrome_around_it() {
+    let document = page("lead_story.html", "https://news.example.com/2024/tram");
+    let text = text(&document);
+    assert_eq!(document.title, "City council approves the new tram line");
+    assert_eq!(document.byline.as_deref(), Some("Jane Doe"));
+    assert_eq!(document.lead_image.as_deref(), Some("https://news.example.com/images/tram.jpg"));
+    assert!(text.contains("voted on Tuesday to approve the long debated tram line"));
+    assert!(text.contains("Construction is expected to start next spring"));
+    assert!(text.contains("better spent on buses"));
+    assert!(document.blocks.iter().any(|b| matches!(b, Block::Image { src, .. } if src == "https://news.example.com/images/route.png")));
+    for chrome in ["Advertisement", "Share this article", "First comment", "Subscribe to our newsletter", "Copyright"] {
+        assert!(!text.contains(chrome), "{} was extracted",chrome);
+    }
+}
+
+#[test]
+fn keeps_a_shared_text_body_and_drops_its_related_posts() {
+    let document = page("shared_text.html", "https://blog.example.org/caching");
+    let text = text(&document);
+    assert_eq!(document.title, "Notes on caching proxies");
+    assert!(text.contains("Why cache at all"));
+    assert!(text.contains("A caching proxy keeps the responses"));
+    assert!(text.contains("The hard part is invalidation"));
+    assert!(text.contains("Cache-Control: max-age=600"));
+    assert!(text.contains("Log the hit rate"));
+    for chrome in ["About me", "Related posts", "We use cookies"] {
+        assert!(!text.contains(chrome), "{} was extracted",chrome);
+    }
+    assert!(document.has_content());
+}
@@ -0,0 +1,241 @@
+use headlines::{Action, AppState, Category, Effect, NewsCardData, Overrides, Worker};
+use ratatui::{crossterm::event::{KeyCode, KeyEvent, KeyModifiers}, widgets::ListState};
+
+#[derive(PartialEq)]
+pub enum Mode {
+    Normal,
+    Search,
+    ApiKey
+}
+
+/// terminal view over the shared [`AppState`]
+pub struct App {
+    pub state : AppState,
+    pub list_state : ListState,
+    pub mode : Mode,
+    pub search : String,
+    pub input : String,
+    pub status : String,
+    pub quit : bool,
+    worker : Worker
+}
+
+impl App {
+    pub fn new(overrides : Overrides) -> App {
+        let state = AppState::load(overrides);
+        let mode = if state.api_key_initialized { Mode::Normal } else { Mode::ApiKey };
+        let mut app = App {
+            state,
+            list_state : ListState::default(),
+            mode,
+            search : String::new(),
+            input : String::new(),
+            status : String::new(),
+            quit : false,
+            worker : Worker::spawn()
+        };
+        let effects = app.state.init();
+        app.run_effects(effects);
+        app
+    }
+
+    pub fn dispatch(&mut self,action : Action) {
+        let effects = self.state.update(action);
+        self.run_effects(effects);
+    }
+
+    fn run_effects(&mut self,effects : Vec<Effect>) {
+        for effect in effects {
+            match effect {
+                Effect::Worker(command) => self.worker.send(command),
+                Effect::StoreConfig => self.state.store_config(),
+                Effect::StoreBookmarks => self.state.bookmarks.store(),
+                Effect::StoreHistory => self.state.history.store(),
+                Effect::StoreArchive => self.state.archive.store(),
+                Effect::StoreAlerts => self.state.alerts.store(),
+                Effect::Notify { summary, body } => headlines::notify(&summary, &body)
+            }
+        }
+    }
+
+    /// apply the events reported by the worker since the last frame
+    pub fn preload_articles(&mut self) {
+        let events = self.worker.poll();
+        if events.is_empty() {
+            return;
+        }
+        for event in events {
+            self.dispatch(Action::Worker(event));
+        }
+        if !self.state.api_key_initialized {
+            // the key was rejected, ask for another one
+            self.mode = Mode::ApiKey;
+        }
+        self.status = if self.state.loading() {
+            "Loading ⏳".to_string()
+        } else if let Some(error) = self.state.error() {
+            error.to_string()
+        } else {
+            format!("{} articles",self.state.articles().len())
+        };
+        let len = self.visible().len();
+        if self.list_state.selected().is_none_or(|i| i >= len) {
+            self.select_first();
+        }
+    }
+
+    pub fn shutdown(&mut self) {
+        self.worker.shutdown();
+    }
+
+    /// indices of the articles matching the current search
+    pub fn visible(&self) -> Vec<usize> {
+        let needle = self.search.to_lowercase();
+        self.state.articles().iter().enumerate()
+            .filter(|(_,a)| needle.is_empty()
+                || a.title.to_lowercase().contains(&needle)
+                || a.desc.to_lowercase().contains(&needle))
+            .map(|(i,_)| i)
+            .collect()
+    }
+
+    pub fn selected(&self) -> Option<&NewsCardData> {
+        let visible = self.visible();
+        self.list_state.selected()
+            .and_then(|i| visible.get(i))
+            .map(|&i| &self.state.articles()[i])
+    }
+
+    pub fn on_key(&mut self,key : KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return;
+        }
+        match self.mode {
+            Mode::Normal => self.on_normal_key(key.code),
+            Mode::Search => self.on_search_key(key.code),
+            Mode::ApiKey => self.on_api_key_key(key.code)
+        }
+    }
+
+    fn on_normal_key(&mut self,code : KeyCode) {
+        match code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Char('c') => self.dispatch(Action::Cancel),
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::Char('g') | KeyCode::Home => self.select_first(),
+            KeyCode::Char('G') | KeyCode::End => self.select_last(),
+            KeyCode::Enter | KeyCode::Char('o') => self.open_selected(),
+            KeyCode::Char('r') => self.dispatch(Action::Refresh),
+            KeyCode::Char('s') => self.toggle_bookmark(),
+            KeyCode::Char('m') => self.dispatch(Action::MarkAllRead),
+            KeyCode::Char('a') => self.toggle_archived(),
+            KeyCode::Char('h') => self.dispatch(Action::ToggleShowHidden),
+            KeyCode::Tab => self.cycle_category(1),
+            KeyCode::BackTab => self.cycle_category(-1),
+            KeyCode::Char('/') => {
+                self.input = self.search.clone();
+                self.mode = Mode::Search;
+            }
+            KeyCode::Esc => {
+                self.search.clear();
+                self.select_first();
+            }
+            _ => {}
+        }
+    }
+
+    fn on_search_key(&mut self,code : KeyCode) {
+        match code {
+            KeyCode::Enter => {
+                self.search = self.input.trim().to_string();
+                self.mode = Mode::Normal;
+                self.select_first();
+            }
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Backspace => { self.input.pop(); }
+            KeyCode::Char(c) => self.input.push(c),
+            _ => {}
+        }
+    }
+
+    fn on_api_key_key(&mut self,code : KeyCode) {
+        match code {
+            KeyCode::Enter if !self.input.trim().is_empty() => {
+                let api_key = std::mem::take(&mut self.input);
+                self.mode = Mode::Normal;
+                self.dispatch(Action::ApiKeySet(api_key));
+            }
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Backspace => { self.input.pop(); }
+            KeyCode::Char(c) => self.input.push(c),
+            _ => {}
+        }
+    }
+
+    /// switch to the next top headlines section, cached sections show up right away
+    fn cycle_category(&mut self,delta : isize) {
+        let tabs : Vec<Option<Category>> = std::iter::once(None).chain(Category::ALL.into_iter().map(Some)).collect();
+        let current = tabs.iter().position(|c| *c == self.state.query.category).unwrap_or(0) as isize;
+        let next = tabs[(current + delta).rem_euclid(tabs.len() as isize) as usize];
+        let query = self.state.query.with_category(next);
+        self.dispatch(Action::ChangeQuery(query));
+        self.search.clear();
+        self.select_first();
+    }
+
+    fn move_selection(&mut self,delta : isize) {
+        let len = self.visible().len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).clamp(0, len as isize - 1);
+        self.list_state.select(Some(next as usize));
+        // the next page once the selection reaches the end of the list
+        if next as usize + 1 >= len {
+            self.dispatch(Action::LoadMore);
+        }
+    }
+
+    fn select_first(&mut self) {
+        let first = if self.visible().is_empty() { None } else { Some(0) };
+        self.list_state.select(first);
+    }
+
+    fn select_last(&mut self) {
+        let len = self.visible().len();
+        self.list_state.select(len.checked_sub(1));
+    }
+
+    fn toggle_bookmark(&mut self) {
+        if let Some(article) = self.selected().cloned() {
+            self.dispatch(Action::ToggleBookmark(article));
+            self.status = format!("{} saved articles",self.state.bookmarks.len());
+        }
+    }
+
+    fn toggle_archived(&mut self) {
+        if let Some(article) = self.selected().cloned() {
+            if self.state.archive.contains(&article.url) {
+                self.dispatch(Action::Unarchive(article.url));
+            } else {
+                self.dispatch(Action::Archive(article));
+                self.status = "archiving…".to_string();
+            }
+        }
+    }
+
+    fn open_selected(&mut self) {
+        if let Some(url) = self.selected().map(|a| a.url.to_string()) {
+            self.dispatch(Action::MarkRead(url.clone()));
+            self.status = match open::that_detached(&url) {
+                Ok(()) => format!("opened {}",url),
+                Err(e) => format!("failed opening {} : {}",url,e)
+            };
+        }
+    }
+}
@@ -0,0 +1,53 @@
+mod app;
+mod ui;
+
+use std::{io, time::Duration};
+
+use ratatui::crossterm::{event::{self, Event, KeyEventKind, EnableFocusChange, DisableFocusChange}, execute};
+use headlines::{Action, Overrides, USAGE};
+
+use crate::app::App;
+
+fn main() -> io::Result<()> {
+    // parsed before the terminal is taken over, for the errors to show
+    let overrides = match Overrides::load(std::env::args_os().skip(1)) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("{}\n\nUsage : headlines_tui [OPTIONS]\n\n{}",e,USAGE);
+            std::process::exit(2);
+        }
+    };
+    if overrides.help {
+        print!("Usage : headlines_tui [OPTIONS]\n\n{}",USAGE);
+        return Ok(());
+    }
+    let mut terminal = ratatui::init();
+    // pauses the automatic refreshes while the terminal is in the background,
+    // terminals without focus reporting just never send the events
+    if let Err(e) = execute!(io::stdout(), EnableFocusChange) {
+        ratatui::restore();
+        return Err(e);
+    }
+    let mut app = App::new(overrides);
+    let result = run(&mut terminal, &mut app);
+    let _ = execute!(io::stdout(), DisableFocusChange);
+    ratatui::restore();
+    app.shutdown();
+    result
+}
+
+fn run(terminal : &mut ratatui::DefaultTerminal,app : &mut App) -> io::Result<()> {
+    while !app.quit {
+        app.preload_articles();
+        terminal.draw(|frame| ui::draw(frame, app))?;
+        if event::poll(Duration::from_millis(100))? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => app.on_key(key),
+                Event::FocusGained => app.dispatch(Action::SetFocused(true)),
+                Event::FocusLost => app.dispatch(Action::SetFocused(false)),
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
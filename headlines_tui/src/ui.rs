@@ -0,0 +1,149 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::{App, Mode};
+
+const CYAN : Color = Color::Rgb(0, 250, 250);
+
+pub fn draw(frame : &mut Frame,app : &mut App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(1)])
+        .split(frame.area());
+
+    render_header(frame, rows[0], app);
+    if app.mode == Mode::ApiKey {
+        render_api_key(frame, rows[1], app);
+    } else {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(rows[1]);
+        render_list(frame, columns[0], app);
+        render_detail(frame, columns[1], app);
+    }
+    render_footer(frame, rows[2], app);
+}
+
+fn render_header(frame : &mut Frame,area : Rect,app : &App) {
+    let query = &app.state.query;
+    let section = match query.category {
+        _ if query.is_search() => format!("search: {}",query.keywords),
+        Some(category) => category.name().to_string(),
+        None => "Top".to_string()
+    };
+    let mut spans = vec![
+        Span::styled("📓 headlines", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(format!("  {} · {}",section,query.country.name())),
+        Span::raw(format!("  {} unread",app.state.unread_count()))
+    ];
+    let hidden = app.state.hidden_count();
+    if hidden > 0 {
+        let label = if app.state.show_hidden { "shown" } else { "hidden" };
+        spans.push(Span::styled(format!("  🔇 {} {}",hidden,label), Style::default().add_modifier(Modifier::DIM)));
+    }
+    let alerts = app.state.alerts.unread(None);
+    if alerts > 0 {
+        spans.push(Span::styled(format!("  🔔 {} alerts",alerts), Style::default().fg(Color::Magenta)));
+    }
+    if app.state.config.offline {
+        spans.push(Span::styled("  ✈ offline", Style::default().fg(Color::Yellow)));
+    }
+    if !app.search.is_empty() {
+        spans.push(Span::raw(format!("  search: {}",app.search)));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn render_list(frame : &mut Frame,area : Rect,app : &mut App) {
+    if app.state.articles().is_empty() && !app.state.loading() {
+        let message = match app.state.error() {
+            Some(error) => Line::from(Span::styled(format!("⚠ {}  (r: retry)",error), Style::default().fg(Color::Red))),
+            None => Line::from("No articles found  (r: refresh)")
+        };
+        let block = Block::default().borders(Borders::ALL).title(" Articles ");
+        frame.render_widget(Paragraph::new(message).block(block).wrap(Wrap { trim: true }), area);
+        return;
+    }
+    let items : Vec<ListItem> = app.visible().into_iter()
+        .map(|i| {
+            let article = &app.state.articles()[i];
+            let history = &app.state.history;
+            let marker = if app.state.bookmarks.contains(&article.url) { "★" } else if history.is_read(&article.url) { "▫" } else { "🔹" };
+            let mut spans = vec![Span::raw(format!("{} ",marker))];
+            if app.state.archive.contains(&article.url) {
+                spans.push(Span::raw("💾 "));
+            }
+            if history.is_new(&article.url) {
+                spans.push(Span::styled("NEW ", Style::default().fg(Color::Yellow)));
+            }
+            let style = if history.is_read(&article.url) { Style::default().add_modifier(Modifier::DIM) } else { Style::default() };
+            spans.push(Span::styled(article.title.as_str(), style));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let feed = app.state.feed();
+    let title = if app.state.loading() {
+        " Loading ⏳ ".to_string()
+    } else if feed.loading_more {
+        format!(" Articles ({}) · loading more ⏳ ",items.len())
+    } else if feed.is_full() {
+        format!(" Articles ({}) · the latest ones kept, end of the feed ",items.len())
+    } else if !feed.more {
+        format!(" Articles ({}) · end of the feed ",items.len())
+    } else {
+        format!(" Articles ({}) ",items.len())
+    };
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().fg(Color::Black).bg(CYAN));
+    frame.render_stateful_widget(list, area, &mut app.list_state);
+}
+
+fn render_detail(frame : &mut Frame,area : Rect,app : &App) {
+    let block = Block::default().borders(Borders::ALL).title(" Detail ");
+    let text = match app.selected() {
+        Some(a) => vec![
+            Line::from(Span::styled(a.title.as_str(), Style::default().add_modifier(Modifier::BOLD))),
+            Line::from(""),
+            Line::from(a.desc.as_str()),
+            Line::from(""),
+            Line::from(Span::styled(a.url.as_str(), Style::default().fg(CYAN).add_modifier(Modifier::UNDERLINED))),
+        ],
+        None => vec![Line::from("No article selected")]
+    };
+    frame.render_widget(Paragraph::new(text).block(block).wrap(Wrap { trim: true }), area);
+}
+
+fn render_api_key(frame : &mut Frame,area : Rect,app : &App) {
+    let mut text = vec![];
+    if let Some(error) = &app.state.key_error {
+        text.push(Line::from(Span::styled(format!("⚠ {}",error), Style::default().fg(Color::Red))));
+        text.push(Line::from(""));
+    }
+    text.extend([
+        Line::from("Enter your API_KEY for newsapi.org"),
+        Line::from("If you havn-t registered for the API_KEY,head over to https://newsapi.org"),
+        Line::from(""),
+        Line::from(format!("> {}",app.input)),
+    ]);
+    let block = Block::default().borders(Borders::ALL).title(" Configuration ");
+    frame.render_widget(Paragraph::new(text).block(block).wrap(Wrap { trim: false }), area);
+}
+
+fn render_footer(frame : &mut Frame,area : Rect,app : &App) {
+    let line = match app.mode {
+        Mode::Search => format!("/{}",app.input),
+        Mode::ApiKey => "enter: save  esc: quit".to_string(),
+        Mode::Normal => format!(
+            "j/k: move  tab: section  enter/o: open  s: save  a: archive  m: all read  h: hidden  /: search  r: refresh  c: cancel  q: quit   {}",
+            app.status
+        )
+    };
+    frame.render_widget(Paragraph::new(line).style(Style::default().add_modifier(Modifier::DIM)), area);
+}
@@ -1,5 +1,7 @@
+#![allow(non_snake_case)]
 #[cfg(feature = "async")]
 use reqwest::Method;
+use std::fmt;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
@@ -7,8 +9,8 @@ const BASE_URL : &str =  "https://newsapi.org/v2";
 
 #[derive(thiserror::Error,Debug)]
 pub enum NewsApiError{
-     #[error("Failed fectching articles")]
-     RequestFailed(#[from] ureq::Error),
+     #[error("Failed fectching articles: {0}")]
+     RequestFailed(Box<ureq::Error>),
      #[error("Failed to convert response to String")]
      FailedToresponseToString(#[from] std::io::Error),
      #[error("Article Parsing Failed")]
@@ -17,27 +19,73 @@ pub enum NewsApiError{
      UrlParsing(#[from] url::ParseError),
      #[error("Request failed: {0}")]
      BadRequest(&'static str),
+     #[error("{message}")]
+     Api{ code: String, message: String },
      #[error("Async request failed")]
      #[cfg(feature = "async")]
      AsyncRequestFailed(#[from] reqwest::Error)
 } 
+impl From<ureq::Error> for NewsApiError {
+    fn from(e: ureq::Error) -> Self {
+        NewsApiError::RequestFailed(Box::new(e))
+    }
+}
+impl NewsApiError {
+    /// the error code returned by newsapi.org, like `apiKeyInvalid` or `rateLimited`
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            NewsApiError::Api { code, .. } => Some(code),
+            _ => None
+        }
+    }
+}
 #[derive(Deserialize,Debug)]
 pub struct NewsApiResponse {
     status: String,
+    #[serde(default)]
     pub articles : Vec<Article>,
-    code:Option<String>
+    #[serde(rename = "totalResults")]
+    total_results : Option<u32>,
+    code:Option<String>,
+    message:Option<String>
 }
 impl NewsApiResponse {
     pub fn articles(&self) -> &Vec<Article> {
         &self.articles
     }
+    /// results of the query across all its pages
+    pub fn total_results(&self) -> Option<u32> {
+        self.total_results
+    }
+}
+
+#[derive(Serialize,Deserialize,Debug)]
+pub struct Source{
+    id : Option<String>,
+    #[serde(default)]
+    name : String
+}
+
+impl Source {
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 #[derive(Serialize,Deserialize,Debug)]
 pub struct Article{
+    source : Option<Source>,
+    #[serde(default)]
     title : String,
     url: String,
-    description : String
+    description : Option<String>,
+    #[serde(rename = "urlToImage")]
+    url_to_image : Option<String>,
+    #[serde(rename = "publishedAt")]
+    published_at : Option<String>
 }
 
 impl Article {
@@ -47,8 +95,18 @@ impl Article {
     pub fn url(&self) -> &str {
         &self.url
     }
-    pub fn desc(&self) -> &str{
-        &self.description
+    pub fn desc(&self) -> Option<&str>{
+        self.description.as_deref()
+    }
+    pub fn url_to_image(&self) -> Option<&str>{
+        self.url_to_image.as_deref()
+    }
+    pub fn source(&self) -> Option<&Source>{
+        self.source.as_ref()
+    }
+    /// RFC 3339, like `2022-09-20T14:05:00Z`
+    pub fn published_at(&self) -> Option<&str>{
+        self.published_at.as_deref()
     }
 }
 
@@ -59,25 +117,264 @@ impl Article {
 
     Ok(articles)
 } */
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash,Serialize,Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Endpoint{
-    TopHeadlines
+    TopHeadlines,
+    Everything
 }
 
-impl ToString for Endpoint{
-    fn to_string(&self) -> String {
+impl fmt::Display for Endpoint{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::TopHeadlines => "top-headlines".to_string()
+            Self::TopHeadlines => write!(f, "top-headlines"),
+            Self::Everything => write!(f, "everything")
         }
     }
 }
 
+/// Countries supported by the `top-headlines` endpoint.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash,Serialize,Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Country{
-    Us
+    Ae, Ar, At, Au, Be, Bg, Br, Ca, Ch, Cn,
+    Co, Cu, Cz, De, Eg, Fr, Gb, Gr, Hk, Hu,
+    Id, Ie, Il, In, It, Jp, Kr, Lt, Lv, Ma,
+    Mx, My, Ng, Nl, No, Nz, Ph, Pl, Pt, Ro,
+    Rs, Ru, Sa, Se, Sg, Si, Sk, Th, Tr, Tw,
+    Ua, Us, Ve, Za
 }
-impl ToString for Country{
-    fn to_string(&self) -> String {
-        match  self {
-            Self::Us => "us".to_string()
+impl Country {
+    pub const ALL : [Country;54] = [
+        Country::Ae, Country::Ar, Country::At, Country::Au, Country::Be, Country::Bg, Country::Br, Country::Ca,
+        Country::Ch, Country::Cn, Country::Co, Country::Cu, Country::Cz, Country::De, Country::Eg, Country::Fr,
+        Country::Gb, Country::Gr, Country::Hk, Country::Hu, Country::Id, Country::Ie, Country::Il, Country::In,
+        Country::It, Country::Jp, Country::Kr, Country::Lt, Country::Lv, Country::Ma, Country::Mx, Country::My,
+        Country::Ng, Country::Nl, Country::No, Country::Nz, Country::Ph, Country::Pl, Country::Pt, Country::Ro,
+        Country::Rs, Country::Ru, Country::Sa, Country::Se, Country::Sg, Country::Si, Country::Sk, Country::Th,
+        Country::Tr, Country::Tw, Country::Ua, Country::Us, Country::Ve, Country::Za
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Ae => "ae",
+            Self::Ar => "ar",
+            Self::At => "at",
+            Self::Au => "au",
+            Self::Be => "be",
+            Self::Bg => "bg",
+            Self::Br => "br",
+            Self::Ca => "ca",
+            Self::Ch => "ch",
+            Self::Cn => "cn",
+            Self::Co => "co",
+            Self::Cu => "cu",
+            Self::Cz => "cz",
+            Self::De => "de",
+            Self::Eg => "eg",
+            Self::Fr => "fr",
+            Self::Gb => "gb",
+            Self::Gr => "gr",
+            Self::Hk => "hk",
+            Self::Hu => "hu",
+            Self::Id => "id",
+            Self::Ie => "ie",
+            Self::Il => "il",
+            Self::In => "in",
+            Self::It => "it",
+            Self::Jp => "jp",
+            Self::Kr => "kr",
+            Self::Lt => "lt",
+            Self::Lv => "lv",
+            Self::Ma => "ma",
+            Self::Mx => "mx",
+            Self::My => "my",
+            Self::Ng => "ng",
+            Self::Nl => "nl",
+            Self::No => "no",
+            Self::Nz => "nz",
+            Self::Ph => "ph",
+            Self::Pl => "pl",
+            Self::Pt => "pt",
+            Self::Ro => "ro",
+            Self::Rs => "rs",
+            Self::Ru => "ru",
+            Self::Sa => "sa",
+            Self::Se => "se",
+            Self::Sg => "sg",
+            Self::Si => "si",
+            Self::Sk => "sk",
+            Self::Th => "th",
+            Self::Tr => "tr",
+            Self::Tw => "tw",
+            Self::Ua => "ua",
+            Self::Us => "us",
+            Self::Ve => "ve",
+            Self::Za => "za",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ae => "United Arab Emirates",
+            Self::Ar => "Argentina",
+            Self::At => "Austria",
+            Self::Au => "Australia",
+            Self::Be => "Belgium",
+            Self::Bg => "Bulgaria",
+            Self::Br => "Brazil",
+            Self::Ca => "Canada",
+            Self::Ch => "Switzerland",
+            Self::Cn => "China",
+            Self::Co => "Colombia",
+            Self::Cu => "Cuba",
+            Self::Cz => "Czechia",
+            Self::De => "Germany",
+            Self::Eg => "Egypt",
+            Self::Fr => "France",
+            Self::Gb => "United Kingdom",
+            Self::Gr => "Greece",
+            Self::Hk => "Hong Kong",
+            Self::Hu => "Hungary",
+            Self::Id => "Indonesia",
+            Self::Ie => "Ireland",
+            Self::Il => "Israel",
+            Self::In => "India",
+            Self::It => "Italy",
+            Self::Jp => "Japan",
+            Self::Kr => "South Korea",
+            Self::Lt => "Lithuania",
+            Self::Lv => "Latvia",
+            Self::Ma => "Morocco",
+            Self::Mx => "Mexico",
+            Self::My => "Malaysia",
+            Self::Ng => "Nigeria",
+            Self::Nl => "Netherlands",
+            Self::No => "Norway",
+            Self::Nz => "New Zealand",
+            Self::Ph => "Philippines",
+            Self::Pl => "Poland",
+            Self::Pt => "Portugal",
+            Self::Ro => "Romania",
+            Self::Rs => "Serbia",
+            Self::Ru => "Russia",
+            Self::Sa => "Saudi Arabia",
+            Self::Se => "Sweden",
+            Self::Sg => "Singapore",
+            Self::Si => "Slovenia",
+            Self::Sk => "Slovakia",
+            Self::Th => "Thailand",
+            Self::Tr => "Turkey",
+            Self::Tw => "Taiwan",
+            Self::Ua => "Ukraine",
+            Self::Us => "United States",
+            Self::Ve => "Venezuela",
+            Self::Za => "South Africa",
+        }
+    }
+}
+impl fmt::Display for Country{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Sections of the `top-headlines` endpoint.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash,Serialize,Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category{
+    Business,
+    Entertainment,
+    General,
+    Health,
+    Science,
+    Sports,
+    Technology
+}
+impl Category {
+    pub const ALL : [Category;7] = [
+        Category::Business, Category::Entertainment, Category::General, Category::Health,
+        Category::Science, Category::Sports, Category::Technology
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Business => "Business",
+            Self::Entertainment => "Entertainment",
+            Self::General => "General",
+            Self::Health => "Health",
+            Self::Science => "Science",
+            Self::Sports => "Sports",
+            Self::Technology => "Technology"
+        }
+    }
+}
+impl fmt::Display for Category{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name().to_lowercase())
+    }
+}
+
+/// Languages supported by the `everything` endpoint.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash,Serialize,Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language{
+    Ar, De, En, Es, Fr, He, It, Nl, No, Pt, Ru, Sv, Ud, Zh
+}
+impl Language {
+    pub const ALL : [Language;14] = [
+        Language::Ar, Language::De, Language::En, Language::Es, Language::Fr, Language::He, Language::It,
+        Language::Nl, Language::No, Language::Pt, Language::Ru, Language::Sv, Language::Ud, Language::Zh
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Ar => "ar", Self::De => "de", Self::En => "en", Self::Es => "es", Self::Fr => "fr",
+            Self::He => "he", Self::It => "it", Self::Nl => "nl", Self::No => "no", Self::Pt => "pt",
+            Self::Ru => "ru", Self::Sv => "sv", Self::Ud => "ud", Self::Zh => "zh"
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ar => "Arabic", Self::De => "German", Self::En => "English", Self::Es => "Spanish",
+            Self::Fr => "French", Self::He => "Hebrew", Self::It => "Italian", Self::Nl => "Dutch",
+            Self::No => "Norwegian", Self::Pt => "Portuguese", Self::Ru => "Russian", Self::Sv => "Swedish",
+            Self::Ud => "Urdu", Self::Zh => "Chinese"
+        }
+    }
+}
+impl fmt::Display for Language{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Ordering of the `everything` results.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash,Serialize,Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortBy{
+    Relevancy,
+    Popularity,
+    PublishedAt
+}
+impl SortBy {
+    pub const ALL : [SortBy;3] = [SortBy::PublishedAt, SortBy::Relevancy, SortBy::Popularity];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Relevancy => "Relevancy",
+            Self::Popularity => "Popularity",
+            Self::PublishedAt => "Newest first"
+        }
+    }
+}
+impl fmt::Display for SortBy{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Relevancy => write!(f, "relevancy"),
+            Self::Popularity => write!(f, "popularity"),
+            Self::PublishedAt => write!(f, "publishedAt")
         }
     }
 }
@@ -85,14 +382,38 @@ impl ToString for Country{
 pub struct NewsApi{
     api_key: String,
     endpoint: Endpoint,
-    country : Country
+    country : Country,
+    category : Option<Category>,
+    query : Option<String>,
+    language : Option<Language>,
+    sort_by : Option<SortBy>,
+    from : Option<String>,
+    to : Option<String>,
+    sources : Option<String>,
+    domains : Option<String>,
+    page : Option<u32>,
+    page_size : Option<u32>,
+    base_url : Option<String>,
+    agent : Option<ureq::Agent>
 }
 impl NewsApi {
     pub fn new(api_key:&str) -> NewsApi {
         NewsApi { 
             api_key: api_key.to_uppercase(), 
             endpoint: Endpoint::TopHeadlines, 
-            country: Country::Us
+            country: Country::Us,
+            category: None,
+            query: None,
+            language: None,
+            sort_by: None,
+            from: None,
+            to: None,
+            sources: None,
+            domains: None,
+            page: None,
+            page_size: None,
+            base_url: None,
+            agent: None
         }
     }
 
@@ -106,65 +427,163 @@ impl NewsApi {
         self
     }
 
+    /// only used by [`Endpoint::TopHeadlines`]
+    pub fn category(&mut self,category:Category) -> &mut NewsApi {
+        self.category = Some(category);
+        self
+    }
+
+    /// keywords or phrase to search for, supports the newsapi.org `AND`/`OR`/`NOT` syntax
+    pub fn query(&mut self,query:&str) -> &mut NewsApi {
+        self.query = Some(query.to_string());
+        self
+    }
+
+    /// only used by [`Endpoint::Everything`]
+    pub fn language(&mut self,language:Language) -> &mut NewsApi {
+        self.language = Some(language);
+        self
+    }
+
+    /// only used by [`Endpoint::Everything`]
+    pub fn sort_by(&mut self,sort_by:SortBy) -> &mut NewsApi {
+        self.sort_by = Some(sort_by);
+        self
+    }
+
+    /// oldest article date, as `YYYY-MM-DD` or a full ISO 8601 date time
+    pub fn from(&mut self,from:&str) -> &mut NewsApi {
+        self.from = Some(from.to_string());
+        self
+    }
+
+    /// newest article date, as `YYYY-MM-DD` or a full ISO 8601 date time
+    pub fn to(&mut self,to:&str) -> &mut NewsApi {
+        self.to = Some(to.to_string());
+        self
+    }
+
+    /// comma separated source ids, like `bbc-news,the-verge`
+    pub fn sources(&mut self,sources:&str) -> &mut NewsApi {
+        self.sources = Some(sources.to_string());
+        self
+    }
+
+    /// comma separated domains, like `bbc.co.uk,techcrunch.com`
+    pub fn domains(&mut self,domains:&str) -> &mut NewsApi {
+        self.domains = Some(domains.to_string());
+        self
+    }
+
+    /// which page of the results, starting at 1
+    pub fn page(&mut self,page:u32) -> &mut NewsApi {
+        self.page = Some(page);
+        self
+    }
+
+    /// results per page, newsapi.org returns 20 by default and 100 at most
+    pub fn page_size(&mut self,page_size:u32) -> &mut NewsApi {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// another server speaking the newsapi.org api, like a mirror or a caching proxy,
+    /// `https://newsapi.org/v2` by default
+    pub fn base_url(&mut self,base_url:&str) -> &mut NewsApi {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// http agent used by [`NewsApi::fetch`], for its timeouts and proxy
+    pub fn agent(&mut self,agent:ureq::Agent) -> &mut NewsApi {
+        self.agent = Some(agent);
+        self
+    }
+
     fn prepare_url(&self) -> Result<String,NewsApiError> {
-        let mut url = Url::parse(BASE_URL)?;
-        url.path_segments_mut().unwrap().push(&self.endpoint.to_string());
-        let country = format!("country={}",&self.country.to_string());
-        url.set_query(Some(&country));
+        let mut url = Url::parse(self.base_url.as_deref().unwrap_or(BASE_URL))?;
+        url.path_segments_mut()
+            .map_err(|_| NewsApiError::BadRequest("the base url cannot have paths"))?
+            .pop_if_empty()
+            .push(&self.endpoint.to_string());
+        {
+            let mut params = url.query_pairs_mut();
+            if let Some(query) = &self.query {
+                params.append_pair("q", query);
+            }
+            match self.endpoint {
+                Endpoint::TopHeadlines => {
+                    // newsapi.org refuses sources mixed with a country
+                    match &self.sources {
+                        Some(sources) => params.append_pair("sources", sources),
+                        None => params.append_pair("country", &self.country.to_string())
+                    };
+                    if let (Some(category), None) = (self.category, &self.sources) {
+                        params.append_pair("category", &category.to_string());
+                    }
+                }
+                Endpoint::Everything => {
+                    let optional = [
+                        ("language", self.language.map(|l| l.to_string())),
+                        ("sortBy", self.sort_by.map(|s| s.to_string())),
+                        ("from", self.from.clone()),
+                        ("to", self.to.clone()),
+                        ("sources", self.sources.clone()),
+                        ("domains", self.domains.clone())
+                    ];
+                    for (name, value) in optional {
+                        if let Some(value) = value {
+                            params.append_pair(name, &value);
+                        }
+                    }
+                }
+            }
+            if let Some(page) = self.page {
+                params.append_pair("page", &page.to_string());
+            }
+            if let Some(page_size) = self.page_size {
+                params.append_pair("pageSize", &page_size.to_string());
+            }
+        }
         Ok(url.to_string())
     }
     pub fn fetch(&self) -> Result<NewsApiResponse,NewsApiError>{
         let url :String = self.prepare_url()?;
-        println!("url : {}",&url);
-        let req = ureq::get(&url)
-        .set("Authorization", &self.api_key);
-        let req2 = ureq::get(&url)
+        let req = match &self.agent {
+            Some(agent) => agent.get(&url),
+            None => ureq::get(&url)
+        }
         .set("Authorization", &self.api_key);
-        println!("Second request : {:#?}",req2.call()?);
 
-        let response : NewsApiResponse = req.call()?.into_json()?;
-        //println!("{:?}",response);
-        match response.status.as_str() {
-            "ok" => return Ok(response),
-            _ => return Err(NewsApi::map_response_error(response.code))
-        } 
-        //todo!() 
+        let response : NewsApiResponse = match req.call() {
+            Ok(resp) => resp.into_json()?,
+            // errors come back with a 4xx status and a json body describing them
+            Err(ureq::Error::Status(_, resp)) => resp.into_json()?,
+            Err(e) => return Err(e.into())
+        };
+        NewsApi::check_response(response)
     }
     #[cfg(feature = "async")]
     pub async fn fetch_async(&self) -> Result<NewsApiResponse,NewsApiError>{
         use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, ACCEPT};
 
         let url = self.prepare_url()?;
-        /* dbg!(&url);  */
         let client = reqwest::Client::new();
         let request = client
-        .request(Method::GET,"https://newsapi.org/v2/everything?q=bitcoin&apiKey=16fbc4733a3d4a43b6da9a7a909bb1de")
+        .request(Method::GET,url)
         .header(AUTHORIZATION, &self.api_key)
         .header(CONTENT_TYPE, "application/json")
         .header(ACCEPT, "application/json")
         .build()
-        .map_err(|e| NewsApiError::AsyncRequestFailed(e))?;
-        
-        let test_request = reqwest::get("https://newsapi.org/v2/everything?q=bitcoin&apiKey=16fbc4733a3d4a43b6da9a7a909bb1de")
-        .await
-        // each response is wrapped in a `Result` type
-        // we'll unwrap here for simplicity
-        .unwrap()
-        .text()
-        .await;
-    println!("{:?}", test_request);
+        .map_err(NewsApiError::AsyncRequestFailed)?;
 
         let response:NewsApiResponse = client
         .execute(request)
         .await?
         .json()
         .await
-        .map_err(|e| NewsApiError::AsyncRequestFailed(e))?;
-        println!("success {:?}",&response);
-        match response.status.as_str() {
-            "ok" => return Ok(response),
-            _ => return Err(NewsApi::map_response_error(response.code))
-        } 
+        .map_err(NewsApiError::AsyncRequestFailed)?;
+        NewsApi::check_response(response)
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -182,25 +601,30 @@ impl NewsApi {
     .await
     .map_err(|_| NewsApiError::BadRequest("failes converting response to json ."))?;    
 
-    match response.status.as_str() {
-        "ok" => return Ok(response),
-        _ => return Err(NewsApi::map_response_error(response.code)),
-    }
+    NewsApi::check_response(response)
     }
 
-    fn map_response_error(code : Option<String>) -> NewsApiError {
-        if let Some(code ) = code{
-            match code.as_str() {
-                "apiKeyDisabled" => NewsApiError::BadRequest("Your API key has been disabled."),
-                "apiKeyExhausted" => NewsApiError::BadRequest("Your API key has no more requests available."),
-                "apiKeyInvalid" => NewsApiError::BadRequest("Your API key hasn't been entered correctly. Double check it and try again."),
-                "parametersMissing" => NewsApiError::BadRequest("Required parameters are missnig from the request."),
-                "parameterInvalid" => NewsApiError::BadRequest("You've included a parameter in your request which is currently not supported"),
-                _ => NewsApiError::BadRequest("Unknown error")
-            }
-        }else{
-            NewsApiError::BadRequest("Unknown error")
+    fn check_response(response : NewsApiResponse) -> Result<NewsApiResponse,NewsApiError> {
+        match response.status.as_str() {
+            "ok" => Ok(response),
+            _ => Err(NewsApi::map_response_error(response.code,response.message))
         }
     }
 
+    fn map_response_error(code : Option<String>,message : Option<String>) -> NewsApiError {
+        let code = code.unwrap_or_else(|| "unknown".to_string());
+        // prefer the explanation sent by the api, it is more specific than ours
+        let message = message.unwrap_or_else(|| match code.as_str() {
+            "apiKeyDisabled" => "Your API key has been disabled.",
+            "apiKeyExhausted" => "Your API key has no more requests available.",
+            "apiKeyInvalid" => "Your API key hasn't been entered correctly. Double check it and try again.",
+            "apiKeyMissing" => "Your API key is missing from the request.",
+            "parametersMissing" => "Required parameters are missnig from the request.",
+            "parameterInvalid" => "You've included a parameter in your request which is currently not supported",
+            "rateLimited" => "You have been rate limited. Back off for a while before trying the request again.",
+            _ => "Unknown error"
+        }.to_string());
+        NewsApiError::Api { code, message }
+    }
+
 }
\ No newline at end of file